@@ -0,0 +1,52 @@
+use crate::error::AppResult;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// One mutating operation recorded in a repository's audit log
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditEntry {
+    /// Start time (Unix seconds)
+    pub timestamp: i64,
+    /// Login name of the user who ran the operation
+    pub user: String,
+    pub operation: String,
+    pub repo: String,
+    pub worktree: Option<String>,
+    pub args: Value,
+    pub success: bool,
+    pub error: Option<String>,
+    pub duration_ms: u64,
+}
+
+/// Filters for querying the audit log; unset fields match everything
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct AuditQuery {
+    pub operation: Option<String>,
+    pub worktree: Option<String>,
+    pub user: Option<String>,
+    /// Only entries at or after this time (Unix seconds)
+    pub since: Option<i64>,
+    /// Only entries before this time (Unix seconds)
+    pub until: Option<i64>,
+    pub failed_only: bool,
+    /// Maximum number of entries, newest first
+    pub limit: Option<usize>,
+}
+
+#[tauri::command]
+pub async fn query_audit_log(
+    repo_path: String,
+    query: Option<AuditQuery>,
+) -> AppResult<Vec<AuditEntry>> {
+    crate::git::audit_log::query(&repo_path, &query.unwrap_or_default())
+}
+
+#[tauri::command]
+pub async fn export_audit_log(
+    repo_path: String,
+    destination: String,
+    query: Option<AuditQuery>,
+) -> AppResult<usize> {
+    crate::git::audit_log::export(&repo_path, &destination, &query.unwrap_or_default())
+}
@@ -0,0 +1,21 @@
+use crate::error::AppResult;
+use serde::Serialize;
+
+/// Event sent to the UI when git or ssh needs a credential or an answer
+pub const AUTH_PROMPT_EVENT: &str = "auth-prompt";
+
+/// A question from git or ssh, e.g. a password or an unknown host key
+#[derive(Debug, Clone, Serialize)]
+pub struct AuthPrompt {
+    pub id: u64,
+    /// Prompt text as git or ssh wrote it
+    pub prompt: String,
+    /// The answer should not be shown while typing
+    pub secret: bool,
+}
+
+/// Answers a prompt; `None` cancels it, failing the operation that asked
+#[tauri::command]
+pub async fn respond_auth_prompt(id: u64, response: Option<String>) -> AppResult<()> {
+    crate::git::askpass::respond(id, response)
+}
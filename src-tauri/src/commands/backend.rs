@@ -0,0 +1,25 @@
+use crate::error::AppResult;
+use serde::{Deserialize, Serialize};
+
+/// How local operations (stage, unstage, commit, checkout, lock and unlock)
+/// are carried out
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GitBackend {
+    /// Run the git CLI, which also runs hooks and signs commits as configured
+    #[default]
+    Cli,
+    /// Use libgit2 in process, without starting git. Hooks are not run, and
+    /// signed commits, `checkout --merge` and sparse worktrees are refused.
+    Libgit2,
+}
+
+#[tauri::command]
+pub async fn git_backend() -> AppResult<GitBackend> {
+    Ok(crate::git::backend::selected())
+}
+
+#[tauri::command]
+pub async fn set_git_backend(backend: GitBackend) -> AppResult<()> {
+    crate::git::backend::select(backend)
+}
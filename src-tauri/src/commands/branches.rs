@@ -1,11 +1,59 @@
 use crate::error::AppResult;
-use serde::Serialize;
+use crate::git::{audit_log, undo_journal};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::path::Path;
 
 #[derive(Debug, Serialize)]
 pub struct BranchInfo {
     pub name: String,
     pub is_remote: bool,
     pub is_current: bool,
+    pub upstream: Option<String>,
+    pub upstream_gone: bool,
+    pub ahead: u32,
+    pub behind: u32,
+    pub last_commit_summary: Option<String>,
+    pub last_commit_time: Option<i64>,
+    pub worktree_path: Option<String>,
+}
+
+/// How checkout treats uncommitted changes in the worktree
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LocalChangesMode {
+    /// Refuse if changes would be overwritten; other changes are carried over
+    #[default]
+    Refuse,
+    /// Carry all changes over, merging conflicting files (`git checkout --merge`)
+    Merge,
+    /// Stash all changes, including untracked files, before switching
+    Stash,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CheckoutResult {
+    pub branch: String,
+    pub created_from: Option<String>,
+    pub stashed: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BranchCleanupCandidate {
+    pub name: String,
+    pub merged: bool,
+    pub upstream_gone: bool,
+    pub stale: bool,
+    pub last_commit_time: Option<i64>,
+    pub worktree_path: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BranchCleanupResult {
+    pub name: String,
+    pub removed_worktree: Option<String>,
+    pub deleted: bool,
+    pub error: Option<String>,
 }
 
 #[tauri::command]
@@ -14,6 +62,234 @@ pub async fn list_branches(repo_path: String) -> AppResult<Vec<BranchInfo>> {
 }
 
 #[tauri::command]
-pub async fn checkout_branch(worktree_path: String, branch: String) -> AppResult<()> {
-    crate::git::operations::checkout(&worktree_path, &branch)
+pub async fn checkout_branch(
+    worktree_path: String,
+    branch: String,
+    local_changes: Option<LocalChangesMode>,
+) -> AppResult<CheckoutResult> {
+    let local_changes = local_changes.unwrap_or_default();
+    audit_log::record(
+        &worktree_path,
+        Some(Path::new(&worktree_path)),
+        "checkout_branch",
+        json!({ "branch": branch, "local_changes": local_changes }),
+        || {
+            undo_journal::journal(
+                &worktree_path,
+                Some(Path::new(&worktree_path)),
+                "checkout_branch",
+                || crate::git::operations::checkout(&worktree_path, &branch, local_changes),
+            )
+        },
+    )
+}
+
+#[tauri::command]
+pub async fn create_branch(
+    repo_path: String,
+    name: String,
+    start_point: Option<String>,
+) -> AppResult<()> {
+    audit_log::record(
+        &repo_path,
+        None,
+        "create_branch",
+        json!({ "name": name, "start_point": start_point }),
+        || {
+            undo_journal::journal(&repo_path, None, "create_branch", || {
+                crate::git::branch_manager::create_branch(&repo_path, &name, start_point.as_deref())
+            })
+        },
+    )
+}
+
+#[tauri::command]
+pub async fn rename_branch(
+    repo_path: String,
+    old_name: String,
+    new_name: String,
+    force: bool,
+) -> AppResult<()> {
+    audit_log::record(
+        &repo_path,
+        None,
+        "rename_branch",
+        json!({ "old_name": old_name, "new_name": new_name, "force": force }),
+        || {
+            undo_journal::journal(&repo_path, None, "rename_branch", || {
+                crate::git::branch_manager::rename_branch(&repo_path, &old_name, &new_name, force)
+            })
+        },
+    )
+}
+
+#[tauri::command]
+pub async fn delete_branch(repo_path: String, name: String, force: bool) -> AppResult<()> {
+    audit_log::record(
+        &repo_path,
+        None,
+        "delete_branch",
+        json!({ "name": name, "force": force }),
+        || {
+            undo_journal::journal(&repo_path, None, "delete_branch", || {
+                crate::git::branch_manager::delete_branch(&repo_path, &name, force)
+            })
+        },
+    )
+}
+
+#[tauri::command]
+pub async fn set_branch_upstream(
+    repo_path: String,
+    name: String,
+    upstream: String,
+) -> AppResult<()> {
+    audit_log::record(
+        &repo_path,
+        None,
+        "set_branch_upstream",
+        json!({ "name": name, "upstream": upstream }),
+        || crate::git::branch_manager::set_upstream(&repo_path, &name, &upstream),
+    )
+}
+
+#[tauri::command]
+pub async fn unset_branch_upstream(repo_path: String, name: String) -> AppResult<()> {
+    audit_log::record(
+        &repo_path,
+        None,
+        "unset_branch_upstream",
+        json!({ "name": name }),
+        || crate::git::branch_manager::unset_upstream(&repo_path, &name),
+    )
+}
+
+#[tauri::command]
+pub async fn analyze_branches(
+    repo_path: String,
+    base: String,
+    stale_days: u32,
+) -> AppResult<Vec<BranchCleanupCandidate>> {
+    crate::git::branch_manager::analyze_branches(&repo_path, &base, stale_days)
+}
+
+#[tauri::command]
+pub async fn cleanup_branches(
+    repo_path: String,
+    branches: Vec<String>,
+    base: String,
+    delete_unmerged: bool,
+    discard_changes: bool,
+    dry_run: bool,
+) -> AppResult<Vec<BranchCleanupResult>> {
+    audit_log::record(
+        &repo_path,
+        None,
+        "cleanup_branches",
+        json!({
+            "branches": branches,
+            "base": base,
+            "delete_unmerged": delete_unmerged,
+            "discard_changes": discard_changes,
+            "dry_run": dry_run,
+        }),
+        || {
+            undo_journal::journal(&repo_path, None, "cleanup_branches", || {
+                crate::git::branch_manager::cleanup_branches(
+                    &repo_path,
+                    &branches,
+                    &base,
+                    delete_unmerged,
+                    discard_changes,
+                    dry_run,
+                )
+            })
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::audit::AuditQuery;
+    use std::fs;
+    use std::process::Command as StdCommand;
+    use tempfile::TempDir;
+
+    fn create_test_repo() -> TempDir {
+        let temp_dir = TempDir::new().unwrap();
+        let repo_path = temp_dir.path();
+
+        StdCommand::new("git")
+            .current_dir(repo_path)
+            .args(["init"])
+            .output()
+            .unwrap();
+
+        StdCommand::new("git")
+            .current_dir(repo_path)
+            .args(["config", "user.email", "test@test.com"])
+            .output()
+            .unwrap();
+
+        StdCommand::new("git")
+            .current_dir(repo_path)
+            .args(["config", "user.name", "Test User"])
+            .output()
+            .unwrap();
+
+        fs::write(repo_path.join("README.md"), "# Test Repository").unwrap();
+
+        StdCommand::new("git")
+            .current_dir(repo_path)
+            .args(["add", "."])
+            .output()
+            .unwrap();
+
+        StdCommand::new("git")
+            .current_dir(repo_path)
+            .args(["commit", "-m", "Initial commit"])
+            .output()
+            .unwrap();
+
+        temp_dir
+    }
+
+    #[tokio::test]
+    async fn test_delete_and_cleanup_are_audited() {
+        let temp_dir = create_test_repo();
+        let repo_path = temp_dir.path().to_str().unwrap().to_string();
+        for branch in ["deleted", "cleaned"] {
+            StdCommand::new("git")
+                .current_dir(&repo_path)
+                .args(["branch", branch])
+                .output()
+                .unwrap();
+        }
+        let base = crate::git::operations::status(&repo_path)
+            .unwrap()
+            .branch
+            .unwrap();
+
+        delete_branch(repo_path.clone(), "deleted".to_string(), false)
+            .await
+            .unwrap();
+        cleanup_branches(
+            repo_path.clone(),
+            vec!["cleaned".to_string()],
+            base,
+            false,
+            false,
+            false,
+        )
+        .await
+        .unwrap();
+
+        let entries = crate::git::audit_log::query(&repo_path, &AuditQuery::default()).unwrap();
+        let operations: Vec<&str> = entries.iter().map(|e| e.operation.as_str()).collect();
+        assert_eq!(operations, vec!["cleanup_branches", "delete_branch"]);
+        assert!(entries.iter().all(|e| e.success));
+        assert_eq!(entries[0].args["branches"][0], "cleaned");
+        assert_eq!(entries[1].args["name"], "deleted");
+    }
 }
@@ -0,0 +1,126 @@
+use crate::error::AppResult;
+use crate::git::audit_log;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::path::Path;
+
+/// Where a config value comes from, in increasing order of precedence
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConfigScope {
+    /// e.g. /etc/gitconfig
+    System,
+    /// ~/.gitconfig or ~/.config/git/config
+    Global,
+    /// The repository's config, shared by all its worktrees
+    Local,
+    /// The worktree's `config.worktree`
+    Worktree,
+    /// Passed in the environment of this process; read-only
+    Command,
+}
+
+impl ConfigScope {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ConfigScope::System => "system",
+            ConfigScope::Global => "global",
+            ConfigScope::Local => "local",
+            ConfigScope::Worktree => "worktree",
+            ConfigScope::Command => "command",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ConfigEntry {
+    /// Name as git normalizes it, with section and key in lowercase
+    pub name: String,
+    /// `None` for a bare key without `=`, which git reads as true
+    pub value: Option<String>,
+    pub scope: ConfigScope,
+}
+
+#[tauri::command]
+pub async fn list_config(
+    worktree_path: String,
+    scope: Option<ConfigScope>,
+) -> AppResult<Vec<ConfigEntry>> {
+    crate::git::config_manager::list_config(&worktree_path, scope)
+}
+
+#[tauri::command]
+pub async fn get_config(worktree_path: String, name: String) -> AppResult<Option<ConfigEntry>> {
+    crate::git::config_manager::get_config(&worktree_path, &name)
+}
+
+#[tauri::command]
+pub async fn set_config(
+    worktree_path: String,
+    name: String,
+    value: String,
+    scope: ConfigScope,
+) -> AppResult<()> {
+    audit_log::record(
+        &worktree_path,
+        Some(Path::new(&worktree_path)),
+        "set_config",
+        // Values may hold credentials (e.g. `http.extraHeader`), so only the
+        // name is logged
+        json!({ "name": name, "scope": scope }),
+        || crate::git::config_manager::set_config(&worktree_path, &name, &value, scope),
+    )
+}
+
+#[tauri::command]
+pub async fn unset_config(
+    worktree_path: String,
+    name: String,
+    scope: ConfigScope,
+) -> AppResult<()> {
+    audit_log::record(
+        &worktree_path,
+        Some(Path::new(&worktree_path)),
+        "unset_config",
+        json!({ "name": name, "scope": scope }),
+        || crate::git::config_manager::unset_config(&worktree_path, &name, scope),
+    )
+}
+
+#[tauri::command]
+pub async fn worktree_config_enabled(worktree_path: String) -> AppResult<bool> {
+    crate::git::config_manager::worktree_config_enabled(&worktree_path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::audit::AuditQuery;
+    use std::process::Command as StdCommand;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_set_config_does_not_log_value() {
+        let temp_dir = TempDir::new().unwrap();
+        StdCommand::new("git")
+            .current_dir(temp_dir.path())
+            .args(["init"])
+            .output()
+            .unwrap();
+        let repo_path = temp_dir.path().to_str().unwrap().to_string();
+
+        set_config(
+            repo_path.clone(),
+            "http.extraHeader".to_string(),
+            "Authorization: Bearer secret".to_string(),
+            ConfigScope::Local,
+        )
+        .await
+        .unwrap();
+
+        let entries = crate::git::audit_log::query(&repo_path, &AuditQuery::default()).unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].args["name"], "http.extraHeader");
+        assert!(!entries[0].args.to_string().contains("secret"));
+    }
+}
@@ -0,0 +1,53 @@
+use crate::error::AppResult;
+use serde::Serialize;
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+pub struct GitVersion {
+    pub major: u32,
+    pub minor: u32,
+    pub patch: u32,
+}
+
+impl fmt::Display for GitVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+/// Features that need a newer git than the minimum supported version
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct GitCapabilities {
+    /// `git worktree repair`, used to repair links and move worktrees with
+    /// submodules
+    pub worktree_repair: bool,
+    /// Cone-mode `git sparse-checkout set` and `add`
+    pub sparse_checkout: bool,
+    /// Reporting the scope of config values
+    pub config_scopes: bool,
+}
+
+/// The git executable the app runs, or why there is none
+#[derive(Debug, Serialize)]
+pub struct GitExecutableInfo {
+    pub path: Option<String>,
+    /// Path set in the settings, if any; otherwise git is looked up in
+    /// `PATH` and the usual install locations
+    pub configured_path: Option<String>,
+    pub version: Option<GitVersion>,
+    pub minimum_version: GitVersion,
+    pub capabilities: GitCapabilities,
+    /// Why git cannot be used, e.g. it was not found or is too old
+    pub error: Option<String>,
+}
+
+#[tauri::command]
+pub async fn git_executable_info() -> AppResult<GitExecutableInfo> {
+    Ok(crate::git::executable::info())
+}
+
+/// Sets the git executable to use, or with `None` goes back to looking it up
+#[tauri::command]
+pub async fn set_git_executable(path: Option<String>) -> AppResult<GitExecutableInfo> {
+    crate::git::executable::set_configured_path(path.as_deref())
+}
@@ -1,13 +1,32 @@
+use crate::commands::paths::PathArg;
 use crate::error::AppResult;
-use serde::Serialize;
+use crate::git::paths::non_utf8_bytes;
+use crate::git::{audit_log, undo_journal};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::path::{Path, PathBuf};
 
 #[derive(Debug, Serialize)]
 pub struct FileStatus {
     pub path: String,
+    /// Raw bytes of `path` when it is not valid UTF-8
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path_bytes: Option<Vec<u8>>,
     pub status: String,
     pub staged: bool,
 }
 
+impl FileStatus {
+    pub fn new(path: &Path, status: &str, staged: bool) -> Self {
+        Self {
+            path: path.to_string_lossy().to_string(),
+            path_bytes: non_utf8_bytes(path),
+            status: status.to_string(),
+            staged,
+        }
+    }
+}
+
 #[derive(Debug, Serialize)]
 pub struct GitStatusResult {
     pub branch: Option<String>,
@@ -16,37 +35,260 @@ pub struct GitStatusResult {
     pub behind: u32,
 }
 
+/// How far `git_reset` resets: the branch only, also the index, or also the
+/// working tree
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ResetMode {
+    Soft,
+    Mixed,
+    Hard,
+}
+
+impl ResetMode {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ResetMode::Soft => "soft",
+            ResetMode::Mixed => "mixed",
+            ResetMode::Hard => "hard",
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct ResetResult {
+    /// Commit HEAD pointed at before the reset
+    pub previous_head: String,
+    /// Trash entry holding the discarded state, for hard resets
+    pub snapshot: Option<String>,
+}
+
+/// Options for `git_cherry_pick`
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CherryPickOptions {
+    /// Append "(cherry picked from commit ...)" to each message (`-x`)
+    pub record_origin: bool,
+    /// Apply the changes to the index and working tree without committing
+    pub no_commit: bool,
+}
+
+/// Outcome of a command that applies commits one at a time, such as revert
+/// or cherry-pick
+#[derive(Debug, Serialize)]
+pub struct SequencerResult {
+    /// `false` if it stopped on conflicts, which `status` lists as `conflicted`
+    pub completed: bool,
+    pub status: GitStatusResult,
+}
+
 #[tauri::command]
 pub async fn git_fetch(worktree_path: String) -> AppResult<String> {
-    crate::git::operations::fetch(&worktree_path)
+    audit_log::record(
+        &worktree_path,
+        Some(Path::new(&worktree_path)),
+        "git_fetch",
+        json!({}),
+        || crate::git::operations::fetch(&worktree_path),
+    )
 }
 
 #[tauri::command]
 pub async fn git_pull(worktree_path: String) -> AppResult<String> {
-    crate::git::operations::pull(&worktree_path)
+    audit_log::record(
+        &worktree_path,
+        Some(Path::new(&worktree_path)),
+        "git_pull",
+        json!({}),
+        || {
+            undo_journal::journal(
+                &worktree_path,
+                Some(Path::new(&worktree_path)),
+                "git_pull",
+                || crate::git::operations::pull(&worktree_path),
+            )
+        },
+    )
 }
 
 #[tauri::command]
 pub async fn git_push(worktree_path: String) -> AppResult<String> {
-    crate::git::operations::push(&worktree_path)
+    audit_log::record(
+        &worktree_path,
+        Some(Path::new(&worktree_path)),
+        "git_push",
+        json!({}),
+        || crate::git::operations::push(&worktree_path),
+    )
 }
 
 #[tauri::command]
-pub async fn git_status(worktree_path: String) -> AppResult<GitStatusResult> {
-    crate::git::operations::status(&worktree_path)
+pub async fn git_status(worktree_path: PathArg) -> AppResult<GitStatusResult> {
+    crate::git::operations::status(PathBuf::from(worktree_path))
 }
 
 #[tauri::command]
 pub async fn git_commit(worktree_path: String, message: String) -> AppResult<String> {
-    crate::git::operations::commit(&worktree_path, &message)
+    audit_log::record(
+        &worktree_path,
+        Some(Path::new(&worktree_path)),
+        "git_commit",
+        json!({ "message": message }),
+        || {
+            undo_journal::journal(
+                &worktree_path,
+                Some(Path::new(&worktree_path)),
+                "git_commit",
+                || crate::git::operations::commit(&worktree_path, &message),
+            )
+        },
+    )
+}
+
+#[tauri::command]
+pub async fn git_stage(worktree_path: PathArg, file_path: PathArg) -> AppResult<()> {
+    let worktree_path = PathBuf::from(worktree_path);
+    let file_path = PathBuf::from(file_path);
+    audit_log::record(
+        &worktree_path.to_string_lossy(),
+        Some(&worktree_path),
+        "git_stage",
+        json!({ "file_path": file_path.display().to_string() }),
+        || crate::git::operations::stage(&worktree_path, &file_path),
+    )
+}
+
+#[tauri::command]
+pub async fn git_unstage(worktree_path: PathArg, file_path: PathArg) -> AppResult<()> {
+    let worktree_path = PathBuf::from(worktree_path);
+    let file_path = PathBuf::from(file_path);
+    audit_log::record(
+        &worktree_path.to_string_lossy(),
+        Some(&worktree_path),
+        "git_unstage",
+        json!({ "file_path": file_path.display().to_string() }),
+        || crate::git::operations::unstage(&worktree_path, &file_path),
+    )
+}
+
+#[tauri::command]
+pub async fn git_reset(
+    worktree_path: String,
+    revision: String,
+    mode: ResetMode,
+) -> AppResult<ResetResult> {
+    let operation = format!("git_reset_{}", mode.as_str());
+    audit_log::record(
+        &worktree_path,
+        Some(Path::new(&worktree_path)),
+        "git_reset",
+        json!({ "revision": revision, "mode": mode }),
+        || {
+            undo_journal::journal(
+                &worktree_path,
+                Some(Path::new(&worktree_path)),
+                &operation,
+                || crate::git::operations::reset(&worktree_path, &revision, mode),
+            )
+        },
+    )
+}
+
+#[tauri::command]
+pub async fn git_revert(worktree_path: String, commits: Vec<String>) -> AppResult<SequencerResult> {
+    audit_log::record(
+        &worktree_path,
+        Some(Path::new(&worktree_path)),
+        "git_revert",
+        json!({ "commits": commits }),
+        || {
+            undo_journal::journal(
+                &worktree_path,
+                Some(Path::new(&worktree_path)),
+                "git_revert",
+                || crate::git::operations::revert(&worktree_path, &commits),
+            )
+        },
+    )
+}
+
+#[tauri::command]
+pub async fn git_revert_continue(worktree_path: String) -> AppResult<SequencerResult> {
+    audit_log::record(
+        &worktree_path,
+        Some(Path::new(&worktree_path)),
+        "git_revert_continue",
+        json!({}),
+        || {
+            undo_journal::journal(
+                &worktree_path,
+                Some(Path::new(&worktree_path)),
+                "git_revert",
+                || crate::git::operations::revert_continue(&worktree_path),
+            )
+        },
+    )
+}
+
+#[tauri::command]
+pub async fn git_revert_abort(worktree_path: String) -> AppResult<()> {
+    audit_log::record(
+        &worktree_path,
+        Some(Path::new(&worktree_path)),
+        "git_revert_abort",
+        json!({}),
+        || crate::git::operations::revert_abort(&worktree_path),
+    )
+}
+
+#[tauri::command]
+pub async fn git_cherry_pick(
+    worktree_path: String,
+    commits: Vec<String>,
+    options: Option<CherryPickOptions>,
+) -> AppResult<SequencerResult> {
+    let options = options.unwrap_or_default();
+    audit_log::record(
+        &worktree_path,
+        Some(Path::new(&worktree_path)),
+        "git_cherry_pick",
+        json!({ "commits": commits, "options": options }),
+        || {
+            undo_journal::journal(
+                &worktree_path,
+                Some(Path::new(&worktree_path)),
+                "git_cherry_pick",
+                || crate::git::operations::cherry_pick(&worktree_path, &commits, options),
+            )
+        },
+    )
 }
 
 #[tauri::command]
-pub async fn git_stage(worktree_path: String, file_path: String) -> AppResult<()> {
-    crate::git::operations::stage(&worktree_path, &file_path)
+pub async fn git_cherry_pick_continue(worktree_path: String) -> AppResult<SequencerResult> {
+    audit_log::record(
+        &worktree_path,
+        Some(Path::new(&worktree_path)),
+        "git_cherry_pick_continue",
+        json!({}),
+        || {
+            undo_journal::journal(
+                &worktree_path,
+                Some(Path::new(&worktree_path)),
+                "git_cherry_pick",
+                || crate::git::operations::cherry_pick_continue(&worktree_path),
+            )
+        },
+    )
 }
 
 #[tauri::command]
-pub async fn git_unstage(worktree_path: String, file_path: String) -> AppResult<()> {
-    crate::git::operations::unstage(&worktree_path, &file_path)
+pub async fn git_cherry_pick_abort(worktree_path: String) -> AppResult<()> {
+    audit_log::record(
+        &worktree_path,
+        Some(Path::new(&worktree_path)),
+        "git_cherry_pick_abort",
+        json!({}),
+        || crate::git::operations::cherry_pick_abort(&worktree_path),
+    )
 }
@@ -5,7 +5,21 @@
 //! the main thread from being blocked during git operations which may involve
 //! file I/O or subprocess execution.
 
+pub mod audit;
+pub mod auth;
+pub mod backend;
 pub mod branches;
+pub mod config;
+pub mod executable;
 pub mod git_ops;
+pub mod paths;
+pub mod rebase;
+pub mod reflog;
+pub mod remotes;
 pub mod repository;
+pub mod sparse;
+pub mod stash;
+pub mod submodules;
+pub mod trash;
+pub mod undo;
 pub mod worktree;
@@ -0,0 +1,23 @@
+use crate::git::paths::path_from_bytes;
+use serde::Deserialize;
+use std::path::PathBuf;
+
+/// A path passed in from the frontend.
+///
+/// Ordinary paths are sent as strings. Paths that are not valid UTF-8 are
+/// sent back as the raw bytes a model reported in its `path_bytes` field.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum PathArg {
+    Text(String),
+    Bytes(Vec<u8>),
+}
+
+impl From<PathArg> for PathBuf {
+    fn from(arg: PathArg) -> Self {
+        match arg {
+            PathArg::Text(text) => PathBuf::from(text),
+            PathArg::Bytes(bytes) => path_from_bytes(&bytes),
+        }
+    }
+}
@@ -0,0 +1,119 @@
+use crate::commands::git_ops::GitStatusResult;
+use crate::error::AppResult;
+use crate::git::{audit_log, undo_journal};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::path::Path;
+
+/// What an interactive rebase does with a commit
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RebaseAction {
+    Pick,
+    /// Meld into the previous commit, combining both messages
+    Squash,
+    /// Meld into the previous commit, keeping its message
+    Fixup,
+    /// Pick with a new message
+    Reword,
+    Drop,
+    /// Pick, then stop so the commit can be amended
+    Edit,
+}
+
+/// One line of a rebase plan
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RebaseStep {
+    pub action: RebaseAction,
+    pub oid: String,
+    /// First line of the original message, for display
+    #[serde(default)]
+    pub summary: String,
+    /// New message for the resulting commit; used by pick, reword, squash
+    /// and fixup
+    #[serde(default)]
+    pub message: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RebaseState {
+    Completed,
+    /// Stopped at an `edit` step, or because a step failed
+    Stopped,
+    /// Stopped on conflicts, which `status` lists as `conflicted`
+    Conflicts,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RebaseProgress {
+    pub state: RebaseState,
+    /// Todo lines done and in total, counting the internal ones that apply
+    /// new messages
+    pub done: usize,
+    pub total: usize,
+    /// Commit the rebase stopped at
+    pub stopped_at: Option<String>,
+    pub status: GitStatusResult,
+}
+
+#[tauri::command]
+pub async fn rebase_plan(worktree_path: String, upstream: String) -> AppResult<Vec<RebaseStep>> {
+    crate::git::rebase_manager::plan(&worktree_path, &upstream)
+}
+
+#[tauri::command]
+pub async fn rebase_execute(
+    worktree_path: String,
+    upstream: String,
+    plan: Vec<RebaseStep>,
+) -> AppResult<RebaseProgress> {
+    audit_log::record(
+        &worktree_path,
+        Some(Path::new(&worktree_path)),
+        "rebase_execute",
+        json!({ "upstream": upstream, "plan": plan }),
+        || {
+            undo_journal::journal(
+                &worktree_path,
+                Some(Path::new(&worktree_path)),
+                "rebase_execute",
+                || crate::git::rebase_manager::execute(&worktree_path, &upstream, &plan),
+            )
+        },
+    )
+}
+
+#[tauri::command]
+pub async fn rebase_progress(worktree_path: String) -> AppResult<Option<RebaseProgress>> {
+    crate::git::rebase_manager::progress(&worktree_path)
+}
+
+#[tauri::command]
+pub async fn rebase_continue(worktree_path: String) -> AppResult<RebaseProgress> {
+    audit_log::record(
+        &worktree_path,
+        Some(Path::new(&worktree_path)),
+        "rebase_continue",
+        json!({}),
+        || {
+            undo_journal::journal(
+                &worktree_path,
+                Some(Path::new(&worktree_path)),
+                "rebase_continue",
+                || crate::git::rebase_manager::continue_rebase(&worktree_path),
+            )
+        },
+    )
+}
+
+#[tauri::command]
+pub async fn rebase_abort(worktree_path: String) -> AppResult<()> {
+    audit_log::record(
+        &worktree_path,
+        Some(Path::new(&worktree_path)),
+        "rebase_abort",
+        json!({}),
+        || crate::git::rebase_manager::abort(&worktree_path),
+    )
+}
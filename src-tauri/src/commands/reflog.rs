@@ -0,0 +1,83 @@
+use crate::commands::paths::PathArg;
+use crate::error::AppResult;
+use crate::git::{audit_log, undo_journal};
+use serde::Serialize;
+use serde_json::json;
+use std::path::PathBuf;
+
+#[derive(Debug, Serialize)]
+pub struct ReflogEntry {
+    /// Position in the reflog, newest first, as in `HEAD@{index}`
+    pub index: usize,
+    /// Previous value, or `None` when the ref was created
+    pub old_oid: Option<String>,
+    pub new_oid: String,
+    pub message: Option<String>,
+    pub committer: String,
+    /// When the ref was updated (Unix seconds)
+    pub time: i64,
+}
+
+#[tauri::command]
+pub async fn read_reflog(
+    worktree_path: PathArg,
+    reference: Option<String>,
+    limit: Option<usize>,
+) -> AppResult<Vec<ReflogEntry>> {
+    crate::git::reflog_manager::read_reflog(
+        PathBuf::from(worktree_path),
+        reference.as_deref(),
+        limit,
+    )
+}
+
+#[tauri::command]
+pub async fn create_branch_from_reflog(
+    worktree_path: String,
+    reference: Option<String>,
+    index: usize,
+    name: String,
+) -> AppResult<()> {
+    audit_log::record(
+        &worktree_path,
+        None,
+        "create_branch_from_reflog",
+        json!({ "reference": reference, "index": index, "name": name }),
+        || {
+            undo_journal::journal(&worktree_path, None, "create_branch", || {
+                crate::git::reflog_manager::create_branch_from_reflog(
+                    &worktree_path,
+                    reference.as_deref(),
+                    index,
+                    &name,
+                )
+            })
+        },
+    )
+}
+
+#[tauri::command]
+pub async fn add_worktree_from_reflog(
+    repo_path: String,
+    reference: Option<String>,
+    index: usize,
+    worktree_path: PathArg,
+    branch: String,
+) -> AppResult<()> {
+    let worktree_path = PathBuf::from(worktree_path);
+    audit_log::record(
+        &repo_path,
+        Some(&worktree_path),
+        "add_worktree",
+        json!({ "reference": reference, "index": index, "branch": branch }),
+        || {
+            crate::git::reflog_manager::add_worktree_from_reflog(
+                &repo_path,
+                reference.as_deref(),
+                index,
+                &worktree_path,
+                &branch,
+            )
+        },
+    )
+}
@@ -0,0 +1,102 @@
+use crate::error::AppResult;
+use crate::git::audit_log;
+use crate::git::remote_manager::strip_userinfo;
+use serde::Serialize;
+use serde_json::json;
+
+#[derive(Debug, Serialize)]
+pub struct RemoteInfo {
+    pub name: String,
+    pub fetch_url: Option<String>,
+    /// Separate push URL, or `None` if pushes go to `fetch_url`
+    pub push_url: Option<String>,
+    pub fetch_refspecs: Vec<String>,
+}
+
+/// A remote-tracking branch, as of the last fetch
+#[derive(Debug, Serialize)]
+pub struct RemoteBranch {
+    /// Branch name on the remote, without the remote prefix
+    pub name: String,
+    pub oid: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RemoteBranches {
+    /// Default branch of the remote, if `refs/remotes/<remote>/HEAD` is set
+    pub head: Option<String>,
+    pub branches: Vec<RemoteBranch>,
+}
+
+#[tauri::command]
+pub async fn list_remotes(repo_path: String) -> AppResult<Vec<RemoteInfo>> {
+    crate::git::remote_manager::list_remotes(&repo_path)
+}
+
+#[tauri::command]
+pub async fn add_remote(repo_path: String, name: String, url: String) -> AppResult<()> {
+    audit_log::record(
+        &repo_path,
+        None,
+        "add_remote",
+        json!({ "name": name, "url": strip_userinfo(&url) }),
+        || crate::git::remote_manager::add_remote(&repo_path, &name, &url),
+    )
+}
+
+#[tauri::command]
+pub async fn rename_remote(
+    repo_path: String,
+    old_name: String,
+    new_name: String,
+) -> AppResult<Vec<String>> {
+    audit_log::record(
+        &repo_path,
+        None,
+        "rename_remote",
+        json!({ "old_name": old_name, "new_name": new_name }),
+        || crate::git::remote_manager::rename_remote(&repo_path, &old_name, &new_name),
+    )
+}
+
+#[tauri::command]
+pub async fn remove_remote(repo_path: String, name: String) -> AppResult<()> {
+    audit_log::record(
+        &repo_path,
+        None,
+        "remove_remote",
+        json!({ "name": name }),
+        || crate::git::remote_manager::remove_remote(&repo_path, &name),
+    )
+}
+
+#[tauri::command]
+pub async fn set_remote_url(repo_path: String, name: String, url: String) -> AppResult<()> {
+    audit_log::record(
+        &repo_path,
+        None,
+        "set_remote_url",
+        json!({ "name": name, "url": strip_userinfo(&url) }),
+        || crate::git::remote_manager::set_remote_url(&repo_path, &name, &url),
+    )
+}
+
+#[tauri::command]
+pub async fn set_remote_push_url(
+    repo_path: String,
+    name: String,
+    url: Option<String>,
+) -> AppResult<()> {
+    audit_log::record(
+        &repo_path,
+        None,
+        "set_remote_push_url",
+        json!({ "name": name, "url": url.as_deref().map(strip_userinfo) }),
+        || crate::git::remote_manager::set_remote_push_url(&repo_path, &name, url.as_deref()),
+    )
+}
+
+#[tauri::command]
+pub async fn list_remote_branches(repo_path: String, name: String) -> AppResult<RemoteBranches> {
+    crate::git::remote_manager::list_remote_branches(&repo_path, &name)
+}
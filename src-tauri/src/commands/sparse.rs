@@ -0,0 +1,100 @@
+use crate::error::AppResult;
+use crate::git::audit_log;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::path::Path;
+
+#[derive(Debug, Serialize)]
+pub struct SparseCheckoutInfo {
+    pub enabled: bool,
+    /// Patterns are directories (cone mode) rather than gitignore-style
+    pub cone: bool,
+    pub patterns: Vec<String>,
+}
+
+/// A named set of sparse checkout directories, saved per repository
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SparseProfile {
+    pub name: String,
+    pub patterns: Vec<String>,
+}
+
+#[tauri::command]
+pub async fn sparse_checkout_info(worktree_path: String) -> AppResult<SparseCheckoutInfo> {
+    crate::git::sparse_manager::sparse_checkout_info(&worktree_path)
+}
+
+#[tauri::command]
+pub async fn enable_sparse_checkout(worktree_path: String) -> AppResult<()> {
+    audit_log::record(
+        &worktree_path,
+        Some(Path::new(&worktree_path)),
+        "enable_sparse_checkout",
+        json!({}),
+        || crate::git::sparse_manager::enable(&worktree_path),
+    )
+}
+
+#[tauri::command]
+pub async fn disable_sparse_checkout(worktree_path: String) -> AppResult<()> {
+    audit_log::record(
+        &worktree_path,
+        Some(Path::new(&worktree_path)),
+        "disable_sparse_checkout",
+        json!({}),
+        || crate::git::sparse_manager::disable(&worktree_path),
+    )
+}
+
+#[tauri::command]
+pub async fn set_sparse_patterns(worktree_path: String, patterns: Vec<String>) -> AppResult<()> {
+    audit_log::record(
+        &worktree_path,
+        Some(Path::new(&worktree_path)),
+        "set_sparse_patterns",
+        json!({ "patterns": patterns }),
+        || crate::git::sparse_manager::set_patterns(&worktree_path, &patterns),
+    )
+}
+
+#[tauri::command]
+pub async fn add_sparse_patterns(worktree_path: String, patterns: Vec<String>) -> AppResult<()> {
+    audit_log::record(
+        &worktree_path,
+        Some(Path::new(&worktree_path)),
+        "add_sparse_patterns",
+        json!({ "patterns": patterns }),
+        || crate::git::sparse_manager::add_patterns(&worktree_path, &patterns),
+    )
+}
+
+#[tauri::command]
+pub async fn list_sparse_profiles(repo_path: String) -> AppResult<Vec<SparseProfile>> {
+    crate::git::sparse_manager::list_profiles(&repo_path)
+}
+
+#[tauri::command]
+pub async fn save_sparse_profile(
+    repo_path: String,
+    name: String,
+    patterns: Vec<String>,
+) -> AppResult<()> {
+    audit_log::record(
+        &repo_path,
+        None,
+        "save_sparse_profile",
+        json!({ "name": name, "patterns": patterns }),
+        || crate::git::sparse_manager::save_profile(&repo_path, &name, &patterns),
+    )
+}
+
+#[tauri::command]
+pub async fn delete_sparse_profile(repo_path: String, name: String) -> AppResult<()> {
+    audit_log::record(
+        &repo_path,
+        None,
+        "delete_sparse_profile",
+        json!({ "name": name }),
+        || crate::git::sparse_manager::delete_profile(&repo_path, &name),
+    )
+}
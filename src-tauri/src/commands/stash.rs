@@ -0,0 +1,93 @@
+use crate::commands::git_ops::FileStatus;
+use crate::error::AppResult;
+use crate::git::audit_log;
+use serde::Serialize;
+use serde_json::json;
+use std::path::Path;
+
+#[derive(Debug, Serialize)]
+pub struct StashInfo {
+    pub index: usize,
+    pub oid: String,
+    pub message: String,
+    pub branch: Option<String>,
+    pub worktree_path: Option<String>,
+    pub time: Option<i64>,
+}
+
+#[tauri::command]
+pub async fn list_stashes(worktree_path: String) -> AppResult<Vec<StashInfo>> {
+    crate::git::stash_manager::list_stashes(&worktree_path)
+}
+
+#[tauri::command]
+pub async fn push_stash(
+    worktree_path: String,
+    message: Option<String>,
+    include_untracked: bool,
+    keep_index: bool,
+    paths: Vec<String>,
+) -> AppResult<Option<StashInfo>> {
+    audit_log::record(
+        &worktree_path,
+        Some(Path::new(&worktree_path)),
+        "push_stash",
+        json!({
+            "message": message,
+            "include_untracked": include_untracked,
+            "keep_index": keep_index,
+            "paths": paths,
+        }),
+        || {
+            crate::git::stash_manager::push_stash(
+                &worktree_path,
+                message.as_deref(),
+                include_untracked,
+                keep_index,
+                &paths,
+            )
+        },
+    )
+}
+
+#[tauri::command]
+pub async fn apply_stash(
+    worktree_path: String,
+    index: usize,
+    restore_index: bool,
+) -> AppResult<()> {
+    audit_log::record(
+        &worktree_path,
+        Some(Path::new(&worktree_path)),
+        "apply_stash",
+        json!({ "index": index, "restore_index": restore_index }),
+        || crate::git::stash_manager::apply_stash(&worktree_path, index, restore_index),
+    )
+}
+
+#[tauri::command]
+pub async fn pop_stash(worktree_path: String, index: usize, restore_index: bool) -> AppResult<()> {
+    audit_log::record(
+        &worktree_path,
+        Some(Path::new(&worktree_path)),
+        "pop_stash",
+        json!({ "index": index, "restore_index": restore_index }),
+        || crate::git::stash_manager::pop_stash(&worktree_path, index, restore_index),
+    )
+}
+
+#[tauri::command]
+pub async fn drop_stash(worktree_path: String, index: usize) -> AppResult<()> {
+    audit_log::record(
+        &worktree_path,
+        Some(Path::new(&worktree_path)),
+        "drop_stash",
+        json!({ "index": index }),
+        || crate::git::stash_manager::drop_stash(&worktree_path, index),
+    )
+}
+
+#[tauri::command]
+pub async fn show_stash(worktree_path: String, index: usize) -> AppResult<Vec<FileStatus>> {
+    crate::git::stash_manager::show_stash(&worktree_path, index)
+}
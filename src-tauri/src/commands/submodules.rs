@@ -0,0 +1,79 @@
+use crate::error::AppResult;
+use crate::git::audit_log;
+use serde::Serialize;
+use serde_json::json;
+use std::path::Path;
+
+/// A submodule as seen from one worktree
+#[derive(Debug, Serialize)]
+pub struct SubmoduleInfo {
+    pub name: String,
+    /// Path relative to the worktree root
+    pub path: String,
+    pub url: Option<String>,
+    /// Commit recorded in the worktree's index
+    pub recorded_oid: Option<String>,
+    /// Commit checked out in the submodule, or `None` if it is not checked out
+    pub checked_out_oid: Option<String>,
+    /// Cloned into this worktree; a new worktree starts with empty
+    /// submodule directories even if the repository config registers them
+    pub initialized: bool,
+    /// The checked out commit differs from the recorded one
+    pub commit_changed: bool,
+    /// The submodule has uncommitted or untracked changes
+    pub dirty: bool,
+}
+
+#[tauri::command]
+pub async fn list_submodules(worktree_path: String) -> AppResult<Vec<SubmoduleInfo>> {
+    crate::git::submodule_manager::list_submodules(&worktree_path)
+}
+
+#[tauri::command]
+pub async fn init_submodules(worktree_path: String, paths: Vec<String>) -> AppResult<()> {
+    audit_log::record(
+        &worktree_path,
+        Some(Path::new(&worktree_path)),
+        "init_submodules",
+        json!({ "paths": paths }),
+        || crate::git::submodule_manager::init_submodules(&worktree_path, &paths),
+    )
+}
+
+#[tauri::command]
+pub async fn update_submodules(
+    worktree_path: String,
+    paths: Vec<String>,
+    init: bool,
+    recursive: bool,
+) -> AppResult<()> {
+    audit_log::record(
+        &worktree_path,
+        Some(Path::new(&worktree_path)),
+        "update_submodules",
+        json!({ "paths": paths, "init": init, "recursive": recursive }),
+        || {
+            crate::git::submodule_manager::update_submodules(
+                &worktree_path,
+                &paths,
+                init,
+                recursive,
+            )
+        },
+    )
+}
+
+#[tauri::command]
+pub async fn sync_submodules(
+    worktree_path: String,
+    paths: Vec<String>,
+    recursive: bool,
+) -> AppResult<()> {
+    audit_log::record(
+        &worktree_path,
+        Some(Path::new(&worktree_path)),
+        "sync_submodules",
+        json!({ "paths": paths, "recursive": recursive }),
+        || crate::git::submodule_manager::sync_submodules(&worktree_path, &paths, recursive),
+    )
+}
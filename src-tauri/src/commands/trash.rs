@@ -0,0 +1,47 @@
+use crate::error::AppResult;
+use crate::git::audit_log;
+use serde::Serialize;
+use serde_json::json;
+use std::path::Path;
+
+/// Snapshot of a worktree taken before it was force-removed
+#[derive(Debug, Serialize)]
+pub struct TrashEntry {
+    pub name: String,
+    pub path: String,
+    pub branch: Option<String>,
+    /// Commit the worktree had checked out
+    pub head: String,
+    pub time: i64,
+}
+
+#[tauri::command]
+pub async fn list_trash(repo_path: String) -> AppResult<Vec<TrashEntry>> {
+    crate::git::trash_manager::list_trash(&repo_path)
+}
+
+#[tauri::command]
+pub async fn restore_trash(
+    repo_path: String,
+    name: String,
+    worktree_path: String,
+) -> AppResult<()> {
+    audit_log::record(
+        &repo_path,
+        Some(Path::new(&worktree_path)),
+        "restore_trash",
+        json!({ "name": name }),
+        || crate::git::trash_manager::restore_trash(&repo_path, &name, &worktree_path),
+    )
+}
+
+#[tauri::command]
+pub async fn purge_trash(repo_path: String, retention_days: u32) -> AppResult<Vec<String>> {
+    audit_log::record(
+        &repo_path,
+        None,
+        "purge_trash",
+        json!({ "retention_days": retention_days }),
+        || crate::git::trash_manager::purge_trash(&repo_path, retention_days),
+    )
+}
@@ -0,0 +1,57 @@
+use crate::error::AppResult;
+use crate::git::audit_log;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+/// What a worktree's HEAD pointed at
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HeadState {
+    /// Checked out branch, or `None` if HEAD was detached
+    pub branch: Option<String>,
+    /// Commit HEAD resolved to, or `None` if the branch was unborn
+    pub oid: Option<String>,
+}
+
+/// A local branch moved, created or deleted by an operation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BranchChange {
+    pub name: String,
+    /// Tip before the operation, or `None` if it created the branch
+    pub before: Option<String>,
+    /// Tip after the operation, or `None` if it deleted the branch
+    pub after: Option<String>,
+}
+
+/// One undoable operation in a repository's undo journal
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UndoEntry {
+    pub operation: String,
+    /// When the operation finished (Unix seconds)
+    pub time: i64,
+    /// Worktree whose HEAD the operation could move
+    pub worktree: Option<String>,
+    pub head_before: Option<HeadState>,
+    pub head_after: Option<HeadState>,
+    pub branches: Vec<BranchChange>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct UndoResult {
+    pub operation: String,
+    pub head_restored: bool,
+    pub restored_branches: Vec<String>,
+    /// Branches left alone because they are checked out in another worktree
+    pub skipped_branches: Vec<String>,
+}
+
+#[tauri::command]
+pub async fn last_undo_entry(repo_path: String) -> AppResult<Option<UndoEntry>> {
+    crate::git::undo_journal::last_entry(&repo_path)
+}
+
+#[tauri::command]
+pub async fn undo_last_operation(repo_path: String) -> AppResult<UndoResult> {
+    audit_log::record(&repo_path, None, "undo_last_operation", json!({}), || {
+        crate::git::undo_journal::undo_last(&repo_path)
+    })
+}
@@ -1,14 +1,69 @@
+use crate::commands::paths::PathArg;
 use crate::error::AppResult;
-use serde::Serialize;
+use crate::git::{audit_log, undo_journal};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::path::PathBuf;
 
 #[derive(Debug, Serialize)]
 pub struct WorktreeInfo {
     pub path: String,
+    /// Raw bytes of `path` when it is not valid UTF-8
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path_bytes: Option<Vec<u8>>,
     pub branch: Option<String>,
     pub is_main: bool,
     pub is_locked: bool,
 }
 
+/// Which side of the link between a linked worktree and its repository broke
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WorktreeLinkProblem {
+    /// The recorded worktree directory no longer exists (it was moved or deleted)
+    WorktreeMissing,
+    /// The worktree's `.git` file does not point back at the repository
+    BacklinkBroken,
+}
+
+#[derive(Debug, Serialize)]
+pub struct WorktreeLinkIssue {
+    pub name: String,
+    pub path: String,
+    pub problem: WorktreeLinkProblem,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RepairedLink {
+    pub description: String,
+    pub path: String,
+}
+
+/// Everything that would be lost or overridden by removing a worktree
+#[derive(Debug, Serialize)]
+pub struct RemovalPreflight {
+    pub path: String,
+    pub branch: Option<String>,
+    pub is_locked: bool,
+    pub lock_reason: Option<String>,
+    pub has_uncommitted_changes: bool,
+    pub upstream: Option<String>,
+    /// Commits not on the upstream, or on no other branch when there is none
+    pub unpushed_commits: u32,
+    /// Total size of ignored files (build artifacts, caches) in bytes
+    pub ignored_size_bytes: u64,
+    pub stash_count: usize,
+}
+
+/// Risks the caller has explicitly accepted when removing a worktree
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RemovalConfirmation {
+    pub uncommitted_changes: bool,
+    pub unpushed_commits: bool,
+    pub locked: bool,
+}
+
 #[tauri::command]
 pub async fn list_worktrees(repo_path: String) -> AppResult<Vec<WorktreeInfo>> {
     crate::git::worktree_manager::list_worktrees(&repo_path)
@@ -17,32 +72,142 @@ pub async fn list_worktrees(repo_path: String) -> AppResult<Vec<WorktreeInfo>> {
 #[tauri::command]
 pub async fn add_worktree(
     repo_path: String,
-    worktree_path: String,
+    worktree_path: PathArg,
     branch: String,
     create_branch: bool,
+    sparse_profile: Option<String>,
+) -> AppResult<()> {
+    let worktree_path = PathBuf::from(worktree_path);
+    audit_log::record(
+        &repo_path,
+        Some(&worktree_path),
+        "add_worktree",
+        json!({
+            "branch": branch,
+            "create_branch": create_branch,
+            "sparse_profile": sparse_profile,
+        }),
+        || {
+            undo_journal::journal(&repo_path, None, "add_worktree", || match &sparse_profile {
+                Some(profile) => crate::git::sparse_manager::add_sparse_worktree(
+                    &repo_path,
+                    &worktree_path,
+                    &branch,
+                    create_branch,
+                    profile,
+                ),
+                None => crate::git::worktree_manager::add_worktree(
+                    &repo_path,
+                    &worktree_path,
+                    &branch,
+                    create_branch,
+                ),
+            })
+        },
+    )
+}
+
+#[tauri::command]
+pub async fn removal_preflight(
+    repo_path: String,
+    worktree_path: PathArg,
+) -> AppResult<RemovalPreflight> {
+    crate::git::worktree_manager::removal_preflight(&repo_path, PathBuf::from(worktree_path))
+}
+
+#[tauri::command]
+pub async fn remove_worktree(
+    repo_path: String,
+    worktree_path: PathArg,
+    force: bool,
+    confirm: Option<RemovalConfirmation>,
 ) -> AppResult<()> {
-    crate::git::worktree_manager::add_worktree(&repo_path, &worktree_path, &branch, create_branch)
+    let mut confirm = confirm.unwrap_or_default();
+    confirm.uncommitted_changes |= force;
+    let worktree_path = PathBuf::from(worktree_path);
+    audit_log::record(
+        &repo_path,
+        Some(&worktree_path),
+        "remove_worktree",
+        json!({ "confirm": confirm }),
+        || {
+            undo_journal::journal(&repo_path, None, "remove_worktree", || {
+                crate::git::worktree_manager::remove_worktree(&repo_path, &worktree_path, confirm)
+            })
+        },
+    )
 }
 
 #[tauri::command]
-pub async fn remove_worktree(repo_path: String, worktree_path: String, force: bool) -> AppResult<()> {
-    crate::git::worktree_manager::remove_worktree(&repo_path, &worktree_path, force)
+pub async fn move_worktree(
+    repo_path: String,
+    worktree_path: PathArg,
+    new_path: PathArg,
+) -> AppResult<()> {
+    let worktree_path = PathBuf::from(worktree_path);
+    let new_path = PathBuf::from(new_path);
+    audit_log::record(
+        &repo_path,
+        Some(&worktree_path),
+        "move_worktree",
+        json!({ "new_path": new_path.display().to_string() }),
+        || {
+            undo_journal::journal(&repo_path, None, "move_worktree", || {
+                crate::git::worktree_manager::move_worktree(&repo_path, &worktree_path, &new_path)
+            })
+        },
+    )
+}
+
+#[tauri::command]
+pub async fn check_worktree_links(repo_path: String) -> AppResult<Vec<WorktreeLinkIssue>> {
+    crate::git::worktree_manager::check_worktree_links(&repo_path)
+}
+
+#[tauri::command]
+pub async fn repair_worktrees(
+    repo_path: String,
+    worktree_paths: Vec<String>,
+) -> AppResult<Vec<RepairedLink>> {
+    audit_log::record(
+        &repo_path,
+        None,
+        "repair_worktrees",
+        json!({ "worktree_paths": worktree_paths }),
+        || crate::git::worktree_manager::repair_worktrees(&repo_path, &worktree_paths),
+    )
 }
 
 #[tauri::command]
 pub async fn lock_worktree(
     repo_path: String,
-    worktree_path: String,
+    worktree_path: PathArg,
     reason: Option<String>,
 ) -> AppResult<()> {
-    crate::git::worktree_manager::lock_worktree(
+    let worktree_path = PathBuf::from(worktree_path);
+    audit_log::record(
         &repo_path,
-        &worktree_path,
-        reason.as_deref(),
+        Some(&worktree_path),
+        "lock_worktree",
+        json!({ "reason": reason }),
+        || {
+            crate::git::worktree_manager::lock_worktree(
+                &repo_path,
+                &worktree_path,
+                reason.as_deref(),
+            )
+        },
     )
 }
 
 #[tauri::command]
-pub async fn unlock_worktree(repo_path: String, worktree_path: String) -> AppResult<()> {
-    crate::git::worktree_manager::unlock_worktree(&repo_path, &worktree_path)
+pub async fn unlock_worktree(repo_path: String, worktree_path: PathArg) -> AppResult<()> {
+    let worktree_path = PathBuf::from(worktree_path);
+    audit_log::record(
+        &repo_path,
+        Some(&worktree_path),
+        "unlock_worktree",
+        json!({}),
+        || crate::git::worktree_manager::unlock_worktree(&repo_path, &worktree_path),
+    )
 }
@@ -9,8 +9,17 @@ pub enum AppError {
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
 
-    #[error("Command failed: {0}")]
-    Command(String),
+    /// A git subprocess exited unsuccessfully
+    #[error("Command failed: `{command}` {}", describe_failure(.exit_code, .stderr))]
+    GitCommand {
+        command: String,
+        /// `None` if git was killed by a signal
+        exit_code: Option<i32>,
+        stderr: String,
+    },
+
+    #[error("Command timed out after {seconds}s: `{command}`")]
+    Timeout { command: String, seconds: u64 },
 
     #[error("Invalid path: {0}")]
     InvalidPath(String),
@@ -21,8 +30,10 @@ pub enum AppError {
     #[error("Worktree has uncommitted changes")]
     UncommittedChanges,
 
+    #[error("Worktree branch has {0} unpushed commit(s)")]
+    UnpushedCommits(u32),
+
     #[error("Worktree is locked: {0}")]
-    #[allow(dead_code)]
     WorktreeLocked(String),
 
     #[error("Branch already checked out in another worktree: {0}")]
@@ -31,11 +42,40 @@ pub enum AppError {
     #[error("Worktree not found: {0}")]
     WorktreeNotFound(String),
 
+    #[error("Branch is not fully merged: {0}")]
+    BranchNotMerged(String),
+
+    #[error("Local changes would be overwritten by checkout: {0}")]
+    CheckoutConflict(String),
+
+    /// Credentials were refused or not given, or the server's host key could
+    /// not be verified
+    #[error("Authentication failed: {0}")]
+    Authentication(String),
+
+    #[error("Git not found: {0}")]
+    GitNotFound(String),
+
+    #[error("Git is too old: {0}")]
+    GitTooOld(String),
+
     #[error("{0}")]
     #[allow(dead_code)]
     Other(String),
 }
 
+fn describe_failure(exit_code: &Option<i32>, stderr: &str) -> String {
+    let exit = match exit_code {
+        Some(code) => format!("exited with code {}", code),
+        None => "was killed by a signal".to_string(),
+    };
+    if stderr.is_empty() {
+        exit
+    } else {
+        format!("{}: {}", exit, stderr)
+    }
+}
+
 impl Serialize for AppError {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
@@ -67,9 +107,36 @@ mod tests {
     }
 
     #[test]
-    fn test_app_error_display_command() {
-        let err = AppError::Command("command failed".to_string());
-        assert_eq!(err.to_string(), "Command failed: command failed");
+    fn test_app_error_display_git_command() {
+        let err = AppError::GitCommand {
+            command: "git worktree add ../feature feature".to_string(),
+            exit_code: Some(128),
+            stderr: "fatal: '../feature' already exists".to_string(),
+        };
+        assert_eq!(
+            err.to_string(),
+            "Command failed: `git worktree add ../feature feature` exited with code 128: \
+             fatal: '../feature' already exists"
+        );
+
+        let err = AppError::GitCommand {
+            command: "git fetch --all".to_string(),
+            exit_code: None,
+            stderr: String::new(),
+        };
+        assert_eq!(
+            err.to_string(),
+            "Command failed: `git fetch --all` was killed by a signal"
+        );
+    }
+
+    #[test]
+    fn test_app_error_display_timeout() {
+        let err = AppError::Timeout {
+            command: "git push".to_string(),
+            seconds: 1800,
+        };
+        assert_eq!(err.to_string(), "Command timed out after 1800s: `git push`");
     }
 
     #[test]
@@ -92,9 +159,16 @@ mod tests {
 
     #[test]
     fn test_app_error_serializes_to_string() {
-        let err = AppError::Command("test error".to_string());
+        let err = AppError::GitCommand {
+            command: "git push".to_string(),
+            exit_code: Some(1),
+            stderr: "test error".to_string(),
+        };
         let serialized = serde_json::to_string(&err).unwrap();
-        assert_eq!(serialized, "\"Command failed: test error\"");
+        assert_eq!(
+            serialized,
+            "\"Command failed: `git push` exited with code 1: test error\""
+        );
     }
 
     #[test]
@@ -117,6 +191,12 @@ mod tests {
         assert_eq!(err.to_string(), "Not a git repository: /some/path");
     }
 
+    #[test]
+    fn test_app_error_display_unpushed_commits() {
+        let err = AppError::UnpushedCommits(3);
+        assert_eq!(err.to_string(), "Worktree branch has 3 unpushed commit(s)");
+    }
+
     #[test]
     fn test_app_error_display_worktree_locked() {
         let err = AppError::WorktreeLocked("Work in progress".to_string());
@@ -138,6 +218,39 @@ mod tests {
         assert_eq!(err.to_string(), "Worktree not found: /path/to/worktree");
     }
 
+    #[test]
+    fn test_app_error_display_branch_not_merged() {
+        let err = AppError::BranchNotMerged("feature-branch".to_string());
+        assert_eq!(err.to_string(), "Branch is not fully merged: feature-branch");
+    }
+
+    #[test]
+    fn test_app_error_display_checkout_conflict() {
+        let err = AppError::CheckoutConflict("src/main.rs".to_string());
+        assert_eq!(
+            err.to_string(),
+            "Local changes would be overwritten by checkout: src/main.rs"
+        );
+    }
+
+    #[test]
+    fn test_app_error_display_authentication() {
+        let err = AppError::Authentication("Host key verification failed.".to_string());
+        assert_eq!(
+            err.to_string(),
+            "Authentication failed: Host key verification failed."
+        );
+    }
+
+    #[test]
+    fn test_app_error_display_git_too_old() {
+        let err = AppError::GitTooOld("/usr/bin/git is version 2.17.1".to_string());
+        assert_eq!(
+            err.to_string(),
+            "Git is too old: /usr/bin/git is version 2.17.1"
+        );
+    }
+
     #[test]
     fn test_app_error_serialize_not_a_repository() {
         let err = AppError::NotARepository("/test/repo".to_string());
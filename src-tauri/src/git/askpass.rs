@@ -0,0 +1,338 @@
+//! Forwards credential and host key prompts from git and ssh to the UI.
+//!
+//! Git runs without a terminal, so it asks through `GIT_ASKPASS` (and ssh
+//! through `SSH_ASKPASS`). Both point at this executable, which then acts as
+//! a client: it sends the prompt to a listener in the running app over a
+//! loopback socket, and prints the answer for git to read. The listener
+//! hands prompts to the UI and waits for `respond` to answer them.
+
+use crate::commands::auth::AuthPrompt;
+use crate::error::{AppError, AppResult};
+use crate::git::runner::GitOutput;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::env;
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::process::Command;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::mpsc::{self, Sender};
+use std::sync::{Mutex, OnceLock, RwLock};
+use std::thread;
+use std::time::Duration;
+
+const ADDR_ENV: &str = "WTVIEW_ASKPASS_ADDR";
+const TOKEN_ENV: &str = "WTVIEW_ASKPASS_TOKEN";
+
+/// How long a prompt waits for an answer before it is cancelled
+const PROMPT_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// How long a connection may take to send its request; the client sends it
+/// right away
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+type PromptHandler = Box<dyn Fn(AuthPrompt) + Send + Sync>;
+
+static HANDLER: RwLock<Option<PromptHandler>> = RwLock::new(None);
+static PENDING: Mutex<Option<HashMap<u64, Sender<Option<String>>>>> = Mutex::new(None);
+static NEXT_ID: AtomicU64 = AtomicU64::new(1);
+static BRIDGE: OnceLock<Option<Bridge>> = OnceLock::new();
+
+struct Bridge {
+    addr: SocketAddr,
+    /// Shared with the askpass client, so other local processes cannot ask
+    token: String,
+}
+
+#[derive(Serialize, Deserialize)]
+struct AskpassRequest {
+    token: String,
+    prompt: String,
+}
+
+#[derive(Serialize, Deserialize)]
+struct AskpassResponse {
+    response: Option<String>,
+}
+
+/// Sets where prompts go; without a handler every prompt is cancelled
+pub fn set_prompt_handler(handler: impl Fn(AuthPrompt) + Send + Sync + 'static) {
+    *HANDLER.write().unwrap_or_else(|e| e.into_inner()) = Some(Box::new(handler));
+}
+
+/// 128 bits from the OS random number generator, hex encoded
+fn random_token() -> io::Result<String> {
+    let mut bytes = [0u8; 16];
+    getrandom::fill(&mut bytes)?;
+    Ok(bytes.iter().map(|b| format!("{:02x}", b)).collect())
+}
+
+fn start_bridge() -> io::Result<Bridge> {
+    let listener = TcpListener::bind(("127.0.0.1", 0))?;
+    let addr = listener.local_addr()?;
+    let token = random_token()?;
+
+    let expected = token.clone();
+    thread::spawn(move || {
+        for stream in listener.incoming().flatten() {
+            let expected = expected.clone();
+            thread::spawn(move || {
+                let _ = serve(stream, &expected);
+            });
+        }
+    });
+
+    Ok(Bridge { addr, token })
+}
+
+/// The listener, started on first use; `None` if it could not be started
+fn bridge() -> Option<&'static Bridge> {
+    BRIDGE.get_or_init(|| start_bridge().ok()).as_ref()
+}
+
+/// Compares tokens in time that depends only on their length, so timing
+/// answers does not reveal how much of a guess was right
+fn tokens_match(given: &str, expected: &str) -> bool {
+    given.len() == expected.len()
+        && given
+            .bytes()
+            .zip(expected.bytes())
+            .fold(0, |diff, (a, b)| diff | (a ^ b))
+            == 0
+}
+
+fn serve(stream: TcpStream, token: &str) -> io::Result<()> {
+    // Without a timeout an idle connection would hold its thread forever
+    stream.set_read_timeout(Some(REQUEST_TIMEOUT))?;
+    let mut line = String::new();
+    BufReader::new(&stream).read_line(&mut line)?;
+    let request: AskpassRequest = serde_json::from_str(&line)?;
+    if !tokens_match(&request.token, token) {
+        return Ok(());
+    }
+
+    let response = AskpassResponse {
+        response: ask(&request.prompt),
+    };
+    let mut stream = stream;
+    writeln!(stream, "{}", serde_json::to_string(&response)?)
+}
+
+/// Hands a prompt to the UI and waits for the answer
+fn ask(prompt: &str) -> Option<String> {
+    let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+    let (sender, receiver) = mpsc::channel();
+    PENDING
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .get_or_insert_with(HashMap::new)
+        .insert(id, sender);
+
+    let delivered = match HANDLER.read().unwrap_or_else(|e| e.into_inner()).as_ref() {
+        Some(handler) => {
+            handler(AuthPrompt {
+                id,
+                prompt: prompt.to_string(),
+                secret: is_secret(prompt),
+            });
+            true
+        }
+        None => false,
+    };
+
+    let response = if delivered {
+        receiver.recv_timeout(PROMPT_TIMEOUT).ok().flatten()
+    } else {
+        None
+    };
+
+    if let Some(pending) = PENDING.lock().unwrap_or_else(|e| e.into_inner()).as_mut() {
+        pending.remove(&id);
+    }
+    response
+}
+
+/// Usernames and host key confirmations can be shown; anything else is
+/// treated as a password or passphrase
+fn is_secret(prompt: &str) -> bool {
+    !(prompt.starts_with("Username") || prompt.contains("(yes/no"))
+}
+
+pub fn respond(id: u64, response: Option<String>) -> AppResult<()> {
+    let sender = PENDING
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .as_mut()
+        .and_then(|pending| pending.remove(&id))
+        .ok_or_else(|| AppError::Other(format!("No pending prompt with id {}", id)))?;
+
+    // The operation may have timed out in the meantime
+    let _ = sender.send(response);
+    Ok(())
+}
+
+/// Makes git and ssh ask through the UI instead of a terminal. Credential
+/// helpers configured by the user are still asked first.
+pub fn configure(cmd: &mut Command) {
+    let (Some(bridge), Ok(exe)) = (bridge(), env::current_exe()) else {
+        return;
+    };
+    cmd.env("GIT_ASKPASS", &exe)
+        .env("SSH_ASKPASS", &exe)
+        .env("SSH_ASKPASS_REQUIRE", "force")
+        .env(ADDR_ENV, bridge.addr.to_string())
+        .env(TOKEN_ENV, &bridge.token);
+}
+
+fn request(addr: &str, token: &str, prompt: &str) -> io::Result<Option<String>> {
+    let mut stream = TcpStream::connect(addr)?;
+    let request = AskpassRequest {
+        token: token.to_string(),
+        prompt: prompt.to_string(),
+    };
+    writeln!(stream, "{}", serde_json::to_string(&request)?)?;
+
+    let mut line = String::new();
+    BufReader::new(&stream).read_line(&mut line)?;
+    if line.is_empty() {
+        return Ok(None);
+    }
+    let response: AskpassResponse = serde_json::from_str(&line)?;
+    Ok(response.response)
+}
+
+/// Runs the askpass client when git or ssh started this executable to ask
+/// something, returning the exit code; `None` for a normal start
+pub fn askpass_client() -> Option<i32> {
+    let addr = env::var(ADDR_ENV).ok()?;
+    let token = env::var(TOKEN_ENV).ok()?;
+    let prompt = env::args().nth(1).unwrap_or_default();
+
+    match request(&addr, &token, &prompt) {
+        Ok(Some(response)) => {
+            println!("{}", response);
+            Some(0)
+        }
+        _ => Some(1),
+    }
+}
+
+/// Turns a failed network operation into an error, telling authentication
+/// and host key failures apart from other failures
+pub fn command_error(output: &GitOutput) -> AppError {
+    const AUTH_FAILURES: &[&str] = &[
+        "Authentication failed",
+        "could not read Username",
+        "could not read Password",
+        "Permission denied (publickey",
+        "Host key verification failed",
+        "Invalid username or password",
+    ];
+
+    if AUTH_FAILURES
+        .iter()
+        .any(|failure| output.stderr.contains(failure))
+    {
+        AppError::Authentication(output.stderr.trim().to_string())
+    } else {
+        output.error()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_prompt_round_trip() {
+        set_prompt_handler(|prompt| {
+            let response = if prompt.prompt.starts_with("Username") {
+                None
+            } else {
+                Some(format!("answer {}", prompt.secret))
+            };
+            thread::spawn(move || respond(prompt.id, response).unwrap());
+        });
+        let bridge = bridge().unwrap();
+        let addr = bridge.addr.to_string();
+
+        let response = request(
+            &addr,
+            &bridge.token,
+            "Password for 'https://me@example.com': ",
+        );
+        assert_eq!(response.unwrap().as_deref(), Some("answer true"));
+
+        let response = request(&addr, &bridge.token, "Username for 'https://example.com': ");
+        assert_eq!(response.unwrap(), None);
+
+        let response = request(&addr, "wrong token", "Password: ");
+        assert_eq!(response.unwrap(), None);
+    }
+
+    #[test]
+    fn test_random_token() {
+        let token = random_token().unwrap();
+        assert_eq!(token.len(), 32);
+        assert!(token.chars().all(|c| c.is_ascii_hexdigit()));
+        assert_ne!(token, random_token().unwrap());
+    }
+
+    #[test]
+    fn test_tokens_match() {
+        assert!(tokens_match("0123abcd", "0123abcd"));
+        assert!(!tokens_match("0123abce", "0123abcd"));
+        assert!(!tokens_match("0123abc", "0123abcd"));
+        assert!(!tokens_match("", "0123abcd"));
+    }
+
+    #[test]
+    fn test_respond_to_unknown_prompt() {
+        assert!(matches!(respond(u64::MAX, None), Err(AppError::Other(_))));
+    }
+
+    #[test]
+    fn test_is_secret() {
+        assert!(is_secret("Password for 'https://me@example.com': "));
+        assert!(is_secret(
+            "Enter passphrase for key '/home/me/.ssh/id_ed25519': "
+        ));
+        assert!(!is_secret("Username for 'https://example.com': "));
+        assert!(!is_secret(
+            "Are you sure you want to continue connecting (yes/no/[fingerprint])? "
+        ));
+    }
+
+    fn failed(stderr: &str) -> GitOutput {
+        GitOutput {
+            command: "git fetch --all".to_string(),
+            exit_code: Some(128),
+            stdout: String::new(),
+            stderr: stderr.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_command_error() {
+        let err = command_error(&failed(
+            "fatal: could not read Username for 'https://example.com': terminal prompts disabled\n",
+        ));
+        assert!(matches!(err, AppError::Authentication(_)));
+
+        let err = command_error(&failed(
+            "Host key verification failed.\nfatal: Could not read from remote repository.\n",
+        ));
+        assert!(matches!(err, AppError::Authentication(_)));
+
+        let err = command_error(&failed(
+            "fatal: 'origin' does not appear to be a git repository\n",
+        ));
+        assert!(matches!(
+            err,
+            AppError::GitCommand {
+                exit_code: Some(128),
+                ..
+            }
+        ));
+    }
+}
@@ -0,0 +1,300 @@
+use crate::commands::audit::{AuditEntry, AuditQuery};
+use crate::error::AppResult;
+use crate::git::branch_manager::unix_now;
+use crate::git::paths::same_path;
+use crate::git::worktree_manager::{common_dir, validate_repository};
+use git2::Repository;
+use serde_json::Value;
+use std::env;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+/// Location of the audit log, inside the common git directory so that every
+/// user and worktree of a repository shares one log
+fn log_path(repo: &Repository) -> PathBuf {
+    common_dir(repo).join("wtview").join("audit.log")
+}
+
+fn current_user() -> String {
+    env::var("USER")
+        .or_else(|_| env::var("USERNAME"))
+        .unwrap_or_else(|_| "unknown".to_string())
+}
+
+fn append(repo_path: &str, entry: &AuditEntry) -> AppResult<()> {
+    let repo = Repository::open(repo_path)?;
+    let path = log_path(&repo);
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+
+    let mut line = serde_json::to_string(entry).map_err(std::io::Error::from)?;
+    line.push('\n');
+
+    // A single append-mode write keeps concurrent writers from interleaving
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    file.write_all(line.as_bytes())?;
+    Ok(())
+}
+
+/// Runs a mutating operation and appends its outcome to the audit log of
+/// the repository at `repo_path`, which may be any of its worktrees.
+///
+/// Failing to write the log never fails the operation itself.
+pub fn record<T>(
+    repo_path: &str,
+    worktree: Option<&Path>,
+    operation: &str,
+    args: Value,
+    run: impl FnOnce() -> AppResult<T>,
+) -> AppResult<T> {
+    let timestamp = unix_now();
+    let started = Instant::now();
+    let result = run();
+
+    let entry = AuditEntry {
+        timestamp,
+        user: current_user(),
+        operation: operation.to_string(),
+        repo: repo_path.to_string(),
+        worktree: worktree.map(|p| p.display().to_string()),
+        args,
+        success: result.is_ok(),
+        error: result.as_ref().err().map(ToString::to_string),
+        duration_ms: started.elapsed().as_millis() as u64,
+    };
+    let _ = append(repo_path, &entry);
+
+    result
+}
+
+fn matches(entry: &AuditEntry, query: &AuditQuery) -> bool {
+    query
+        .operation
+        .as_ref()
+        .is_none_or(|op| &entry.operation == op)
+        && query.user.as_ref().is_none_or(|user| &entry.user == user)
+        && query.worktree.as_ref().is_none_or(|wt| {
+            entry
+                .worktree
+                .as_ref()
+                .is_some_and(|path| same_path(path, wt))
+        })
+        && query.since.is_none_or(|since| entry.timestamp >= since)
+        && query.until.is_none_or(|until| entry.timestamp < until)
+        && (!query.failed_only || !entry.success)
+}
+
+/// Returns the audit log entries matching `query`, newest first.
+///
+/// Lines that cannot be parsed, such as one cut short by a crash, are
+/// skipped.
+pub fn query(repo_path: &str, query: &AuditQuery) -> AppResult<Vec<AuditEntry>> {
+    let repo = validate_repository(repo_path)?;
+    let contents = match fs::read_to_string(log_path(&repo)) {
+        Ok(contents) => contents,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e.into()),
+    };
+
+    let mut entries: Vec<AuditEntry> = contents
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .filter(|entry| matches(entry, query))
+        .collect();
+    entries.reverse();
+    if let Some(limit) = query.limit {
+        entries.truncate(limit);
+    }
+
+    Ok(entries)
+}
+
+/// Writes the entries matching `query` to `destination` as JSON lines,
+/// oldest first, and returns how many were written
+pub fn export(repo_path: &str, destination: &str, query: &AuditQuery) -> AppResult<usize> {
+    let mut entries = self::query(repo_path, query)?;
+    entries.reverse();
+
+    let mut output = String::new();
+    for entry in &entries {
+        output.push_str(&serde_json::to_string(entry).map_err(std::io::Error::from)?);
+        output.push('\n');
+    }
+    fs::write(destination, output)?;
+
+    Ok(entries.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::AppError;
+    use std::process::Command as StdCommand;
+    use tempfile::TempDir;
+
+    fn create_test_repo() -> TempDir {
+        let temp_dir = TempDir::new().unwrap();
+        let repo_path = temp_dir.path();
+
+        StdCommand::new("git")
+            .current_dir(repo_path)
+            .args(["init"])
+            .output()
+            .unwrap();
+
+        StdCommand::new("git")
+            .current_dir(repo_path)
+            .args(["config", "user.email", "test@test.com"])
+            .output()
+            .unwrap();
+
+        StdCommand::new("git")
+            .current_dir(repo_path)
+            .args(["config", "user.name", "Test User"])
+            .output()
+            .unwrap();
+
+        fs::write(repo_path.join("README.md"), "# Test Repository").unwrap();
+
+        StdCommand::new("git")
+            .current_dir(repo_path)
+            .args(["add", "."])
+            .output()
+            .unwrap();
+
+        StdCommand::new("git")
+            .current_dir(repo_path)
+            .args(["commit", "-m", "Initial commit"])
+            .output()
+            .unwrap();
+
+        temp_dir
+    }
+
+    fn repo_str(temp_dir: &TempDir) -> &str {
+        temp_dir.path().to_str().unwrap()
+    }
+
+    #[test]
+    fn test_record_success_and_failure() {
+        let temp_dir = create_test_repo();
+        let repo_path = repo_str(&temp_dir);
+
+        let ok = record(
+            repo_path,
+            None,
+            "first",
+            serde_json::json!({ "n": 1 }),
+            || Ok(7),
+        );
+        assert_eq!(ok.unwrap(), 7);
+        let err: AppResult<()> = record(
+            repo_path,
+            Some(temp_dir.path()),
+            "second",
+            serde_json::json!({}),
+            || Err(AppError::Other("boom".to_string())),
+        );
+        assert!(err.is_err());
+
+        let entries = query(repo_path, &AuditQuery::default()).unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].operation, "second");
+        assert!(!entries[0].success);
+        assert_eq!(entries[0].error.as_deref(), Some("boom"));
+        assert!(entries[0].worktree.is_some());
+        assert_eq!(entries[1].operation, "first");
+        assert!(entries[1].success);
+        assert_eq!(entries[1].args["n"], 1);
+    }
+
+    #[test]
+    fn test_query_filters_and_limit() {
+        let temp_dir = create_test_repo();
+        let repo_path = repo_str(&temp_dir);
+
+        for op in ["a", "b", "a"] {
+            let _ = record(repo_path, None, op, Value::Null, || Ok(()));
+        }
+        let _: AppResult<()> = record(repo_path, None, "a", Value::Null, || {
+            Err(AppError::Other("failed".to_string()))
+        });
+
+        let by_op = AuditQuery {
+            operation: Some("a".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(query(repo_path, &by_op).unwrap().len(), 3);
+
+        let failed = AuditQuery {
+            failed_only: true,
+            ..Default::default()
+        };
+        assert_eq!(query(repo_path, &failed).unwrap().len(), 1);
+
+        let limited = AuditQuery {
+            limit: Some(2),
+            ..Default::default()
+        };
+        assert_eq!(query(repo_path, &limited).unwrap().len(), 2);
+
+        let future = AuditQuery {
+            since: Some(unix_now() + 3600),
+            ..Default::default()
+        };
+        assert!(query(repo_path, &future).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_query_skips_corrupt_lines() {
+        let temp_dir = create_test_repo();
+        let repo_path = repo_str(&temp_dir);
+
+        let _ = record(repo_path, None, "ok", Value::Null, || Ok(()));
+        let repo = Repository::open(repo_path).unwrap();
+        let mut file = OpenOptions::new()
+            .append(true)
+            .open(log_path(&repo))
+            .unwrap();
+        file.write_all(b"{\"truncated\n").unwrap();
+
+        let entries = query(repo_path, &AuditQuery::default()).unwrap();
+        assert_eq!(entries.len(), 1);
+    }
+
+    #[test]
+    fn test_query_without_log() {
+        let temp_dir = create_test_repo();
+        let entries = query(repo_str(&temp_dir), &AuditQuery::default()).unwrap();
+        assert!(entries.is_empty());
+    }
+
+    #[test]
+    fn test_export_writes_json_lines() {
+        let temp_dir = create_test_repo();
+        let repo_path = repo_str(&temp_dir);
+
+        for op in ["first", "second"] {
+            let _ = record(repo_path, None, op, Value::Null, || Ok(()));
+        }
+
+        let destination = temp_dir.path().join("audit.jsonl");
+        let count = export(
+            repo_path,
+            destination.to_str().unwrap(),
+            &AuditQuery::default(),
+        )
+        .unwrap();
+        assert_eq!(count, 2);
+
+        let contents = fs::read_to_string(&destination).unwrap();
+        let operations: Vec<String> = contents
+            .lines()
+            .map(|line| serde_json::from_str::<AuditEntry>(line).unwrap().operation)
+            .collect();
+        assert_eq!(operations, vec!["first", "second"]);
+    }
+}
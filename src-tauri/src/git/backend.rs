@@ -0,0 +1,817 @@
+//! Local operations implemented both with the git CLI and with libgit2.
+//!
+//! The CLI behaves exactly like git on the command line, running hooks and
+//! signing commits as configured. libgit2 avoids starting a process per
+//! operation, which is noticeably faster on Windows, but refuses what it
+//! cannot do the same way as git. The settings choose between them.
+
+use crate::commands::backend::GitBackend;
+use crate::error::{AppError, AppResult};
+use crate::git::paths::{normalize_path, path_from_bytes, path_to_bytes};
+use crate::git::runner::git;
+use crate::git::settings;
+use crate::git::sparse_manager::skip_worktree_paths;
+use git2::build::CheckoutBuilder;
+use git2::{
+    ErrorCode, IndexAddOption, IndexEntry, IndexTime, ObjectType, Repository, RepositoryState,
+    Tree, Worktree, WorktreeLockStatus,
+};
+use std::collections::BTreeSet;
+use std::path::{Component, Path, PathBuf};
+use std::sync::RwLock;
+
+pub trait Backend: Sync {
+    /// Stages a file, or everything under a directory, including deletions
+    fn stage(&self, worktree_path: &Path, file_path: &Path) -> AppResult<()>;
+
+    /// Resets the index entries of a file, or everything under a directory,
+    /// to HEAD, leaving the working tree alone
+    fn unstage(&self, worktree_path: &Path, file_path: &Path) -> AppResult<()>;
+
+    /// Commits the index, returning git's summary starting with
+    /// `[<branch> <short id>] <subject>`
+    fn commit(&self, worktree_path: &Path, message: &str) -> AppResult<String>;
+
+    /// Switches the worktree to a local branch, carrying local changes over.
+    /// With `merge`, changes to files that differ between the branches are
+    /// merged instead of refused.
+    fn checkout(&self, worktree_path: &Path, branch: &str, merge: bool) -> AppResult<()>;
+
+    fn lock(&self, repo_path: &Path, worktree: &Worktree, reason: Option<&str>) -> AppResult<()>;
+
+    fn unlock(&self, repo_path: &Path, worktree: &Worktree) -> AppResult<()>;
+}
+
+/// Runs the git CLI
+pub struct CliBackend;
+
+/// Uses libgit2 in process
+pub struct Git2Backend;
+
+/// The selected backend, read from the settings on first use
+static SELECTED: RwLock<Option<GitBackend>> = RwLock::new(None);
+
+pub fn selected() -> GitBackend {
+    if let Some(backend) = *SELECTED.read().unwrap_or_else(|e| e.into_inner()) {
+        return backend;
+    }
+    let backend = settings::load().backend;
+    *SELECTED.write().unwrap_or_else(|e| e.into_inner()) = Some(backend);
+    backend
+}
+
+/// Saves the backend to use from now on
+pub fn select(backend: GitBackend) -> AppResult<()> {
+    let mut saved = settings::load();
+    saved.backend = backend;
+    settings::save(&saved)?;
+    *SELECTED.write().unwrap_or_else(|e| e.into_inner()) = Some(backend);
+    Ok(())
+}
+
+pub fn backend(kind: GitBackend) -> &'static dyn Backend {
+    match kind {
+        GitBackend::Cli => &CliBackend,
+        GitBackend::Libgit2 => &Git2Backend,
+    }
+}
+
+/// The backend local operations should use
+pub fn current() -> &'static dyn Backend {
+    backend(selected())
+}
+
+impl Backend for CliBackend {
+    fn stage(&self, worktree_path: &Path, file_path: &Path) -> AppResult<()> {
+        // Literal pathspecs keep names containing `*`, `?` or `:` from
+        // matching other files
+        git(worktree_path)?
+            .args(["--literal-pathspecs", "add", "--"])
+            .arg(file_path)
+            .run()?;
+        Ok(())
+    }
+
+    fn unstage(&self, worktree_path: &Path, file_path: &Path) -> AppResult<()> {
+        git(worktree_path)?
+            .args(["--literal-pathspecs", "restore", "--staged", "--"])
+            .arg(file_path)
+            .run()?;
+        Ok(())
+    }
+
+    fn commit(&self, worktree_path: &Path, message: &str) -> AppResult<String> {
+        git(worktree_path)?.args(["commit", "-m", message]).run()
+    }
+
+    fn checkout(&self, worktree_path: &Path, branch: &str, merge: bool) -> AppResult<()> {
+        let mut cmd = git(worktree_path)?;
+        cmd.arg("checkout");
+        if merge {
+            cmd.arg("--merge");
+        }
+        cmd.arg(branch);
+        cmd.run()?;
+        Ok(())
+    }
+
+    fn lock(&self, repo_path: &Path, worktree: &Worktree, reason: Option<&str>) -> AppResult<()> {
+        let mut cmd = git(repo_path)?;
+        cmd.args(["worktree", "lock"]);
+        if let Some(reason) = reason {
+            cmd.args(["--reason", reason]);
+        }
+        cmd.arg(worktree.path());
+        cmd.run()?;
+        Ok(())
+    }
+
+    fn unlock(&self, repo_path: &Path, worktree: &Worktree) -> AppResult<()> {
+        git(repo_path)?
+            .args(["worktree", "unlock"])
+            .arg(worktree.path())
+            .run()?;
+        Ok(())
+    }
+}
+
+/// libgit2 ignores sparse checkout, so it would write out excluded files
+/// and take their absence for deletions
+fn ensure_not_sparse(repo: &Repository) -> AppResult<()> {
+    let sparse = repo
+        .config()
+        .and_then(|config| config.get_bool("core.sparseCheckout"))
+        .unwrap_or(false);
+    if sparse || !skip_worktree_paths(repo)?.is_empty() {
+        return Err(AppError::Other(
+            "Sparse worktrees are not supported by the libgit2 backend".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// The path of `file_path` within the worktree as git records it, with an
+/// empty path for the whole worktree
+fn index_path(repo: &Repository, file_path: &Path) -> AppResult<Vec<u8>> {
+    let outside =
+        || AppError::InvalidPath(format!("{} is outside the worktree", file_path.display()));
+
+    let relative = if file_path.is_absolute() {
+        let workdir = repo.workdir().ok_or_else(outside)?;
+        normalize_path(file_path)
+            .strip_prefix(normalize_path(workdir))
+            .map_err(|_| outside())?
+            .to_path_buf()
+    } else {
+        file_path.to_path_buf()
+    };
+
+    let mut cleaned = PathBuf::new();
+    for component in relative.components() {
+        match component {
+            Component::Normal(name) => cleaned.push(name),
+            Component::CurDir => {}
+            Component::ParentDir if cleaned.pop() => {}
+            _ => return Err(outside()),
+        }
+    }
+    Ok(path_to_bytes(&cleaned))
+}
+
+/// Whether `path` is `prefix` or lies under it; an empty prefix matches
+/// every path
+fn is_under(path: &[u8], prefix: &[u8]) -> bool {
+    prefix.is_empty()
+        || path == prefix
+        || (path.starts_with(prefix) && path.get(prefix.len()) == Some(&b'/'))
+}
+
+/// Adds the paths of the blobs and submodules in `tree` under `prefix`
+fn tree_paths(
+    repo: &Repository,
+    tree: &Tree,
+    base: &[u8],
+    prefix: &[u8],
+    paths: &mut BTreeSet<Vec<u8>>,
+) -> AppResult<()> {
+    for entry in tree.iter() {
+        let mut path = base.to_vec();
+        if !path.is_empty() {
+            path.push(b'/');
+        }
+        path.extend_from_slice(entry.name_bytes());
+
+        // Only descend into directories on the way to or below the prefix
+        if !is_under(&path, prefix) && !is_under(prefix, &path) {
+            continue;
+        }
+        match entry.kind() {
+            Some(ObjectType::Tree) => {
+                let subtree = repo.find_tree(entry.id())?;
+                tree_paths(repo, &subtree, &path, prefix, paths)?;
+            }
+            _ if is_under(&path, prefix) => {
+                paths.insert(path);
+            }
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+fn no_match(file_path: &Path) -> AppError {
+    AppError::Other(format!(
+        "pathspec '{}' did not match any files",
+        file_path.display()
+    ))
+}
+
+impl Backend for Git2Backend {
+    fn stage(&self, worktree_path: &Path, file_path: &Path) -> AppResult<()> {
+        let repo = Repository::open(worktree_path)?;
+        ensure_not_sparse(&repo)?;
+        let prefix = index_path(&repo, file_path)?;
+        let mut index = repo.index()?;
+
+        // Paths are matched here rather than as pathspecs, which libgit2
+        // would treat as globs. `*` stands in for no pathspec, for which the
+        // callback would be given a null pathspec.
+        let mut matched = false;
+        let mut matches = |path: &Path, _: &[u8]| {
+            if is_under(&path_to_bytes(path), &prefix) {
+                matched = true;
+                0
+            } else {
+                1
+            }
+        };
+        index.add_all(["*"], IndexAddOption::DEFAULT, Some(&mut matches))?;
+        // Removes deleted files
+        index.update_all(["*"], Some(&mut matches))?;
+
+        let on_disk = repo
+            .workdir()
+            .is_some_and(|workdir| workdir.join(path_from_bytes(&prefix)).exists());
+        let tracked = index.iter().any(|entry| is_under(&entry.path, &prefix));
+        if !matched && !tracked {
+            if on_disk && repo.is_path_ignored(path_from_bytes(&prefix))? {
+                return Err(AppError::Other(format!(
+                    "{} is ignored by .gitignore",
+                    file_path.display()
+                )));
+            }
+            if !on_disk {
+                return Err(no_match(file_path));
+            }
+        }
+
+        index.write()?;
+        Ok(())
+    }
+
+    fn unstage(&self, worktree_path: &Path, file_path: &Path) -> AppResult<()> {
+        let repo = Repository::open(worktree_path)?;
+        ensure_not_sparse(&repo)?;
+        let prefix = index_path(&repo, file_path)?;
+        let head_tree = repo.head().ok().and_then(|head| head.peel_to_tree().ok());
+        let mut index = repo.index()?;
+
+        let mut paths: BTreeSet<Vec<u8>> = index
+            .iter()
+            .map(|entry| entry.path)
+            .filter(|path| is_under(path, &prefix))
+            .collect();
+        if let Some(tree) = &head_tree {
+            tree_paths(&repo, tree, &[], &prefix, &mut paths)?;
+        }
+        if paths.is_empty() {
+            return Err(no_match(file_path));
+        }
+
+        for path in paths {
+            let in_head = head_tree
+                .as_ref()
+                .and_then(|tree| tree.get_path(&path_from_bytes(&path)).ok())
+                .filter(|entry| entry.kind() != Some(ObjectType::Tree));
+            match in_head {
+                // Without stat data git rechecks the file's content, so the
+                // working tree change shows up as unstaged
+                Some(entry) => index.add(&IndexEntry {
+                    ctime: IndexTime::new(0, 0),
+                    mtime: IndexTime::new(0, 0),
+                    dev: 0,
+                    ino: 0,
+                    mode: entry.filemode() as u32,
+                    uid: 0,
+                    gid: 0,
+                    file_size: 0,
+                    id: entry.id(),
+                    flags: 0,
+                    flags_extended: 0,
+                    path,
+                })?,
+                None => index.remove_path(&path_from_bytes(&path))?,
+            }
+        }
+
+        index.write()?;
+        Ok(())
+    }
+
+    fn commit(&self, worktree_path: &Path, message: &str) -> AppResult<String> {
+        let mut repo = Repository::open(worktree_path)?;
+
+        // Cleaned up as `git commit -m` does
+        let message = git2::message_prettify(message, None)?;
+        if message.is_empty() {
+            return Err(AppError::Other(
+                "Aborting commit due to empty commit message".to_string(),
+            ));
+        }
+        if repo.config()?.get_bool("commit.gpgsign").unwrap_or(false) {
+            return Err(AppError::Other(
+                "Signed commits are not supported by the libgit2 backend".to_string(),
+            ));
+        }
+
+        let mut parents = Vec::new();
+        if let Ok(head) = repo.head() {
+            parents.push(head.peel_to_commit()?.id());
+        }
+        let merging = match repo.state() {
+            RepositoryState::Clean => false,
+            RepositoryState::Merge => {
+                repo.mergehead_foreach(|oid| {
+                    parents.push(*oid);
+                    true
+                })?;
+                true
+            }
+            state => {
+                return Err(AppError::Other(format!(
+                    "Committing during {:?} is not supported by the libgit2 backend",
+                    state
+                )))
+            }
+        };
+
+        let mut index = repo.index()?;
+        if index.has_conflicts() {
+            return Err(AppError::Other(
+                "Committing is not possible because you have unmerged files".to_string(),
+            ));
+        }
+        let tree = repo.find_tree(index.write_tree()?)?;
+        let parents = parents
+            .iter()
+            .map(|oid| repo.find_commit(*oid))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let unchanged = match parents.first() {
+            Some(head) => !merging && head.tree_id() == tree.id(),
+            None => index.is_empty(),
+        };
+        if unchanged {
+            return Err(AppError::Other("nothing to commit".to_string()));
+        }
+
+        let signature = repo.signature()?;
+        let parent_refs: Vec<_> = parents.iter().collect();
+        let oid = repo.commit(
+            Some("HEAD"),
+            &signature,
+            &signature,
+            &message,
+            &tree,
+            &parent_refs,
+        )?;
+        if merging {
+            repo.cleanup_state()?;
+        }
+
+        let head = repo.head()?;
+        let branch = if head.is_branch() {
+            head.shorthand().unwrap_or("HEAD").to_string()
+        } else {
+            "detached HEAD".to_string()
+        };
+        let root = if parents.is_empty() {
+            " (root-commit)"
+        } else {
+            ""
+        };
+        let commit = repo.find_commit(oid)?;
+        let short_id = commit.as_object().short_id()?;
+        Ok(format!(
+            "[{}{} {}] {}\n",
+            branch,
+            root,
+            short_id.as_str().unwrap_or_default(),
+            commit.summary().unwrap_or_default()
+        ))
+    }
+
+    fn checkout(&self, worktree_path: &Path, branch: &str, merge: bool) -> AppResult<()> {
+        if merge {
+            return Err(AppError::Other(
+                "Merging local changes on checkout is not supported by the libgit2 backend"
+                    .to_string(),
+            ));
+        }
+        let repo = Repository::open(worktree_path)?;
+        ensure_not_sparse(&repo)?;
+
+        let refname = format!("refs/heads/{}", branch);
+        let target = repo.find_reference(&refname)?.peel_to_commit()?;
+
+        // Safe mode keeps local changes and fails rather than overwrite them
+        let mut opts = CheckoutBuilder::new();
+        opts.safe();
+        match repo.checkout_tree(target.as_object(), Some(&mut opts)) {
+            Err(e) if e.code() == ErrorCode::Conflict => {
+                return Err(AppError::CheckoutConflict(e.message().to_string()));
+            }
+            result => result?,
+        }
+        repo.set_head(&refname)?;
+        Ok(())
+    }
+
+    fn lock(&self, _repo_path: &Path, worktree: &Worktree, reason: Option<&str>) -> AppResult<()> {
+        if let WorktreeLockStatus::Locked(reason) = worktree.is_locked()? {
+            let reason = reason.map(|r| r.trim().to_string()).unwrap_or_default();
+            return Err(AppError::WorktreeLocked(if reason.is_empty() {
+                worktree.path().display().to_string()
+            } else {
+                reason
+            }));
+        }
+        // Written with a newline, as git does
+        let reason = reason.map(|r| format!("{}\n", r));
+        worktree.lock(reason.as_deref())?;
+        Ok(())
+    }
+
+    fn unlock(&self, _repo_path: &Path, worktree: &Worktree) -> AppResult<()> {
+        if matches!(worktree.is_locked()?, WorktreeLockStatus::Unlocked) {
+            return Err(AppError::Other(format!(
+                "{} is not locked",
+                worktree.path().display()
+            )));
+        }
+        worktree.unlock()?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::worktree_manager::add_worktree;
+    use std::fs;
+    use std::process::Command as StdCommand;
+    use tempfile::TempDir;
+
+    const BACKENDS: [GitBackend; 2] = [GitBackend::Cli, GitBackend::Libgit2];
+
+    fn run_git(dir: &Path, args: &[&str]) -> String {
+        let output = StdCommand::new("git")
+            .current_dir(dir)
+            .args(args)
+            .output()
+            .unwrap();
+        assert!(
+            output.status.success(),
+            "git {:?} failed: {}",
+            args,
+            String::from_utf8_lossy(&output.stderr)
+        );
+        String::from_utf8_lossy(&output.stdout).to_string()
+    }
+
+    fn create_empty_repo() -> TempDir {
+        let temp_dir = TempDir::new().unwrap();
+        let repo_path = temp_dir.path();
+        run_git(repo_path, &["init"]);
+        run_git(repo_path, &["config", "user.email", "test@test.com"]);
+        run_git(repo_path, &["config", "user.name", "Test User"]);
+        temp_dir
+    }
+
+    fn create_test_repo() -> TempDir {
+        let temp_dir = create_empty_repo();
+        let repo_path = temp_dir.path();
+        fs::write(repo_path.join("README.md"), "# Test Repository").unwrap();
+        fs::create_dir(repo_path.join("src")).unwrap();
+        fs::write(repo_path.join("src/lib.rs"), "pub fn lib() {}\n").unwrap();
+        run_git(repo_path, &["add", "."]);
+        run_git(repo_path, &["commit", "-m", "Initial commit"]);
+        temp_dir
+    }
+
+    fn porcelain(dir: &Path) -> String {
+        run_git(dir, &["status", "--porcelain", "--untracked-files=all"])
+    }
+
+    /// Sets up a repository for each backend with `setup`, runs `operation`
+    /// on it and returns what `observe` sees afterwards, per backend
+    fn compare<T: std::fmt::Debug + PartialEq>(
+        create: fn() -> TempDir,
+        setup: impl Fn(&Path),
+        operation: impl Fn(&dyn Backend, &Path),
+        observe: impl Fn(&Path) -> T,
+    ) -> T {
+        let results: Vec<T> = BACKENDS
+            .iter()
+            .map(|kind| {
+                let temp_dir = create();
+                setup(temp_dir.path());
+                operation(backend(*kind), temp_dir.path());
+                observe(temp_dir.path())
+            })
+            .collect();
+        assert_eq!(results[0], results[1], "CLI and libgit2 backends differ");
+        results.into_iter().next().unwrap()
+    }
+
+    #[test]
+    fn test_stage_matches_cli() {
+        let status = compare(
+            create_test_repo,
+            |dir| {
+                fs::write(dir.join("README.md"), "changed").unwrap();
+                fs::write(dir.join("a*.txt"), "glob-like name").unwrap();
+                fs::write(dir.join("abc.txt"), "other").unwrap();
+                fs::write(dir.join("src/new.rs"), "new").unwrap();
+                fs::remove_file(dir.join("src/lib.rs")).unwrap();
+                fs::create_dir(dir.join("docs")).unwrap();
+                fs::write(dir.join("docs/guide.md"), "guide").unwrap();
+            },
+            |backend, dir| {
+                backend.stage(dir, Path::new("a*.txt")).unwrap();
+                backend.stage(dir, Path::new("src")).unwrap();
+                backend.stage(dir, &dir.join("README.md")).unwrap();
+            },
+            porcelain,
+        );
+
+        assert_eq!(
+            status,
+            "M  README.md\nA  a*.txt\nD  src/lib.rs\nA  src/new.rs\n?? abc.txt\n?? docs/guide.md\n"
+        );
+    }
+
+    #[test]
+    fn test_stage_missing_path_fails() {
+        for kind in BACKENDS {
+            let temp_dir = create_test_repo();
+            let result = backend(kind).stage(temp_dir.path(), Path::new("missing.txt"));
+            assert!(result.is_err(), "{:?} staged a missing file", kind);
+        }
+    }
+
+    #[test]
+    fn test_unstage_matches_cli() {
+        let status = compare(
+            create_test_repo,
+            |dir| {
+                fs::write(dir.join("README.md"), "changed").unwrap();
+                fs::write(dir.join("new.txt"), "new").unwrap();
+                fs::write(dir.join("src/lib.rs"), "pub fn changed() {}\n").unwrap();
+                fs::write(dir.join("src/extra.rs"), "extra").unwrap();
+                fs::write(dir.join("kept.txt"), "kept").unwrap();
+                run_git(dir, &["add", "-A"]);
+            },
+            |backend, dir| {
+                backend.unstage(dir, Path::new("src")).unwrap();
+                backend.unstage(dir, Path::new("new.txt")).unwrap();
+                backend.unstage(dir, &dir.join("README.md")).unwrap();
+            },
+            porcelain,
+        );
+
+        assert_eq!(
+            status,
+            " M README.md\nA  kept.txt\n M src/lib.rs\n?? new.txt\n?? src/extra.rs\n"
+        );
+    }
+
+    #[test]
+    fn test_commit_matches_cli() {
+        let commit = compare(
+            create_test_repo,
+            |dir| {
+                fs::write(dir.join("committed.txt"), "content").unwrap();
+                run_git(dir, &["add", "committed.txt"]);
+            },
+            |backend, dir| {
+                let summary = backend
+                    .commit(dir, "Add a file  \n\n\nWith a body   \n")
+                    .unwrap();
+                let short_id = run_git(dir, &["rev-parse", "--short", "HEAD"]);
+                let first_line = summary.lines().next().unwrap();
+                let expected = format!("[{} {}] Add a file", current_branch(dir), short_id.trim());
+                assert_eq!(first_line, expected);
+            },
+            |dir| {
+                (
+                    run_git(dir, &["log", "-1", "--format=%B%n%T%n%P"]).replace(
+                        &run_git(dir, &["rev-parse", "HEAD~1"]).trim().to_string(),
+                        "<parent>",
+                    ),
+                    porcelain(dir),
+                )
+            },
+        );
+
+        assert!(commit.0.starts_with("Add a file\n\nWith a body\n"));
+        assert!(commit.0.contains("<parent>"));
+        assert_eq!(commit.1, "");
+    }
+
+    fn current_branch(dir: &Path) -> String {
+        run_git(dir, &["symbolic-ref", "--short", "HEAD"])
+            .trim()
+            .to_string()
+    }
+
+    #[test]
+    fn test_root_commit_matches_cli() {
+        compare(
+            create_empty_repo,
+            |dir| {
+                fs::write(dir.join("first.txt"), "first").unwrap();
+                run_git(dir, &["add", "first.txt"]);
+            },
+            |backend, dir| {
+                let summary = backend.commit(dir, "First").unwrap();
+                let prefix = format!("[{} (root-commit) ", current_branch(dir));
+                assert!(summary.starts_with(&prefix), "{}", summary);
+            },
+            |dir| run_git(dir, &["log", "--format=%s %T"]),
+        );
+    }
+
+    #[test]
+    fn test_commit_refusals() {
+        for kind in BACKENDS {
+            let temp_dir = create_test_repo();
+            let backend = backend(kind);
+            assert!(backend.commit(temp_dir.path(), "Nothing").is_err());
+
+            fs::write(temp_dir.path().join("file.txt"), "content").unwrap();
+            run_git(temp_dir.path(), &["add", "file.txt"]);
+            assert!(backend.commit(temp_dir.path(), "  \n\n").is_err());
+        }
+    }
+
+    #[test]
+    fn test_commit_concludes_merge() {
+        compare(
+            create_test_repo,
+            |dir| {
+                let main = current_branch(dir);
+                run_git(dir, &["checkout", "-b", "feature"]);
+                fs::write(dir.join("feature.txt"), "feature").unwrap();
+                run_git(dir, &["add", "feature.txt"]);
+                run_git(dir, &["commit", "-m", "Feature"]);
+                run_git(dir, &["checkout", &main]);
+                run_git(dir, &["merge", "--no-ff", "--no-commit", "feature"]);
+            },
+            |backend, dir| {
+                backend.commit(dir, "Merge feature").unwrap();
+                assert!(!dir.join(".git/MERGE_HEAD").exists());
+            },
+            |dir| {
+                (
+                    run_git(dir, &["log", "-1", "--format=%s %T"]),
+                    run_git(dir, &["rev-list", "--count", "--merges", "HEAD"]),
+                    porcelain(dir),
+                )
+            },
+        );
+    }
+
+    #[test]
+    fn test_checkout_matches_cli() {
+        let setup = |dir: &Path| {
+            let main = current_branch(dir);
+            run_git(dir, &["checkout", "-b", "other"]);
+            fs::write(dir.join("other.txt"), "other").unwrap();
+            fs::write(dir.join("README.md"), "# Changed on other").unwrap();
+            run_git(dir, &["add", "."]);
+            run_git(dir, &["commit", "-m", "Other"]);
+            run_git(dir, &["checkout", &main]);
+            fs::write(dir.join("src/lib.rs"), "pub fn local() {}\n").unwrap();
+            fs::write(dir.join("untracked.txt"), "untracked").unwrap();
+        };
+
+        let (branch, status) = compare(
+            create_test_repo,
+            setup,
+            |backend, dir| backend.checkout(dir, "other", false).unwrap(),
+            |dir| (current_branch(dir), porcelain(dir)),
+        );
+        assert_eq!(branch, "other");
+        assert_eq!(status, " M src/lib.rs\n?? untracked.txt\n");
+
+        for kind in BACKENDS {
+            let temp_dir = create_test_repo();
+            setup(temp_dir.path());
+            let main = current_branch(temp_dir.path());
+            fs::write(temp_dir.path().join("README.md"), "local").unwrap();
+
+            let result = backend(kind).checkout(temp_dir.path(), "other", false);
+            assert!(result.is_err(), "{:?} overwrote local changes", kind);
+            assert_eq!(current_branch(temp_dir.path()), main);
+            assert_eq!(
+                fs::read_to_string(temp_dir.path().join("README.md")).unwrap(),
+                "local"
+            );
+        }
+    }
+
+    #[test]
+    fn test_lock_and_unlock_match_cli() {
+        let locks: Vec<_> = BACKENDS
+            .iter()
+            .map(|kind| {
+                let temp_dir = create_test_repo();
+                let parent = TempDir::new().unwrap();
+                let repo_path = temp_dir.path();
+                let worktree_path = parent.path().join("locked-wt");
+                add_worktree(
+                    repo_path.to_str().unwrap(),
+                    worktree_path.to_str().unwrap(),
+                    "locked-branch",
+                    true,
+                )
+                .unwrap();
+                let repo = Repository::open(repo_path).unwrap();
+                let worktree = repo.find_worktree("locked-wt").unwrap();
+                let backend = backend(*kind);
+
+                backend
+                    .lock(repo_path, &worktree, Some("Work in progress"))
+                    .unwrap();
+                let locked = worktree.is_locked().unwrap();
+                assert!(backend.lock(repo_path, &worktree, None).is_err());
+
+                backend.unlock(repo_path, &worktree).unwrap();
+                assert!(backend.unlock(repo_path, &worktree).is_err());
+                let unlocked = worktree.is_locked().unwrap();
+
+                backend.lock(repo_path, &worktree, None).unwrap();
+                (locked, unlocked, worktree.is_locked().unwrap())
+            })
+            .collect();
+
+        assert_eq!(locks[0], locks[1]);
+        assert_eq!(
+            locks[0],
+            (
+                WorktreeLockStatus::Locked(Some("Work in progress\n".to_string())),
+                WorktreeLockStatus::Unlocked,
+                WorktreeLockStatus::Locked(None),
+            )
+        );
+    }
+
+    #[test]
+    fn test_libgit2_refuses_sparse_worktree() {
+        let temp_dir = create_test_repo();
+        run_git(temp_dir.path(), &["config", "core.sparseCheckout", "true"]);
+        fs::write(temp_dir.path().join("README.md"), "changed").unwrap();
+
+        let result = Git2Backend.stage(temp_dir.path(), Path::new("README.md"));
+        assert!(matches!(result, Err(AppError::Other(_))));
+        assert!(CliBackend
+            .stage(temp_dir.path(), Path::new("README.md"))
+            .is_ok());
+    }
+
+    #[test]
+    fn test_index_path() {
+        let temp_dir = create_test_repo();
+        let repo = Repository::open(temp_dir.path()).unwrap();
+
+        let path = |p: &Path| index_path(&repo, p).map(|bytes| String::from_utf8(bytes).unwrap());
+        assert_eq!(path(Path::new("src/lib.rs")).unwrap(), "src/lib.rs");
+        assert_eq!(path(Path::new("./src/../README.md")).unwrap(), "README.md");
+        assert_eq!(path(Path::new(".")).unwrap(), "");
+        assert_eq!(path(&temp_dir.path().join("src")).unwrap(), "src");
+        assert!(matches!(
+            path(Path::new("../elsewhere")),
+            Err(AppError::InvalidPath(_))
+        ));
+    }
+
+    #[test]
+    fn test_is_under() {
+        assert!(is_under(b"src/lib.rs", b"src"));
+        assert!(is_under(b"src", b"src"));
+        assert!(is_under(b"anything", b""));
+        assert!(!is_under(b"srcs/lib.rs", b"src"));
+        assert!(!is_under(b"src", b"src/lib.rs"));
+    }
+}
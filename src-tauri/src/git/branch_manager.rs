@@ -0,0 +1,889 @@
+use crate::commands::branches::{BranchCleanupCandidate, BranchCleanupResult};
+use crate::commands::worktree::RemovalConfirmation;
+use crate::error::{AppError, AppResult};
+use crate::git::paths::same_path;
+use crate::git::runner::git;
+use crate::git::worktree_manager::{
+    check_removal, common_dir, main_repository, open_worktree, remove_worktree, worktree_names,
+};
+use git2::{BranchType, Oid, Repository};
+use std::fs;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+pub const SECONDS_PER_DAY: i64 = 24 * 60 * 60;
+
+/// Current time in Unix seconds
+pub fn unix_now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0)
+}
+
+/// Returns every branch checked out in a worktree of the repository as
+/// `(branch_name, worktree_path)` pairs, including the main worktree.
+///
+/// Linked worktrees are read from their administrative `HEAD` file so that
+/// entries whose directory has gone missing still count, matching git.
+pub fn checked_out_branches(repo: &Repository) -> AppResult<Vec<(String, String)>> {
+    let mut branches = Vec::new();
+
+    let main_repo = main_repository(repo)?;
+    if let Ok(head) = main_repo.head() {
+        if head.is_branch() {
+            if let (Some(name), Some(workdir)) = (head.shorthand(), main_repo.workdir()) {
+                branches.push((name.to_string(), workdir.to_string_lossy().to_string()));
+            }
+        }
+    }
+
+    for name in worktree_names(&main_repo) {
+        let head_file = common_dir(&main_repo)
+            .join("worktrees")
+            .join(&name)
+            .join("HEAD");
+        let Ok(contents) = fs::read_to_string(&head_file) else {
+            continue;
+        };
+        let Some(branch) = contents.trim().strip_prefix("ref: refs/heads/") else {
+            continue;
+        };
+        let wt_path = open_worktree(&main_repo, &name)
+            .map(|wt| wt.path().to_string_lossy().to_string())
+            .unwrap_or_default();
+        branches.push((branch.to_string(), wt_path));
+    }
+
+    Ok(branches)
+}
+
+/// Returns the path of the worktree that has `branch` checked out, if any
+pub fn branch_worktree(repo: &Repository, branch: &str) -> AppResult<Option<String>> {
+    Ok(checked_out_branches(repo)?
+        .into_iter()
+        .find(|(name, _)| name == branch)
+        .map(|(_, path)| path))
+}
+
+fn ensure_branch_not_in_use(repo: &Repository, branch: &str) -> AppResult<()> {
+    if let Some(wt_path) = branch_worktree(repo, branch)? {
+        return Err(AppError::BranchInUse(format!("{} ({})", branch, wt_path)));
+    }
+    Ok(())
+}
+
+fn validate_branch_name(name: &str) -> AppResult<()> {
+    if !git2::Branch::name_is_valid(name)? {
+        return Err(AppError::Other(format!("Invalid branch name: {}", name)));
+    }
+    Ok(())
+}
+
+/// Creates a local branch at `start_point` (any revision, defaults to HEAD).
+///
+/// Like `git branch`, starting from a remote-tracking branch sets it as the
+/// new branch's upstream.
+pub fn create_branch(repo_path: &str, name: &str, start_point: Option<&str>) -> AppResult<()> {
+    let repo = Repository::open(repo_path)?;
+    validate_branch_name(name)?;
+
+    let start = start_point.unwrap_or("HEAD");
+    let commit = repo.revparse_single(start)?.peel_to_commit()?;
+
+    let mut branch = repo.branch(name, &commit, false)?;
+
+    if let Some(start) = start_point {
+        if repo.find_branch(start, BranchType::Remote).is_ok() {
+            branch.set_upstream(Some(start))?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Renames a local branch, updating any worktree that has it checked out.
+///
+/// Without `force`, renaming onto an existing branch is refused. Even with
+/// `force`, a branch checked out in a worktree is never overwritten.
+pub fn rename_branch(
+    repo_path: &str,
+    old_name: &str,
+    new_name: &str,
+    force: bool,
+) -> AppResult<()> {
+    let repo = Repository::open(repo_path)?;
+    validate_branch_name(new_name)?;
+    repo.find_branch(old_name, BranchType::Local)?;
+
+    if repo.find_branch(new_name, BranchType::Local).is_ok() {
+        if !force {
+            return Err(AppError::Other(format!(
+                "A branch named '{}' already exists",
+                new_name
+            )));
+        }
+        ensure_branch_not_in_use(&repo, new_name)?;
+    }
+
+    // The git CLI rewrites the HEAD of every worktree using the branch,
+    // which libgit2 only does for the repository it was opened on.
+    git(repo_path)?
+        .args([
+            "branch",
+            if force { "-M" } else { "-m" },
+            old_name,
+            new_name,
+        ])
+        .run()?;
+
+    Ok(())
+}
+
+/// Deletes a local branch.
+///
+/// A branch checked out in any worktree is refused with `BranchInUse`. In
+/// safe mode the branch must also be merged into its upstream (or HEAD when
+/// it has none), otherwise `BranchNotMerged` is returned.
+pub fn delete_branch(repo_path: &str, name: &str, force: bool) -> AppResult<()> {
+    let repo = Repository::open(repo_path)?;
+    repo.find_branch(name, BranchType::Local)?;
+    ensure_branch_not_in_use(&repo, name)?;
+
+    let output = git(repo_path)?
+        .args(["branch", if force { "-D" } else { "-d" }, name])
+        .output()?;
+
+    if !output.success() && output.stderr.contains("not fully merged") {
+        return Err(AppError::BranchNotMerged(name.to_string()));
+    }
+    output.check()?;
+
+    Ok(())
+}
+
+/// Sets the upstream of a local branch to a remote-tracking or local branch
+pub fn set_upstream(repo_path: &str, name: &str, upstream: &str) -> AppResult<()> {
+    let repo = Repository::open(repo_path)?;
+    let mut branch = repo.find_branch(name, BranchType::Local)?;
+    branch.set_upstream(Some(upstream))?;
+    Ok(())
+}
+
+/// Removes the upstream configuration of a local branch
+pub fn unset_upstream(repo_path: &str, name: &str) -> AppResult<()> {
+    let repo = Repository::open(repo_path)?;
+    let mut branch = repo.find_branch(name, BranchType::Local)?;
+    branch.set_upstream(None)?;
+    Ok(())
+}
+
+/// Returns true if the commit `tip` is reachable from `base`
+fn is_merged_into(repo: &Repository, tip: Oid, base: Oid) -> bool {
+    tip == base || repo.graph_descendant_of(base, tip).unwrap_or(false)
+}
+
+/// Reports local branches that are candidates for cleanup: fully merged into
+/// `base`, tracking an upstream that no longer exists, or without commits for
+/// at least `stale_days` days. The base branch itself is never reported.
+pub fn analyze_branches(
+    repo_path: &str,
+    base: &str,
+    stale_days: u32,
+) -> AppResult<Vec<BranchCleanupCandidate>> {
+    let repo = Repository::open(repo_path)?;
+    let base_oid = repo.revparse_single(base)?.peel_to_commit()?.id();
+    let checked_out = checked_out_branches(&repo)?;
+
+    let stale_cutoff = unix_now() - i64::from(stale_days) * SECONDS_PER_DAY;
+
+    let mut candidates = Vec::new();
+
+    for branch_result in repo.branches(Some(BranchType::Local))? {
+        let (branch, _) = branch_result?;
+        let Some(name) = branch.name()? else {
+            continue;
+        };
+        if name == base {
+            continue;
+        }
+        let Ok(tip) = branch.get().peel_to_commit() else {
+            continue;
+        };
+
+        let merged = is_merged_into(&repo, tip.id(), base_oid);
+
+        let upstream_gone = branch
+            .get()
+            .name()
+            .and_then(|refname| repo.branch_upstream_name(refname).ok())
+            .and_then(|upstream| upstream.as_str().map(String::from))
+            .is_some_and(|upstream| repo.find_reference(&upstream).is_err());
+
+        let last_commit_time = tip.time().seconds();
+        let stale = last_commit_time < stale_cutoff;
+
+        if !(merged || upstream_gone || stale) {
+            continue;
+        }
+
+        let worktree_path = checked_out
+            .iter()
+            .find(|(branch_name, _)| branch_name == name)
+            .map(|(_, path)| path.clone());
+
+        candidates.push(BranchCleanupCandidate {
+            name: name.to_string(),
+            merged,
+            upstream_gone,
+            stale,
+            last_commit_time: Some(last_commit_time),
+            worktree_path,
+        });
+    }
+
+    Ok(candidates)
+}
+
+/// Removes the worktree (if any) of each branch and then deletes the branch.
+///
+/// Branches merged into `base` are deleted even without `delete_unmerged`,
+/// since the merge was verified here rather than against HEAD as
+/// `git branch -d` does. Worktrees with uncommitted changes are only removed
+/// with `discard_changes`, and locked ones never are; neither is the main
+/// worktree. A branch that cannot be cleaned up is reported with its error
+/// and the others are still processed. With `dry_run`, nothing is changed
+/// and the results describe what would happen, including refusals.
+pub fn cleanup_branches(
+    repo_path: &str,
+    branches: &[String],
+    base: &str,
+    delete_unmerged: bool,
+    discard_changes: bool,
+    dry_run: bool,
+) -> AppResult<Vec<BranchCleanupResult>> {
+    let repo = Repository::open(repo_path)?;
+    let base_oid = repo.revparse_single(base)?.peel_to_commit()?.id();
+    let main_workdir = main_repository(&repo)?
+        .workdir()
+        .map(|p| p.to_string_lossy().to_string());
+    // The branch itself is only deleted if merged or `delete_unmerged`, so
+    // commits unique to a worktree are never lost by removing it
+    let confirm = RemovalConfirmation {
+        uncommitted_changes: discard_changes,
+        unpushed_commits: true,
+        locked: false,
+    };
+
+    let mut results = Vec::new();
+
+    for name in branches {
+        let mut result = BranchCleanupResult {
+            name: name.clone(),
+            removed_worktree: None,
+            deleted: false,
+            error: None,
+        };
+
+        let worktree_path = match cleanable_worktree(
+            &repo,
+            name,
+            base_oid,
+            main_workdir.as_deref(),
+            delete_unmerged,
+        ) {
+            Ok(worktree_path) => worktree_path,
+            Err(e) => {
+                result.error = Some(e.to_string());
+                results.push(result);
+                continue;
+            }
+        };
+
+        if let Some(wt_path) = worktree_path {
+            let removal = if dry_run {
+                check_removal(repo_path, &wt_path, confirm)
+            } else {
+                remove_worktree(repo_path, &wt_path, confirm)
+            };
+            if let Err(e) = removal {
+                result.error = Some(e.to_string());
+                results.push(result);
+                continue;
+            }
+            result.removed_worktree = Some(wt_path);
+        }
+
+        // Merges were verified above, so the branch is deleted with `-D`
+        let deletion = if dry_run {
+            Ok(())
+        } else {
+            delete_branch(repo_path, name, true)
+        };
+        match deletion {
+            Ok(()) => result.deleted = true,
+            Err(e) => result.error = Some(e.to_string()),
+        }
+        results.push(result);
+    }
+
+    Ok(results)
+}
+
+/// Checks that [`cleanup_branches`] may delete `name` and returns the
+/// worktree it has to remove first, if any
+fn cleanable_worktree(
+    repo: &Repository,
+    name: &str,
+    base_oid: Oid,
+    main_workdir: Option<&str>,
+    delete_unmerged: bool,
+) -> AppResult<Option<String>> {
+    let tip = repo
+        .find_branch(name, BranchType::Local)?
+        .get()
+        .peel_to_commit()?
+        .id();
+
+    let worktree_path = branch_worktree(repo, name)?;
+    let in_main_worktree = worktree_path
+        .as_deref()
+        .zip(main_workdir)
+        .is_some_and(|(wt, main)| same_path(wt, main));
+    if in_main_worktree {
+        return Err(AppError::Other(
+            "Branch is checked out in the main worktree".to_string(),
+        ));
+    }
+
+    if !delete_unmerged && !is_merged_into(repo, tip, base_oid) {
+        return Err(AppError::BranchNotMerged(name.to_string()));
+    }
+
+    Ok(worktree_path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::process::Command as StdCommand;
+    use tempfile::TempDir;
+
+    fn create_test_repo() -> TempDir {
+        let temp_dir = TempDir::new().unwrap();
+        let repo_path = temp_dir.path();
+
+        StdCommand::new("git")
+            .current_dir(repo_path)
+            .args(["init"])
+            .output()
+            .unwrap();
+
+        StdCommand::new("git")
+            .current_dir(repo_path)
+            .args(["config", "user.email", "test@test.com"])
+            .output()
+            .unwrap();
+
+        StdCommand::new("git")
+            .current_dir(repo_path)
+            .args(["config", "user.name", "Test User"])
+            .output()
+            .unwrap();
+
+        fs::write(repo_path.join("README.md"), "# Test Repository").unwrap();
+
+        StdCommand::new("git")
+            .current_dir(repo_path)
+            .args(["add", "."])
+            .output()
+            .unwrap();
+
+        StdCommand::new("git")
+            .current_dir(repo_path)
+            .args(["commit", "-m", "Initial commit"])
+            .output()
+            .unwrap();
+
+        temp_dir
+    }
+
+    fn commit_file(repo_path: &std::path::Path, name: &str, message: &str) {
+        fs::write(repo_path.join(name), message).unwrap();
+        StdCommand::new("git")
+            .current_dir(repo_path)
+            .args(["add", name])
+            .output()
+            .unwrap();
+        StdCommand::new("git")
+            .current_dir(repo_path)
+            .args(["commit", "-m", message])
+            .output()
+            .unwrap();
+    }
+
+    fn branch_exists(repo_path: &str, name: &str) -> bool {
+        let repo = Repository::open(repo_path).unwrap();
+        let exists = repo.find_branch(name, BranchType::Local).is_ok();
+        exists
+    }
+
+    #[test]
+    fn test_create_branch_from_head() {
+        let temp_dir = create_test_repo();
+        let repo_path = temp_dir.path().to_str().unwrap();
+
+        create_branch(repo_path, "feature", None).unwrap();
+        assert!(branch_exists(repo_path, "feature"));
+    }
+
+    #[test]
+    fn test_create_branch_from_start_point() {
+        let temp_dir = create_test_repo();
+        let repo_path = temp_dir.path().to_str().unwrap();
+        let repo = Repository::open(repo_path).unwrap();
+        let first = repo.head().unwrap().target().unwrap();
+
+        commit_file(temp_dir.path(), "second.txt", "Second commit");
+
+        create_branch(repo_path, "from-first", Some(&first.to_string())).unwrap();
+
+        let branch = repo.find_branch("from-first", BranchType::Local).unwrap();
+        assert_eq!(branch.get().target(), Some(first));
+    }
+
+    #[test]
+    fn test_create_branch_invalid_name() {
+        let temp_dir = create_test_repo();
+        let repo_path = temp_dir.path().to_str().unwrap();
+
+        let result = create_branch(repo_path, "bad..name", None);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_create_branch_already_exists() {
+        let temp_dir = create_test_repo();
+        let repo_path = temp_dir.path().to_str().unwrap();
+
+        create_branch(repo_path, "dup", None).unwrap();
+        assert!(create_branch(repo_path, "dup", None).is_err());
+    }
+
+    #[test]
+    fn test_rename_branch() {
+        let temp_dir = create_test_repo();
+        let repo_path = temp_dir.path().to_str().unwrap();
+
+        create_branch(repo_path, "old-name", None).unwrap();
+        rename_branch(repo_path, "old-name", "new-name", false).unwrap();
+
+        assert!(!branch_exists(repo_path, "old-name"));
+        assert!(branch_exists(repo_path, "new-name"));
+    }
+
+    #[test]
+    fn test_rename_branch_onto_existing_requires_force() {
+        let temp_dir = create_test_repo();
+        let repo_path = temp_dir.path().to_str().unwrap();
+
+        create_branch(repo_path, "a", None).unwrap();
+        create_branch(repo_path, "b", None).unwrap();
+
+        assert!(rename_branch(repo_path, "a", "b", false).is_err());
+        rename_branch(repo_path, "a", "b", true).unwrap();
+        assert!(!branch_exists(repo_path, "a"));
+    }
+
+    #[test]
+    fn test_rename_branch_updates_linked_worktree() {
+        let temp_dir = create_test_repo();
+        let repo_path = temp_dir.path().to_str().unwrap();
+        let worktree_path = temp_dir.path().parent().unwrap().join("rename-wt");
+
+        StdCommand::new("git")
+            .current_dir(repo_path)
+            .args([
+                "worktree",
+                "add",
+                "-b",
+                "wt-branch",
+                worktree_path.to_str().unwrap(),
+            ])
+            .output()
+            .unwrap();
+
+        rename_branch(repo_path, "wt-branch", "wt-renamed", false).unwrap();
+
+        let wt_repo = Repository::open(&worktree_path).unwrap();
+        assert_eq!(wt_repo.head().unwrap().shorthand(), Some("wt-renamed"));
+
+        StdCommand::new("git")
+            .current_dir(repo_path)
+            .args(["worktree", "remove", worktree_path.to_str().unwrap()])
+            .output()
+            .unwrap();
+    }
+
+    #[test]
+    fn test_delete_merged_branch() {
+        let temp_dir = create_test_repo();
+        let repo_path = temp_dir.path().to_str().unwrap();
+
+        create_branch(repo_path, "merged", None).unwrap();
+        delete_branch(repo_path, "merged", false).unwrap();
+
+        assert!(!branch_exists(repo_path, "merged"));
+    }
+
+    #[test]
+    fn test_delete_unmerged_branch_requires_force() {
+        let temp_dir = create_test_repo();
+        let repo_path = temp_dir.path().to_str().unwrap();
+
+        StdCommand::new("git")
+            .current_dir(repo_path)
+            .args(["checkout", "-b", "unmerged"])
+            .output()
+            .unwrap();
+        commit_file(temp_dir.path(), "unmerged.txt", "Unmerged work");
+        StdCommand::new("git")
+            .current_dir(repo_path)
+            .args(["checkout", "-"])
+            .output()
+            .unwrap();
+
+        let result = delete_branch(repo_path, "unmerged", false);
+        assert!(matches!(result, Err(AppError::BranchNotMerged(_))));
+
+        delete_branch(repo_path, "unmerged", true).unwrap();
+        assert!(!branch_exists(repo_path, "unmerged"));
+    }
+
+    #[test]
+    fn test_delete_current_branch_refused() {
+        let temp_dir = create_test_repo();
+        let repo_path = temp_dir.path().to_str().unwrap();
+        let repo = Repository::open(repo_path).unwrap();
+        let current = repo.head().unwrap().shorthand().unwrap().to_string();
+
+        let result = delete_branch(repo_path, &current, true);
+        assert!(matches!(result, Err(AppError::BranchInUse(_))));
+    }
+
+    #[test]
+    fn test_delete_branch_in_linked_worktree_refused() {
+        let temp_dir = create_test_repo();
+        let repo_path = temp_dir.path().to_str().unwrap();
+        let worktree_path = temp_dir.path().parent().unwrap().join("delete-wt");
+
+        StdCommand::new("git")
+            .current_dir(repo_path)
+            .args([
+                "worktree",
+                "add",
+                "-b",
+                "in-use",
+                worktree_path.to_str().unwrap(),
+            ])
+            .output()
+            .unwrap();
+
+        let result = delete_branch(repo_path, "in-use", true);
+        assert!(matches!(result, Err(AppError::BranchInUse(_))));
+        assert!(branch_exists(repo_path, "in-use"));
+
+        StdCommand::new("git")
+            .current_dir(repo_path)
+            .args(["worktree", "remove", worktree_path.to_str().unwrap()])
+            .output()
+            .unwrap();
+    }
+
+    #[test]
+    fn test_set_and_unset_upstream() {
+        let temp_dir = create_test_repo();
+        let repo_path = temp_dir.path().to_str().unwrap();
+        let repo = Repository::open(repo_path).unwrap();
+        let current = repo.head().unwrap().shorthand().unwrap().to_string();
+
+        create_branch(repo_path, "tracking", None).unwrap();
+        set_upstream(repo_path, "tracking", &current).unwrap();
+
+        let branch = repo.find_branch("tracking", BranchType::Local).unwrap();
+        assert_eq!(
+            branch.upstream().unwrap().name().unwrap(),
+            Some(current.as_str())
+        );
+
+        unset_upstream(repo_path, "tracking").unwrap();
+        let branch = repo.find_branch("tracking", BranchType::Local).unwrap();
+        assert!(branch.upstream().is_err());
+    }
+
+    #[test]
+    fn test_checked_out_branches_includes_linked_worktrees() {
+        let temp_dir = create_test_repo();
+        let repo_path = temp_dir.path().to_str().unwrap();
+        let worktree_path = temp_dir.path().parent().unwrap().join("checked-out-wt");
+
+        StdCommand::new("git")
+            .current_dir(repo_path)
+            .args([
+                "worktree",
+                "add",
+                "-b",
+                "linked",
+                worktree_path.to_str().unwrap(),
+            ])
+            .output()
+            .unwrap();
+
+        let repo = Repository::open(repo_path).unwrap();
+        let owner = branch_worktree(&repo, "linked").unwrap();
+        assert!(owner.is_some());
+        assert!(owner.unwrap().contains("checked-out-wt"));
+
+        StdCommand::new("git")
+            .current_dir(repo_path)
+            .args(["worktree", "remove", worktree_path.to_str().unwrap()])
+            .output()
+            .unwrap();
+    }
+
+    #[test]
+    fn test_analyze_branches_reports_merged_and_skips_unmerged() {
+        let temp_dir = create_test_repo();
+        let repo_path = temp_dir.path().to_str().unwrap();
+        let repo = Repository::open(repo_path).unwrap();
+        let base = repo.head().unwrap().shorthand().unwrap().to_string();
+
+        create_branch(repo_path, "done", None).unwrap();
+        StdCommand::new("git")
+            .current_dir(repo_path)
+            .args(["checkout", "-b", "wip"])
+            .output()
+            .unwrap();
+        commit_file(temp_dir.path(), "wip.txt", "Work in progress");
+        StdCommand::new("git")
+            .current_dir(repo_path)
+            .args(["checkout", &base])
+            .output()
+            .unwrap();
+
+        let candidates = analyze_branches(repo_path, &base, 30).unwrap();
+
+        let done = candidates.iter().find(|c| c.name == "done").unwrap();
+        assert!(done.merged);
+        assert!(!done.stale);
+        assert!(!candidates.iter().any(|c| c.name == "wip"));
+        assert!(!candidates.iter().any(|c| c.name == base));
+    }
+
+    #[test]
+    fn test_analyze_branches_reports_stale() {
+        let temp_dir = create_test_repo();
+        let repo_path = temp_dir.path().to_str().unwrap();
+        let repo = Repository::open(repo_path).unwrap();
+        let base = repo.head().unwrap().shorthand().unwrap().to_string();
+
+        StdCommand::new("git")
+            .current_dir(repo_path)
+            .args(["checkout", "-b", "old"])
+            .output()
+            .unwrap();
+        fs::write(temp_dir.path().join("old.txt"), "old").unwrap();
+        StdCommand::new("git")
+            .current_dir(repo_path)
+            .args(["add", "old.txt"])
+            .output()
+            .unwrap();
+        StdCommand::new("git")
+            .current_dir(repo_path)
+            .env("GIT_AUTHOR_DATE", "2000-01-01T00:00:00Z")
+            .env("GIT_COMMITTER_DATE", "2000-01-01T00:00:00Z")
+            .args(["commit", "-m", "Old commit"])
+            .output()
+            .unwrap();
+        StdCommand::new("git")
+            .current_dir(repo_path)
+            .args(["checkout", &base])
+            .output()
+            .unwrap();
+
+        let candidates = analyze_branches(repo_path, &base, 30).unwrap();
+        let old = candidates.iter().find(|c| c.name == "old").unwrap();
+        assert!(old.stale);
+        assert!(!old.merged);
+    }
+
+    #[test]
+    fn test_cleanup_branches_dry_run_changes_nothing() {
+        let temp_dir = create_test_repo();
+        let repo_path = temp_dir.path().to_str().unwrap();
+        let repo = Repository::open(repo_path).unwrap();
+        let base = repo.head().unwrap().shorthand().unwrap().to_string();
+
+        create_branch(repo_path, "finished", None).unwrap();
+
+        let results = cleanup_branches(
+            repo_path,
+            &["finished".to_string()],
+            &base,
+            false,
+            false,
+            true,
+        )
+        .unwrap();
+
+        assert_eq!(results.len(), 1);
+        assert!(results[0].deleted);
+        assert!(results[0].error.is_none());
+        assert!(branch_exists(repo_path, "finished"));
+    }
+
+    #[test]
+    fn test_cleanup_branches_removes_worktree_then_branch() {
+        let temp_dir = create_test_repo();
+        let repo_path = temp_dir.path().to_str().unwrap();
+        let repo = Repository::open(repo_path).unwrap();
+        let base = repo.head().unwrap().shorthand().unwrap().to_string();
+        let worktree_path = temp_dir.path().parent().unwrap().join("cleanup-wt");
+
+        StdCommand::new("git")
+            .current_dir(repo_path)
+            .args([
+                "worktree",
+                "add",
+                "-b",
+                "merged-wt",
+                worktree_path.to_str().unwrap(),
+            ])
+            .output()
+            .unwrap();
+
+        let results = cleanup_branches(
+            repo_path,
+            &["merged-wt".to_string()],
+            &base,
+            false,
+            false,
+            false,
+        )
+        .unwrap();
+
+        assert!(results[0].error.is_none());
+        assert!(results[0].removed_worktree.is_some());
+        assert!(results[0].deleted);
+        assert!(!worktree_path.exists());
+        assert!(!branch_exists(repo_path, "merged-wt"));
+    }
+
+    #[test]
+    fn test_cleanup_branches_refuses_main_worktree_branch() {
+        let temp_dir = create_test_repo();
+        let repo_path = temp_dir.path().to_str().unwrap();
+        let repo = Repository::open(repo_path).unwrap();
+        let base = repo.head().unwrap().shorthand().unwrap().to_string();
+
+        let results = cleanup_branches(
+            repo_path,
+            std::slice::from_ref(&base),
+            &base,
+            true,
+            true,
+            false,
+        )
+        .unwrap();
+
+        assert!(results[0].error.is_some());
+        assert!(!results[0].deleted);
+        assert!(branch_exists(repo_path, &base));
+    }
+
+    #[test]
+    fn test_cleanup_branches_dry_run_reports_refusals() {
+        let temp_dir = create_test_repo();
+        let repo_path = temp_dir.path().to_str().unwrap();
+        let repo = Repository::open(repo_path).unwrap();
+        let base = repo.head().unwrap().shorthand().unwrap().to_string();
+        let worktree_dir = TempDir::new().unwrap();
+        let worktree_path = worktree_dir.path().join("dirty-cleanup-wt");
+
+        create_branch(repo_path, "unmerged", None).unwrap();
+        StdCommand::new("git")
+            .current_dir(repo_path)
+            .args([
+                "worktree",
+                "add",
+                "-b",
+                "dirty",
+                worktree_path.to_str().unwrap(),
+            ])
+            .output()
+            .unwrap();
+        fs::write(worktree_path.join("wip.txt"), "wip").unwrap();
+        StdCommand::new("git")
+            .current_dir(repo_path)
+            .args(["checkout", "-q", "unmerged"])
+            .output()
+            .unwrap();
+        commit_file(temp_dir.path(), "unmerged.txt", "Unmerged work");
+        StdCommand::new("git")
+            .current_dir(repo_path)
+            .args(["checkout", "-q", &base])
+            .output()
+            .unwrap();
+
+        let branches = ["unmerged".to_string(), "dirty".to_string()];
+        let results = cleanup_branches(repo_path, &branches, &base, false, false, true).unwrap();
+        assert!(!results[0].deleted);
+        assert!(results[0]
+            .error
+            .as_ref()
+            .unwrap()
+            .contains("not fully merged"));
+        assert!(!results[1].deleted);
+        assert_eq!(
+            results[1].error.as_deref(),
+            Some("Worktree has uncommitted changes")
+        );
+
+        let results = cleanup_branches(repo_path, &branches, &base, true, true, true).unwrap();
+        assert!(results.iter().all(|r| r.deleted && r.error.is_none()));
+        assert!(results[1].removed_worktree.is_some());
+        assert!(branch_exists(repo_path, "unmerged"));
+        assert!(worktree_path.join("wip.txt").exists());
+    }
+
+    #[test]
+    fn test_cleanup_branches_discard_changes_keeps_unmerged_branch() {
+        let temp_dir = create_test_repo();
+        let repo_path = temp_dir.path().to_str().unwrap();
+        let repo = Repository::open(repo_path).unwrap();
+        let base = repo.head().unwrap().shorthand().unwrap().to_string();
+        let worktree_dir = TempDir::new().unwrap();
+        let worktree_path = worktree_dir.path().join("unmerged-cleanup-wt");
+
+        StdCommand::new("git")
+            .current_dir(repo_path)
+            .args([
+                "worktree",
+                "add",
+                "-b",
+                "wip",
+                worktree_path.to_str().unwrap(),
+            ])
+            .output()
+            .unwrap();
+        commit_file(&worktree_path, "wip.txt", "Work in progress");
+
+        let branches = ["wip".to_string(), "missing".to_string()];
+        let results = cleanup_branches(repo_path, &branches, &base, false, true, false).unwrap();
+
+        assert!(!results[0].deleted);
+        assert!(results[0].removed_worktree.is_none());
+        assert!(worktree_path.exists());
+        assert!(branch_exists(repo_path, "wip"));
+        assert!(results[1].error.is_some());
+    }
+}
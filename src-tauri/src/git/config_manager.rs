@@ -0,0 +1,435 @@
+use crate::commands::config::{ConfigEntry, ConfigScope};
+use crate::error::{AppError, AppResult};
+use crate::git::executable::Feature;
+use crate::git::runner::{git, git_for, GitOutput};
+use crate::git::worktree_manager::{common_dir, validate_repository};
+use std::ffi::OsStr;
+use std::path::Path;
+
+/// Runs `git config <args>` in a worktree, leaving the exit status to the
+/// caller since git uses it to report missing keys
+fn git_config<S: AsRef<OsStr>>(worktree_path: &Path, args: &[S]) -> AppResult<GitOutput> {
+    git(worktree_path)?.arg("config").args(args).output()
+}
+
+fn run_config<S: AsRef<OsStr>>(worktree_path: &Path, args: &[S]) -> AppResult<()> {
+    git_config(worktree_path, args)?.check()?;
+    Ok(())
+}
+
+fn parse_scope(scope: &str) -> Option<ConfigScope> {
+    match scope {
+        "system" => Some(ConfigScope::System),
+        "global" => Some(ConfigScope::Global),
+        "local" => Some(ConfigScope::Local),
+        "worktree" => Some(ConfigScope::Worktree),
+        "command" => Some(ConfigScope::Command),
+        _ => None,
+    }
+}
+
+/// Parses `git config --list --show-scope -z`, where each entry is
+/// `scope NUL name [LF value] NUL`
+fn parse_list(output: &str) -> Vec<ConfigEntry> {
+    let mut fields = output.split('\0');
+    let mut entries = Vec::new();
+
+    while let (Some(scope), Some(item)) = (fields.next(), fields.next()) {
+        let Some(scope) = parse_scope(scope) else {
+            continue;
+        };
+        let (name, value) = match item.split_once('\n') {
+            Some((name, value)) => (name, Some(value.to_string())),
+            None => (item, None),
+        };
+        entries.push(ConfigEntry {
+            name: name.to_string(),
+            value,
+            scope,
+        });
+    }
+
+    entries
+}
+
+/// Lowercases the section and key of a name, which git matches without
+/// regard to case, keeping the subsection in between as written
+fn normalize_name(name: &str) -> String {
+    match (name.find('.'), name.rfind('.')) {
+        (Some(first), Some(last)) => format!(
+            "{}{}{}",
+            name[..first].to_lowercase(),
+            &name[first..last],
+            name[last..].to_lowercase()
+        ),
+        _ => name.to_lowercase(),
+    }
+}
+
+/// Lists config visible from a worktree in the order git reads it, so for
+/// each name the last entry is the effective one
+pub fn list_config(worktree_path: &str, scope: Option<ConfigScope>) -> AppResult<Vec<ConfigEntry>> {
+    validate_repository(worktree_path)?;
+    let output = git_for(Feature::ConfigScopes, worktree_path)?
+        .args(["config", "--list", "--show-scope", "-z"])
+        .run()?;
+
+    let mut entries = parse_list(&output);
+    if let Some(scope) = scope {
+        entries.retain(|entry| entry.scope == scope);
+    }
+    Ok(entries)
+}
+
+/// Returns the value git uses in a worktree and the scope it comes from
+pub fn get_config(worktree_path: &str, name: &str) -> AppResult<Option<ConfigEntry>> {
+    let name = normalize_name(name);
+    Ok(list_config(worktree_path, None)?
+        .into_iter()
+        .rfind(|entry| entry.name == name))
+}
+
+pub fn worktree_config_enabled(worktree_path: &str) -> AppResult<bool> {
+    validate_repository(worktree_path)?;
+    let output = git_config(
+        Path::new(worktree_path),
+        &["--type=bool", "--get", "extensions.worktreeConfig"],
+    )?;
+    Ok(output.success() && output.stdout.trim() == "true")
+}
+
+/// Turns on `extensions.worktreeConfig`. As git documents, `core.bare` and
+/// `core.worktree` must then move from the shared config to the main
+/// worktree's own, or every linked worktree would pick them up.
+fn enable_worktree_config(worktree_path: &str) -> AppResult<()> {
+    if worktree_config_enabled(worktree_path)? {
+        return Ok(());
+    }
+
+    let repo = validate_repository(worktree_path)?;
+    let path = Path::new(worktree_path);
+    let common = common_dir(&repo);
+    let shared = common.join("config");
+    let main = common.join("config.worktree");
+
+    for key in ["core.bare", "core.worktree"] {
+        let output = git_config(
+            path,
+            &[
+                OsStr::new("--file"),
+                shared.as_os_str(),
+                OsStr::new("--get"),
+                OsStr::new(key),
+            ],
+        )?;
+        if !output.success() {
+            continue;
+        }
+        let value = output.stdout.trim();
+        // A non-bare repository's worktrees can all share `bare = false`
+        if key == "core.bare" && value != "true" {
+            continue;
+        }
+        run_config(
+            path,
+            &[
+                OsStr::new("--file"),
+                main.as_os_str(),
+                OsStr::new(key),
+                OsStr::new(value),
+            ],
+        )?;
+        run_config(
+            path,
+            &[
+                OsStr::new("--file"),
+                shared.as_os_str(),
+                OsStr::new("--unset"),
+                OsStr::new(key),
+            ],
+        )?;
+    }
+
+    run_config(path, &["--local", "extensions.worktreeConfig", "true"])
+}
+
+/// Checks that `name` has the form `section[.subsection].key` git accepts:
+/// section and key of letters, digits and `-`, the key starting with a
+/// letter, and a subsection of anything but newlines and NUL
+fn validate_name(name: &str) -> AppResult<()> {
+    let invalid = || AppError::Other(format!("Invalid config name: {}", name));
+    let (Some(first), Some(last)) = (name.find('.'), name.rfind('.')) else {
+        return Err(invalid());
+    };
+    let (section, subsection, key) = (&name[..first], &name[first..last], &name[last + 1..]);
+
+    let is_word = |part: &str| {
+        !part.is_empty() && part.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+    };
+    if !is_word(section)
+        || !is_word(key)
+        || !key.starts_with(|c: char| c.is_ascii_alphabetic())
+        || subsection.contains(['\n', '\0'])
+    {
+        return Err(invalid());
+    }
+    Ok(())
+}
+
+fn writable_scope_flag(scope: ConfigScope) -> AppResult<String> {
+    if scope == ConfigScope::Command {
+        return Err(AppError::Other(
+            "Config passed on the command line cannot be changed".to_string(),
+        ));
+    }
+    Ok(format!("--{}", scope.as_str()))
+}
+
+/// Sets a value at the given scope, replacing all existing values of the
+/// name there. Writing worktree config enables it for the repository first.
+pub fn set_config(
+    worktree_path: &str,
+    name: &str,
+    value: &str,
+    scope: ConfigScope,
+) -> AppResult<()> {
+    let flag = writable_scope_flag(scope)?;
+    validate_name(name)?;
+    validate_repository(worktree_path)?;
+    if scope == ConfigScope::Worktree {
+        enable_worktree_config(worktree_path)?;
+    }
+
+    run_config(
+        Path::new(worktree_path),
+        &[flag.as_str(), "--replace-all", "--", name, value],
+    )
+}
+
+pub fn unset_config(worktree_path: &str, name: &str, scope: ConfigScope) -> AppResult<()> {
+    let flag = writable_scope_flag(scope)?;
+    validate_name(name)?;
+    validate_repository(worktree_path)?;
+
+    let not_set = || AppError::Other(format!("{} is not set in {} config", name, scope.as_str()));
+    // Without the extension git would fall back to the shared config
+    if scope == ConfigScope::Worktree && !worktree_config_enabled(worktree_path)? {
+        return Err(not_set());
+    }
+
+    let output = git_config(
+        Path::new(worktree_path),
+        &[flag.as_str(), "--unset-all", "--", name],
+    )?;
+    match output.exit_code {
+        Some(0) => Ok(()),
+        Some(5) => Err(not_set()),
+        _ => Err(output.error()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::worktree_manager::add_worktree;
+    use git2::Repository;
+    use std::fs;
+    use std::process::Command as StdCommand;
+    use tempfile::TempDir;
+
+    fn create_test_repo() -> TempDir {
+        let temp_dir = TempDir::new().unwrap();
+        let repo_path = temp_dir.path();
+
+        StdCommand::new("git")
+            .current_dir(repo_path)
+            .args(["init"])
+            .output()
+            .unwrap();
+
+        StdCommand::new("git")
+            .current_dir(repo_path)
+            .args(["config", "user.email", "test@test.com"])
+            .output()
+            .unwrap();
+
+        StdCommand::new("git")
+            .current_dir(repo_path)
+            .args(["config", "user.name", "Test User"])
+            .output()
+            .unwrap();
+
+        fs::write(repo_path.join("README.md"), "# Test Repository").unwrap();
+
+        StdCommand::new("git")
+            .current_dir(repo_path)
+            .args(["add", "."])
+            .output()
+            .unwrap();
+
+        StdCommand::new("git")
+            .current_dir(repo_path)
+            .args(["commit", "-m", "Initial commit"])
+            .output()
+            .unwrap();
+
+        temp_dir
+    }
+
+    fn git_get(dir: &Path, args: &[&str]) -> String {
+        let output = StdCommand::new("git")
+            .current_dir(dir)
+            .args(args)
+            .output()
+            .unwrap();
+        String::from_utf8_lossy(&output.stdout).trim().to_string()
+    }
+
+    #[test]
+    fn test_parse_list() {
+        let entries =
+            parse_list("global\0user.name\nTest User\0local\0x.y\0local\0a.b\nline1\nline2\0");
+
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[0].scope, ConfigScope::Global);
+        assert_eq!(entries[0].name, "user.name");
+        assert_eq!(entries[0].value.as_deref(), Some("Test User"));
+        assert_eq!(entries[1].name, "x.y");
+        assert_eq!(entries[1].value, None);
+        assert_eq!(entries[2].value.as_deref(), Some("line1\nline2"));
+    }
+
+    #[test]
+    fn test_normalize_name() {
+        assert_eq!(normalize_name("User.Email"), "user.email");
+        assert_eq!(normalize_name("Remote.MyOrigin.URL"), "remote.MyOrigin.url");
+    }
+
+    #[test]
+    fn test_validate_name() {
+        assert!(validate_name("user.email").is_ok());
+        assert!(validate_name("remote.My Origin.url").is_ok());
+        assert!(validate_name("branch.feature/x.merge").is_ok());
+        assert!(validate_name("core-x.key-2").is_ok());
+
+        assert!(validate_name("--file=/tmp/config").is_err());
+        assert!(validate_name("noDot").is_err());
+        assert!(validate_name(".key").is_err());
+        assert!(validate_name("section.").is_err());
+        assert!(validate_name("section.2key").is_err());
+        assert!(validate_name("sec tion.key").is_err());
+        assert!(validate_name("a.sub\nsection.key").is_err());
+    }
+
+    #[test]
+    fn test_set_config_value_starting_with_dash() {
+        let temp_dir = create_test_repo();
+        let repo_path = temp_dir.path().to_str().unwrap();
+
+        set_config(repo_path, "wtview.flag", "-x", ConfigScope::Local).unwrap();
+        let entry = get_config(repo_path, "wtview.flag").unwrap().unwrap();
+        assert_eq!(entry.value.as_deref(), Some("-x"));
+
+        let result = set_config(repo_path, "--file=/tmp/x", "y", ConfigScope::Local);
+        assert!(matches!(result, Err(AppError::Other(_))));
+    }
+
+    #[test]
+    fn test_get_config_reports_scope() {
+        let temp_dir = create_test_repo();
+        let repo_path = temp_dir.path().to_str().unwrap();
+
+        let entry = get_config(repo_path, "User.Email").unwrap().unwrap();
+        assert_eq!(entry.value.as_deref(), Some("test@test.com"));
+        assert_eq!(entry.scope, ConfigScope::Local);
+
+        assert!(get_config(repo_path, "wtview.missing").unwrap().is_none());
+    }
+
+    #[test]
+    fn test_worktree_config_is_per_worktree() {
+        let temp_dir = create_test_repo();
+        let repo_path = temp_dir.path().to_str().unwrap();
+        let worktree_path = temp_dir.path().parent().unwrap().join(format!(
+            "{}-config",
+            temp_dir.path().file_name().unwrap().to_str().unwrap()
+        ));
+        add_worktree(repo_path, &worktree_path, "client", true).unwrap();
+        let worktree = worktree_path.to_str().unwrap();
+
+        assert!(!worktree_config_enabled(worktree).unwrap());
+        set_config(
+            worktree,
+            "user.email",
+            "me@client.com",
+            ConfigScope::Worktree,
+        )
+        .unwrap();
+        assert!(worktree_config_enabled(repo_path).unwrap());
+
+        let entry = get_config(worktree, "user.email").unwrap().unwrap();
+        assert_eq!(entry.value.as_deref(), Some("me@client.com"));
+        assert_eq!(entry.scope, ConfigScope::Worktree);
+
+        let entry = get_config(repo_path, "user.email").unwrap().unwrap();
+        assert_eq!(entry.value.as_deref(), Some("test@test.com"));
+        assert_eq!(entry.scope, ConfigScope::Local);
+
+        let worktree_entries = list_config(worktree, Some(ConfigScope::Worktree)).unwrap();
+        assert_eq!(worktree_entries.len(), 1);
+        Repository::open(worktree).unwrap();
+
+        unset_config(worktree, "user.email", ConfigScope::Worktree).unwrap();
+        let entry = get_config(worktree, "user.email").unwrap().unwrap();
+        assert_eq!(entry.scope, ConfigScope::Local);
+
+        fs::remove_dir_all(&worktree_path).ok();
+    }
+
+    #[test]
+    fn test_enable_worktree_config_moves_core_bare() {
+        let temp_dir = create_test_repo();
+        let bare = temp_dir.path().join("bare.git");
+        StdCommand::new("git")
+            .current_dir(temp_dir.path())
+            .args(["clone", "--bare", ".", "bare.git"])
+            .output()
+            .unwrap();
+        let bare_path = bare.to_str().unwrap();
+        let worktree_path = temp_dir.path().join("linked");
+        add_worktree(bare_path, &worktree_path, "linked", true).unwrap();
+        let worktree = worktree_path.to_str().unwrap();
+
+        set_config(worktree, "wtview.test", "yes", ConfigScope::Worktree).unwrap();
+
+        assert_eq!(
+            git_get(&bare, &["config", "--file", "config", "core.bare"]),
+            ""
+        );
+        assert_eq!(
+            git_get(&bare, &["rev-parse", "--is-bare-repository"]),
+            "true"
+        );
+        assert_eq!(
+            git_get(&worktree_path, &["rev-parse", "--is-bare-repository"]),
+            "false"
+        );
+        assert!(!Repository::open(worktree).unwrap().is_bare());
+        assert!(Repository::open(bare_path).unwrap().is_bare());
+    }
+
+    #[test]
+    fn test_unset_config_errors() {
+        let temp_dir = create_test_repo();
+        let repo_path = temp_dir.path().to_str().unwrap();
+
+        let result = unset_config(repo_path, "wtview.missing", ConfigScope::Local);
+        assert!(matches!(result, Err(AppError::Other(_))));
+
+        let result = unset_config(repo_path, "user.email", ConfigScope::Worktree);
+        assert!(matches!(result, Err(AppError::Other(_))));
+
+        let result = set_config(repo_path, "user.email", "x", ConfigScope::Command);
+        assert!(matches!(result, Err(AppError::Other(_))));
+    }
+}
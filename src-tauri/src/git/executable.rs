@@ -0,0 +1,309 @@
+use crate::commands::executable::{GitCapabilities, GitExecutableInfo, GitVersion};
+use crate::error::{AppError, AppResult};
+use crate::git::settings;
+use std::env;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::RwLock;
+
+/// Oldest git with everything the app needs outside of `Feature`s
+pub const MINIMUM_VERSION: GitVersion = GitVersion {
+    major: 2,
+    minor: 20,
+    patch: 0,
+};
+
+#[cfg(windows)]
+const EXECUTABLE_NAME: &str = "git.exe";
+#[cfg(not(windows))]
+const EXECUTABLE_NAME: &str = "git";
+
+/// Where git is usually installed, for when the app is started from a
+/// desktop launcher with a minimal `PATH`
+#[cfg(windows)]
+const FALLBACK_DIRS: &[&str] = &[
+    r"C:\Program Files\Git\cmd",
+    r"C:\Program Files (x86)\Git\cmd",
+];
+#[cfg(not(windows))]
+const FALLBACK_DIRS: &[&str] = &[
+    "/usr/bin",
+    "/usr/local/bin",
+    "/opt/homebrew/bin",
+    "/opt/local/bin",
+];
+
+/// Operations that need a newer git than `MINIMUM_VERSION`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Feature {
+    WorktreeRepair,
+    SparseCheckout,
+    ConfigScopes,
+}
+
+impl Feature {
+    fn minimum_version(self) -> GitVersion {
+        let (major, minor) = match self {
+            Feature::WorktreeRepair => (2, 29),
+            Feature::SparseCheckout => (2, 35),
+            Feature::ConfigScopes => (2, 26),
+        };
+        GitVersion {
+            major,
+            minor,
+            patch: 0,
+        }
+    }
+
+    fn description(self) -> &'static str {
+        match self {
+            Feature::WorktreeRepair => "Repairing worktrees",
+            Feature::SparseCheckout => "Sparse checkout",
+            Feature::ConfigScopes => "Showing config scopes",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct GitExecutable {
+    path: PathBuf,
+    version: GitVersion,
+}
+
+/// The git found by the last successful lookup. Failures are not cached, so
+/// installing git takes effect without a restart.
+static RESOLVED: RwLock<Option<GitExecutable>> = RwLock::new(None);
+
+/// Parses the output of `git --version`, e.g. `git version 2.39.5` or
+/// `git version 2.45.1.windows.1`
+fn parse_version(output: &str) -> Option<GitVersion> {
+    let version = output.trim().strip_prefix("git version ")?;
+    let mut parts = version
+        .split(|c: char| !c.is_ascii_digit())
+        .map(|part| part.parse::<u32>().ok());
+    Some(GitVersion {
+        major: parts.next()??,
+        minor: parts.next()??,
+        patch: parts.next().flatten().unwrap_or(0),
+    })
+}
+
+/// Runs `git --version` to check an executable is a usable git
+fn probe(path: &Path) -> AppResult<GitExecutable> {
+    let output = Command::new(path)
+        .arg("--version")
+        .output()
+        .map_err(|e| AppError::GitNotFound(format!("cannot run {}: {}", path.display(), e)))?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let version = parse_version(&stdout).ok_or_else(|| {
+        AppError::GitNotFound(format!("{} is not a git executable", path.display()))
+    })?;
+
+    check_minimum_version(path, version)?;
+
+    Ok(GitExecutable {
+        path: path.to_path_buf(),
+        version,
+    })
+}
+
+fn check_minimum_version(path: &Path, version: GitVersion) -> AppResult<()> {
+    if version < MINIMUM_VERSION {
+        return Err(AppError::GitTooOld(format!(
+            "{} is version {}, but at least {} is required",
+            path.display(),
+            version,
+            MINIMUM_VERSION
+        )));
+    }
+    Ok(())
+}
+
+fn find_in(dirs: impl IntoIterator<Item = PathBuf>) -> Option<PathBuf> {
+    dirs.into_iter()
+        .map(|dir| dir.join(EXECUTABLE_NAME))
+        .find(|candidate| candidate.is_file())
+}
+
+/// Finds git at the configured path, or else in `PATH` and then the usual
+/// install locations
+fn discover(configured: Option<&Path>) -> AppResult<GitExecutable> {
+    if let Some(path) = configured {
+        return probe(path);
+    }
+
+    let path_dirs = env::var_os("PATH")
+        .map(|paths| env::split_paths(&paths).collect::<Vec<_>>())
+        .unwrap_or_default();
+    let fallback_dirs = FALLBACK_DIRS.iter().map(PathBuf::from);
+
+    match find_in(path_dirs.into_iter().chain(fallback_dirs)) {
+        Some(path) => probe(&path),
+        None => Err(AppError::GitNotFound(
+            "git was not found in PATH or the usual install locations; set its path in the settings"
+                .to_string(),
+        )),
+    }
+}
+
+fn resolve() -> AppResult<GitExecutable> {
+    if let Some(git) = RESOLVED.read().unwrap_or_else(|e| e.into_inner()).as_ref() {
+        return Ok(git.clone());
+    }
+
+    let git = discover(settings::load().git_path.as_deref())?;
+    *RESOLVED.write().unwrap_or_else(|e| e.into_inner()) = Some(git.clone());
+    Ok(git)
+}
+
+/// A `git` command using the discovered executable, or an error saying why
+/// git cannot be run
+pub fn git_command() -> AppResult<Command> {
+    Ok(Command::new(resolve()?.path))
+}
+
+/// Path of the discovered git executable, for command lines git itself runs
+pub fn git_path() -> AppResult<PathBuf> {
+    Ok(resolve()?.path)
+}
+
+/// Like `git_command`, but first checks git is new enough for `feature`
+pub fn git_command_for(feature: Feature) -> AppResult<Command> {
+    let git = resolve()?;
+    let required = feature.minimum_version();
+    if git.version < required {
+        return Err(AppError::GitTooOld(format!(
+            "{} needs git {} or later, but {} is version {}",
+            feature.description(),
+            required,
+            git.path.display(),
+            git.version
+        )));
+    }
+    Ok(Command::new(git.path))
+}
+
+fn capabilities(version: GitVersion) -> GitCapabilities {
+    let supports = |feature: Feature| version >= feature.minimum_version();
+    GitCapabilities {
+        worktree_repair: supports(Feature::WorktreeRepair),
+        sparse_checkout: supports(Feature::SparseCheckout),
+        config_scopes: supports(Feature::ConfigScopes),
+    }
+}
+
+fn describe(configured: Option<&Path>, result: AppResult<GitExecutable>) -> GitExecutableInfo {
+    let configured_path = configured.map(|path| path.to_string_lossy().to_string());
+    match result {
+        Ok(git) => GitExecutableInfo {
+            path: Some(git.path.to_string_lossy().to_string()),
+            configured_path,
+            version: Some(git.version),
+            minimum_version: MINIMUM_VERSION,
+            capabilities: capabilities(git.version),
+            error: None,
+        },
+        Err(e) => GitExecutableInfo {
+            path: None,
+            configured_path,
+            version: None,
+            minimum_version: MINIMUM_VERSION,
+            capabilities: GitCapabilities::default(),
+            error: Some(e.to_string()),
+        },
+    }
+}
+
+/// Looks up git and reports what was found; run at startup so problems
+/// show before the first operation fails
+pub fn info() -> GitExecutableInfo {
+    describe(settings::load().git_path.as_deref(), resolve())
+}
+
+/// Saves the git executable to use, after checking it works; `None` goes
+/// back to looking git up
+pub fn set_configured_path(path: Option<&str>) -> AppResult<GitExecutableInfo> {
+    let configured = path.map(PathBuf::from);
+    let git = discover(configured.as_deref())?;
+
+    let mut saved = settings::load();
+    saved.git_path = configured;
+    settings::save(&saved)?;
+    *RESOLVED.write().unwrap_or_else(|e| e.into_inner()) = Some(git.clone());
+
+    Ok(describe(saved.git_path.as_deref(), Ok(git)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn version(major: u32, minor: u32, patch: u32) -> GitVersion {
+        GitVersion {
+            major,
+            minor,
+            patch,
+        }
+    }
+
+    #[test]
+    fn test_parse_version() {
+        assert_eq!(
+            parse_version("git version 2.39.5\n"),
+            Some(version(2, 39, 5))
+        );
+        assert_eq!(
+            parse_version("git version 2.45.1.windows.1"),
+            Some(version(2, 45, 1))
+        );
+        assert_eq!(
+            parse_version("git version 2.24.3 (Apple Git-128)"),
+            Some(version(2, 24, 3))
+        );
+        assert_eq!(parse_version("git version 2.40"), Some(version(2, 40, 0)));
+        assert_eq!(parse_version("hg version 6.0"), None);
+    }
+
+    #[test]
+    fn test_capabilities() {
+        let caps = capabilities(version(2, 30, 1));
+        assert!(caps.worktree_repair);
+        assert!(caps.config_scopes);
+        assert!(!caps.sparse_checkout);
+
+        let caps = capabilities(version(2, 35, 0));
+        assert!(caps.sparse_checkout);
+    }
+
+    #[test]
+    fn test_discover_finds_installed_git() {
+        let git = discover(None).unwrap();
+        assert!(git.version >= MINIMUM_VERSION);
+        assert!(git_command_for(Feature::ConfigScopes).is_ok());
+    }
+
+    #[test]
+    fn test_probe_missing_executable() {
+        let temp_dir = TempDir::new().unwrap();
+        let result = probe(&temp_dir.path().join("no-git"));
+        assert!(matches!(result, Err(AppError::GitNotFound(_))));
+    }
+
+    #[test]
+    fn test_find_in_and_minimum_version() {
+        let temp_dir = TempDir::new().unwrap();
+        let empty = TempDir::new().unwrap();
+        let fake = temp_dir.path().join(EXECUTABLE_NAME);
+        fs::write(&fake, "").unwrap();
+
+        let dirs = [empty.path().to_path_buf(), temp_dir.path().to_path_buf()];
+        assert_eq!(find_in(dirs), Some(fake.clone()));
+        assert_eq!(find_in([empty.path().to_path_buf()]), None);
+
+        let result = check_minimum_version(&fake, version(2, 17, 1));
+        assert!(matches!(result, Err(AppError::GitTooOld(_))));
+        assert!(check_minimum_version(&fake, MINIMUM_VERSION).is_ok());
+    }
+}
@@ -1,2 +1,19 @@
+pub mod askpass;
+pub mod audit_log;
+pub mod backend;
+pub mod branch_manager;
+pub mod config_manager;
+pub mod executable;
 pub mod operations;
+pub mod paths;
+pub mod rebase_manager;
+pub mod reflog_manager;
+pub mod remote_manager;
+pub mod runner;
+pub mod settings;
+pub mod sparse_manager;
+pub mod stash_manager;
+pub mod submodule_manager;
+pub mod trash_manager;
+pub mod undo_journal;
 pub mod worktree_manager;
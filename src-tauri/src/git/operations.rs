@@ -1,81 +1,91 @@
-use crate::commands::branches::BranchInfo;
-use crate::commands::git_ops::{FileStatus, GitStatusResult};
+use crate::commands::branches::{BranchInfo, CheckoutResult, LocalChangesMode};
+use crate::commands::git_ops::{
+    CherryPickOptions, FileStatus, GitStatusResult, ResetMode, ResetResult, SequencerResult,
+};
 use crate::error::{AppError, AppResult};
-use git2::{Repository, StatusOptions};
-use std::path::Path;
-use std::process::Command;
-
-/// Validates that a worktree path exists and is a directory
-fn validate_worktree_path(worktree_path: &str) -> AppResult<()> {
-    let path = Path::new(worktree_path);
+use crate::git::askpass;
+use crate::git::backend;
+use crate::git::branch_manager::{
+    branch_worktree, checked_out_branches, create_branch, delete_branch,
+};
+use crate::git::paths::{normalize_path, path_from_bytes, same_path};
+use crate::git::runner::{git, NETWORK_TIMEOUT};
+use crate::git::sparse_manager::skip_worktree_paths;
+use crate::git::stash_manager::{pop_stash, push_stash};
+use crate::git::trash_manager::trash_worktree;
+use git2::{BranchType, Repository, Status, StatusOptions};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+/// Normalizes a worktree path and validates that it exists and is a
+/// directory
+fn validate_worktree_path(worktree_path: impl AsRef<Path>) -> AppResult<PathBuf> {
+    let path = normalize_path(worktree_path);
     if !path.exists() {
         return Err(AppError::InvalidPath(format!(
             "Worktree path does not exist: {}",
-            worktree_path
+            path.display()
         )));
     }
     if !path.is_dir() {
         return Err(AppError::InvalidPath(format!(
             "Worktree path is not a directory: {}",
-            worktree_path
+            path.display()
         )));
     }
-    Ok(())
+    Ok(path)
 }
 
 pub fn fetch(worktree_path: &str) -> AppResult<String> {
-    validate_worktree_path(worktree_path)?;
+    let worktree_path = validate_worktree_path(worktree_path)?;
 
-    let output = Command::new("git")
-        .current_dir(worktree_path)
+    let output = git(worktree_path)?
         .args(["fetch", "--all"])
+        .askpass()
+        .timeout(NETWORK_TIMEOUT)
         .output()?;
 
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        return Err(AppError::Command(stderr.to_string()));
+    if !output.success() {
+        return Err(askpass::command_error(&output));
     }
 
-    let stdout = String::from_utf8_lossy(&output.stdout);
-    Ok(stdout.to_string())
+    Ok(output.stdout)
 }
 
 pub fn pull(worktree_path: &str) -> AppResult<String> {
-    validate_worktree_path(worktree_path)?;
+    let worktree_path = validate_worktree_path(worktree_path)?;
 
-    let output = Command::new("git")
-        .current_dir(worktree_path)
+    let output = git(worktree_path)?
         .args(["pull"])
+        .askpass()
+        .timeout(NETWORK_TIMEOUT)
         .output()?;
 
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        return Err(AppError::Command(stderr.to_string()));
+    if !output.success() {
+        return Err(askpass::command_error(&output));
     }
 
-    let stdout = String::from_utf8_lossy(&output.stdout);
-    Ok(stdout.to_string())
+    Ok(output.stdout)
 }
 
 pub fn push(worktree_path: &str) -> AppResult<String> {
-    validate_worktree_path(worktree_path)?;
+    let worktree_path = validate_worktree_path(worktree_path)?;
 
-    let output = Command::new("git")
-        .current_dir(worktree_path)
+    let output = git(worktree_path)?
         .args(["push"])
+        .askpass()
+        .timeout(NETWORK_TIMEOUT)
         .output()?;
 
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        return Err(AppError::Command(stderr.to_string()));
+    if !output.success() {
+        return Err(askpass::command_error(&output));
     }
 
-    let stdout = String::from_utf8_lossy(&output.stdout);
-    Ok(stdout.to_string())
+    Ok(output.stdout)
 }
 
-pub fn status(worktree_path: &str) -> AppResult<GitStatusResult> {
-    let repo = Repository::open(worktree_path)?;
+pub fn status(worktree_path: impl AsRef<Path>) -> AppResult<GitStatusResult> {
+    let repo = Repository::open(validate_worktree_path(worktree_path)?)?;
 
     let head = repo.head().ok();
     let branch = head.as_ref().and_then(|h| h.shorthand().map(String::from));
@@ -85,85 +95,55 @@ pub fn status(worktree_path: &str) -> AppResult<GitStatusResult> {
     opts.recurse_untracked_dirs(true);
 
     let statuses = repo.statuses(Some(&mut opts))?;
+    let sparse = skip_worktree_paths(&repo)?;
     let mut files = Vec::new();
 
     for entry in statuses.iter() {
-        let path = entry.path().unwrap_or("").to_string();
-        let status = entry.status();
+        let path = path_from_bytes(entry.path_bytes());
+        let mut status = entry.status();
+        if sparse.contains(entry.path_bytes()) {
+            status.remove(Status::WT_DELETED);
+        }
 
         // Handle staged (index) changes
-        if status.is_index_new() {
-            files.push(FileStatus {
-                path: path.clone(),
-                status: "added".to_string(),
-                staged: true,
-            });
+        let staged = if status.is_index_new() {
+            Some("added")
         } else if status.is_index_modified() {
-            files.push(FileStatus {
-                path: path.clone(),
-                status: "modified".to_string(),
-                staged: true,
-            });
+            Some("modified")
         } else if status.is_index_deleted() {
-            files.push(FileStatus {
-                path: path.clone(),
-                status: "deleted".to_string(),
-                staged: true,
-            });
+            Some("deleted")
         } else if status.is_index_renamed() {
-            files.push(FileStatus {
-                path: path.clone(),
-                status: "renamed".to_string(),
-                staged: true,
-            });
+            Some("renamed")
         } else if status.is_index_typechange() {
-            files.push(FileStatus {
-                path: path.clone(),
-                status: "typechange".to_string(),
-                staged: true,
-            });
+            Some("typechange")
+        } else {
+            None
+        };
+        if let Some(staged) = staged {
+            files.push(FileStatus::new(&path, staged, true));
         }
 
         // Handle unstaged (worktree) changes
-        if status.is_wt_new() {
-            files.push(FileStatus {
-                path: path.clone(),
-                status: "untracked".to_string(),
-                staged: false,
-            });
+        let unstaged = if status.is_wt_new() {
+            Some("untracked")
         } else if status.is_wt_modified() {
-            files.push(FileStatus {
-                path: path.clone(),
-                status: "modified".to_string(),
-                staged: false,
-            });
+            Some("modified")
         } else if status.is_wt_deleted() {
-            files.push(FileStatus {
-                path: path.clone(),
-                status: "deleted".to_string(),
-                staged: false,
-            });
+            Some("deleted")
         } else if status.is_wt_renamed() {
-            files.push(FileStatus {
-                path: path.clone(),
-                status: "renamed".to_string(),
-                staged: false,
-            });
+            Some("renamed")
         } else if status.is_wt_typechange() {
-            files.push(FileStatus {
-                path: path.clone(),
-                status: "typechange".to_string(),
-                staged: false,
-            });
+            Some("typechange")
+        } else {
+            None
+        };
+        if let Some(unstaged) = unstaged {
+            files.push(FileStatus::new(&path, unstaged, false));
         }
 
         // Handle conflicted files
         if status.is_conflicted() {
-            files.push(FileStatus {
-                path,
-                status: "conflicted".to_string(),
-                staged: false,
-            });
+            files.push(FileStatus::new(&path, "conflicted", false));
         }
     }
 
@@ -185,7 +165,9 @@ fn get_ahead_behind(repo: &Repository) -> Option<(u32, u32)> {
     let branch_name = head.shorthand()?;
     let upstream_name = format!("origin/{}", branch_name);
 
-    let upstream_ref = repo.find_reference(&format!("refs/remotes/{}", upstream_name)).ok()?;
+    let upstream_ref = repo
+        .find_reference(&format!("refs/remotes/{}", upstream_name))
+        .ok()?;
     let upstream_oid = upstream_ref.target()?;
 
     let (ahead, behind) = repo.graph_ahead_behind(local_oid, upstream_oid).ok()?;
@@ -193,60 +175,169 @@ fn get_ahead_behind(repo: &Repository) -> Option<(u32, u32)> {
 }
 
 pub fn commit(worktree_path: &str, message: &str) -> AppResult<String> {
-    validate_worktree_path(worktree_path)?;
+    let worktree_path = validate_worktree_path(worktree_path)?;
+
+    backend::current().commit(&worktree_path, message)
+}
 
-    let output = Command::new("git")
-        .current_dir(worktree_path)
-        .args(["commit", "-m", message])
+/// Moves HEAD's branch to `revision`. A hard reset first saves the
+/// worktree, uncommitted changes included, to the trash.
+pub fn reset(worktree_path: &str, revision: &str, mode: ResetMode) -> AppResult<ResetResult> {
+    let worktree_path = validate_worktree_path(worktree_path)?;
+    let repo = Repository::open(&worktree_path)?;
+    let previous_head = repo.head()?.peel_to_commit()?.id().to_string();
+    let target = repo
+        .revparse_single(revision)?
+        .peel_to_commit()?
+        .id()
+        .to_string();
+
+    let snapshot = if mode == ResetMode::Hard {
+        // Ref name components may not start with a dot
+        let name = worktree_path
+            .file_name()
+            .map(|n| n.to_string_lossy().trim_start_matches('.').to_string())
+            .filter(|n| !n.is_empty())
+            .unwrap_or_else(|| "worktree".to_string());
+        Some(trash_worktree(&worktree_path, &format!("{}-reset", name))?)
+    } else {
+        None
+    };
+
+    git(&worktree_path)?
+        .args(["reset", &format!("--{}", mode.as_str()), &target])
+        .run()?;
+
+    Ok(ResetResult {
+        previous_head,
+        snapshot,
+    })
+}
+
+/// Runs a sequencer command (revert, cherry-pick) that may stop on conflicts.
+///
+/// Stopping on conflicts is reported as an incomplete result rather than an
+/// error, so the caller can show the conflicted files.
+fn run_sequencer(worktree_path: &Path, args: &[&str]) -> AppResult<SequencerResult> {
+    let output = git(worktree_path)?
+        .env("GIT_EDITOR", "true")
+        .args(args)
         .output()?;
 
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        return Err(AppError::Command(stderr.to_string()));
+    let status = status(worktree_path)?;
+    if output.success() {
+        return Ok(SequencerResult {
+            completed: true,
+            status,
+        });
+    }
+    if status.files.iter().any(|f| f.status == "conflicted") {
+        return Ok(SequencerResult {
+            completed: false,
+            status,
+        });
+    }
+
+    Err(output.error())
+}
+
+/// Resolves each revision to the full oid of its commit, so nothing the
+/// caller passes reaches git's command line as an option
+fn resolve_commits(worktree_path: &Path, commits: &[String]) -> AppResult<Vec<String>> {
+    let repo = Repository::open(worktree_path)?;
+    let mut oids = Vec::new();
+    for commit in commits {
+        let commit = repo.revparse_single(commit)?.peel_to_commit()?;
+        oids.push(commit.id().to_string());
     }
+    Ok(oids)
+}
 
-    let stdout = String::from_utf8_lossy(&output.stdout);
-    Ok(stdout.to_string())
+/// Reverts `commits` in the given order, one revert commit each
+pub fn revert(worktree_path: &str, commits: &[String]) -> AppResult<SequencerResult> {
+    let worktree_path = validate_worktree_path(worktree_path)?;
+    if commits.is_empty() {
+        return Err(AppError::Other("No commits to revert".to_string()));
+    }
+
+    let oids = resolve_commits(&worktree_path, commits)?;
+    let mut args = vec!["revert", "--no-edit"];
+    args.extend(oids.iter().map(String::as_str));
+    run_sequencer(&worktree_path, &args)
 }
 
-pub fn stage(worktree_path: &str, file_path: &str) -> AppResult<()> {
-    validate_worktree_path(worktree_path)?;
+/// Continues a revert stopped on conflicts once they are resolved and staged
+pub fn revert_continue(worktree_path: &str) -> AppResult<SequencerResult> {
+    let worktree_path = validate_worktree_path(worktree_path)?;
+    run_sequencer(&worktree_path, &["revert", "--continue"])
+}
 
-    let output = Command::new("git")
-        .current_dir(worktree_path)
-        .args(["add", file_path])
-        .output()?;
+pub fn revert_abort(worktree_path: &str) -> AppResult<()> {
+    abort_sequencer(worktree_path, "revert")
+}
 
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        return Err(AppError::Command(stderr.to_string()));
+/// Applies `commits`, which may come from any branch, onto the worktree's
+/// HEAD in the given order
+pub fn cherry_pick(
+    worktree_path: &str,
+    commits: &[String],
+    options: CherryPickOptions,
+) -> AppResult<SequencerResult> {
+    let worktree_path = validate_worktree_path(worktree_path)?;
+    if commits.is_empty() {
+        return Err(AppError::Other("No commits to cherry-pick".to_string()));
     }
 
-    Ok(())
+    let oids = resolve_commits(&worktree_path, commits)?;
+    let mut args = vec!["cherry-pick"];
+    if options.record_origin {
+        args.push("-x");
+    }
+    if options.no_commit {
+        args.push("--no-commit");
+    }
+    args.extend(oids.iter().map(String::as_str));
+    run_sequencer(&worktree_path, &args)
 }
 
-pub fn unstage(worktree_path: &str, file_path: &str) -> AppResult<()> {
-    validate_worktree_path(worktree_path)?;
+/// Continues a cherry-pick stopped on conflicts once they are resolved and
+/// staged
+pub fn cherry_pick_continue(worktree_path: &str) -> AppResult<SequencerResult> {
+    let worktree_path = validate_worktree_path(worktree_path)?;
+    run_sequencer(&worktree_path, &["cherry-pick", "--continue"])
+}
 
-    let output = Command::new("git")
-        .current_dir(worktree_path)
-        .args(["restore", "--staged", file_path])
-        .output()?;
+pub fn cherry_pick_abort(worktree_path: &str) -> AppResult<()> {
+    abort_sequencer(worktree_path, "cherry-pick")
+}
 
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        return Err(AppError::Command(stderr.to_string()));
-    }
+/// Abandons a stopped revert or cherry-pick, restoring the previous HEAD
+fn abort_sequencer(worktree_path: &str, command: &str) -> AppResult<()> {
+    let worktree_path = validate_worktree_path(worktree_path)?;
 
+    git(worktree_path)?.args([command, "--abort"]).run()?;
     Ok(())
 }
 
+pub fn stage(worktree_path: impl AsRef<Path>, file_path: impl AsRef<Path>) -> AppResult<()> {
+    let worktree_path = validate_worktree_path(worktree_path)?;
+
+    backend::current().stage(&worktree_path, file_path.as_ref())
+}
+
+pub fn unstage(worktree_path: impl AsRef<Path>, file_path: impl AsRef<Path>) -> AppResult<()> {
+    let worktree_path = validate_worktree_path(worktree_path)?;
+
+    backend::current().unstage(&worktree_path, file_path.as_ref())
+}
+
 pub fn list_branches(repo_path: &str) -> AppResult<Vec<BranchInfo>> {
     let repo = Repository::open(repo_path)?;
     let mut branches = Vec::new();
 
     let head = repo.head().ok();
     let current_branch = head.as_ref().and_then(|h| h.shorthand().map(String::from));
+    let checked_out = checked_out_branches(&repo)?;
 
     for branch_result in repo.branches(None)? {
         let (branch, branch_type) = branch_result?;
@@ -254,10 +345,36 @@ pub fn list_branches(repo_path: &str) -> AppResult<Vec<BranchInfo>> {
             let is_remote = branch_type == git2::BranchType::Remote;
             let is_current = !is_remote && Some(name.to_string()) == current_branch;
 
+            let tip = branch.get().peel_to_commit().ok();
+            let last_commit_summary = tip.as_ref().and_then(|c| c.summary().map(String::from));
+            let last_commit_time = tip.as_ref().map(|c| c.time().seconds());
+
+            let (upstream, upstream_gone, ahead, behind) = if is_remote {
+                (None, false, 0, 0)
+            } else {
+                upstream_tracking(&repo, &branch)
+            };
+
+            let worktree_path = if is_remote {
+                None
+            } else {
+                checked_out
+                    .iter()
+                    .find(|(branch_name, _)| branch_name == name)
+                    .map(|(_, path)| path.clone())
+            };
+
             branches.push(BranchInfo {
                 name: name.to_string(),
                 is_remote,
                 is_current,
+                upstream,
+                upstream_gone,
+                ahead,
+                behind,
+                last_commit_summary,
+                last_commit_time,
+                worktree_path,
             });
         }
     }
@@ -265,20 +382,212 @@ pub fn list_branches(repo_path: &str) -> AppResult<Vec<BranchInfo>> {
     Ok(branches)
 }
 
-pub fn checkout(worktree_path: &str, branch: &str) -> AppResult<()> {
-    validate_worktree_path(worktree_path)?;
+/// Returns the upstream name, whether it is gone, and ahead/behind counts
+/// for a local branch. A branch without configured upstream yields `None`.
+fn upstream_tracking(repo: &Repository, branch: &git2::Branch) -> (Option<String>, bool, u32, u32) {
+    let Some(refname) = branch.get().name() else {
+        return (None, false, 0, 0);
+    };
+    let Ok(upstream_ref) = repo.branch_upstream_name(refname) else {
+        return (None, false, 0, 0);
+    };
+    let Some(upstream_ref) = upstream_ref.as_str() else {
+        return (None, false, 0, 0);
+    };
+
+    let upstream = upstream_ref
+        .strip_prefix("refs/remotes/")
+        .or_else(|| upstream_ref.strip_prefix("refs/heads/"))
+        .unwrap_or(upstream_ref)
+        .to_string();
+
+    let upstream_oid = repo
+        .find_reference(upstream_ref)
+        .ok()
+        .and_then(|r| r.target());
+    let (Some(local_oid), Some(upstream_oid)) = (branch.get().target(), upstream_oid) else {
+        return (Some(upstream), true, 0, 0);
+    };
+
+    let (ahead, behind) = repo
+        .graph_ahead_behind(local_oid, upstream_oid)
+        .unwrap_or((0, 0));
+    (Some(upstream), false, ahead as u32, behind as u32)
+}
 
-    let output = Command::new("git")
-        .current_dir(worktree_path)
-        .args(["checkout", branch])
-        .output()?;
+/// Resolves the local branch to check out for `branch`, creating it from a
+/// remote-tracking branch when no local branch of that name exists.
+///
+/// Returns the local branch name and, when it has to be created, the
+/// remote-tracking branch it will start from. Names that match neither are
+/// passed through unchanged so git can treat them as revisions.
+fn resolve_checkout_target(repo: &Repository, branch: &str) -> AppResult<(String, Option<String>)> {
+    if repo.find_branch(branch, BranchType::Local).is_ok() {
+        return Ok((branch.to_string(), None));
+    }
 
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        return Err(AppError::Command(stderr.to_string()));
+    // `origin/feature` names the remote-tracking branch directly
+    if repo.find_branch(branch, BranchType::Remote).is_ok() {
+        for remote in repo.remotes()?.iter().flatten() {
+            if let Some(local) = branch.strip_prefix(&format!("{}/", remote)) {
+                if repo.find_branch(local, BranchType::Local).is_ok() {
+                    return Ok((local.to_string(), None));
+                }
+                return Ok((local.to_string(), Some(branch.to_string())));
+            }
+        }
     }
 
-    Ok(())
+    // Otherwise look for a unique `<remote>/<branch>`, as `git checkout` does
+    let matches: Vec<String> = repo
+        .remotes()?
+        .iter()
+        .flatten()
+        .map(|remote| format!("{}/{}", remote, branch))
+        .filter(|candidate| repo.find_branch(candidate, BranchType::Remote).is_ok())
+        .collect();
+    if matches.len() == 1 {
+        return Ok((branch.to_string(), matches.into_iter().next()));
+    }
+
+    Ok((branch.to_string(), None))
+}
+
+/// Returns local changes that checking out `target` would overwrite: files
+/// that are modified, staged or untracked and also differ between HEAD and
+/// the target commit.
+fn changes_overwritten_by(repo: &Repository, target: &str) -> AppResult<Vec<String>> {
+    let Ok(target_tree) = repo.revparse_single(target).and_then(|o| o.peel_to_tree()) else {
+        return Ok(Vec::new());
+    };
+    let head_tree = repo.head().ok().and_then(|h| h.peel_to_tree().ok());
+
+    let diff = repo.diff_tree_to_tree(head_tree.as_ref(), Some(&target_tree), None)?;
+    let mut changed = HashSet::new();
+    for delta in diff.deltas() {
+        for file in [delta.old_file(), delta.new_file()] {
+            if let Some(path) = file.path() {
+                changed.insert(path.to_path_buf());
+            }
+        }
+    }
+
+    let mut opts = StatusOptions::new();
+    opts.include_untracked(true);
+    opts.recurse_untracked_dirs(true);
+
+    let mut overwritten = Vec::new();
+    for entry in repo.statuses(Some(&mut opts))?.iter() {
+        if entry.status() == Status::CURRENT || entry.status().is_ignored() {
+            continue;
+        }
+        if let Some(path) = entry.path() {
+            if changed.contains(Path::new(path)) {
+                overwritten.push(path.to_string());
+            }
+        }
+    }
+
+    Ok(overwritten)
+}
+
+/// Deletes the branch `checkout` created and pops the stash it pushed
+/// before failing with `error`, adding whatever could not be undone to it
+fn roll_back_checkout(
+    worktree_path: &str,
+    error: AppError,
+    created_branch: Option<&str>,
+    stashed: bool,
+) -> AppError {
+    let mut left_behind = Vec::new();
+    if let Some(branch) = created_branch {
+        if delete_branch(worktree_path, branch, true).is_err() {
+            left_behind.push(format!("created branch '{}' was kept", branch));
+        }
+    }
+    // The stash pushed by `checkout` is the most recent one
+    if stashed && pop_stash(worktree_path, 0, true).is_err() {
+        left_behind.push("local changes are still stashed in stash@{0}".to_string());
+    }
+
+    if left_behind.is_empty() {
+        error
+    } else {
+        AppError::Other(format!("{} ({})", error, left_behind.join("; ")))
+    }
+}
+
+/// Checks out a branch in a worktree.
+///
+/// A branch checked out in another worktree is refused with `BranchInUse`.
+/// A branch that only exists on a remote is created locally, tracking it.
+/// Uncommitted changes are handled according to `local_changes`.
+///
+/// If the checkout fails, the branch created for it is deleted and stashed
+/// changes are restored; whatever cannot be undone is named in the error.
+pub fn checkout(
+    worktree_path: &str,
+    branch: &str,
+    local_changes: LocalChangesMode,
+) -> AppResult<CheckoutResult> {
+    let worktree_dir = validate_worktree_path(worktree_path)?;
+    let repo = Repository::open(&worktree_dir)?;
+
+    let (local_branch, created_from) = resolve_checkout_target(&repo, branch)?;
+
+    if let Some(owner) = branch_worktree(&repo, &local_branch)? {
+        let is_this_worktree = repo.workdir().is_some_and(|w| same_path(&owner, w));
+        if !is_this_worktree {
+            return Err(AppError::BranchInUse(format!(
+                "{} ({})",
+                local_branch, owner
+            )));
+        }
+    }
+
+    let target = created_from.as_deref().unwrap_or(&local_branch);
+    let mut stashed = false;
+
+    match local_changes {
+        LocalChangesMode::Refuse => {
+            let overwritten = changes_overwritten_by(&repo, target)?;
+            if !overwritten.is_empty() {
+                return Err(AppError::CheckoutConflict(overwritten.join(", ")));
+            }
+        }
+        LocalChangesMode::Merge => {}
+        LocalChangesMode::Stash => {
+            let message = format!("wtview: before checkout of {}", local_branch);
+            stashed = push_stash(worktree_path, Some(&message), true, false, &[])?.is_some();
+        }
+    }
+
+    let mut created_branch = None;
+    if let Some(remote_branch) = &created_from {
+        if let Err(e) = create_branch(worktree_path, &local_branch, Some(remote_branch)) {
+            return Err(roll_back_checkout(worktree_path, e, None, stashed));
+        }
+        created_branch = Some(local_branch.as_str());
+    }
+
+    if let Err(e) = backend::current().checkout(
+        &worktree_dir,
+        &local_branch,
+        local_changes == LocalChangesMode::Merge,
+    ) {
+        return Err(roll_back_checkout(
+            worktree_path,
+            e,
+            created_branch,
+            stashed,
+        ));
+    }
+
+    Ok(CheckoutResult {
+        branch: local_branch,
+        created_from,
+        stashed,
+    })
 }
 
 #[cfg(test)]
@@ -480,6 +789,51 @@ mod tests {
         assert!(!file_after.staged);
     }
 
+    #[test]
+    fn test_stage_uses_literal_pathspec() {
+        let temp_dir = create_test_repo();
+        let repo_path = temp_dir.path();
+
+        fs::write(repo_path.join("a*.txt"), "glob-like name").unwrap();
+        fs::write(repo_path.join("abc.txt"), "other").unwrap();
+
+        stage(repo_path, "a*.txt").unwrap();
+
+        let files = status(repo_path).unwrap().files;
+        let staged: Vec<_> = files
+            .iter()
+            .filter(|f| f.staged)
+            .map(|f| f.path.as_str())
+            .collect();
+        assert_eq!(staged, vec!["a*.txt"]);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_stage_and_unstage_non_utf8_file() {
+        use crate::git::paths::path_from_bytes;
+
+        let temp_dir = create_test_repo();
+        let repo_path = temp_dir.path();
+        let name: &[u8] = b"caf\xe9.txt";
+        fs::write(repo_path.join(path_from_bytes(name)), "latin-1 name").unwrap();
+
+        let untracked = status(repo_path).unwrap().files;
+        assert_eq!(untracked.len(), 1);
+        assert_eq!(untracked[0].path_bytes.as_deref(), Some(name));
+        assert_eq!(untracked[0].status, "untracked");
+
+        let file_path = path_from_bytes(untracked[0].path_bytes.as_ref().unwrap());
+        stage(repo_path, &file_path).unwrap();
+        let staged = status(repo_path).unwrap().files;
+        assert!(staged[0].staged);
+        assert_eq!(staged[0].path_bytes.as_deref(), Some(name));
+
+        unstage(repo_path, &file_path).unwrap();
+        let unstaged = status(repo_path).unwrap().files;
+        assert!(!unstaged[0].staged);
+    }
+
     #[test]
     fn test_commit() {
         let temp_dir = create_test_repo();
@@ -559,6 +913,101 @@ mod tests {
         assert!(branches.iter().any(|b| b.name == "feature-2"));
     }
 
+    #[test]
+    fn test_list_branches_reports_tip_commit() {
+        let temp_dir = create_test_repo();
+        let repo_path = temp_dir.path().to_str().unwrap();
+
+        let branches = list_branches(repo_path).unwrap();
+        let current = branches.iter().find(|b| b.is_current).unwrap();
+
+        assert_eq!(
+            current.last_commit_summary.as_deref(),
+            Some("Initial commit")
+        );
+        assert!(current.last_commit_time.is_some());
+        assert!(current.upstream.is_none());
+        assert!(!current.upstream_gone);
+    }
+
+    #[test]
+    fn test_list_branches_reports_linked_worktree_owner() {
+        let temp_dir = create_test_repo();
+        let repo_path = temp_dir.path().to_str().unwrap();
+        let worktree_path = temp_dir.path().parent().unwrap().join("owner-wt");
+
+        StdCommand::new("git")
+            .current_dir(repo_path)
+            .args([
+                "worktree",
+                "add",
+                "-b",
+                "owned",
+                worktree_path.to_str().unwrap(),
+            ])
+            .output()
+            .unwrap();
+
+        let branches = list_branches(repo_path).unwrap();
+        let owned = branches.iter().find(|b| b.name == "owned").unwrap();
+        assert!(!owned.is_current);
+        assert!(owned.worktree_path.as_deref().unwrap().contains("owner-wt"));
+
+        let current = branches.iter().find(|b| b.is_current).unwrap();
+        assert!(current.worktree_path.is_some());
+
+        StdCommand::new("git")
+            .current_dir(repo_path)
+            .args(["worktree", "remove", worktree_path.to_str().unwrap()])
+            .output()
+            .unwrap();
+    }
+
+    #[test]
+    fn test_list_branches_upstream_ahead_and_gone() {
+        let temp_dir = create_test_repo();
+        let repo_path = temp_dir.path();
+
+        StdCommand::new("git")
+            .current_dir(repo_path)
+            .args(["branch", "base"])
+            .output()
+            .unwrap();
+        StdCommand::new("git")
+            .current_dir(repo_path)
+            .args(["checkout", "-b", "tracking", "--track", "base"])
+            .output()
+            .unwrap();
+        fs::write(repo_path.join("ahead.txt"), "ahead").unwrap();
+        StdCommand::new("git")
+            .current_dir(repo_path)
+            .args(["add", "ahead.txt"])
+            .output()
+            .unwrap();
+        StdCommand::new("git")
+            .current_dir(repo_path)
+            .args(["commit", "-m", "Ahead commit"])
+            .output()
+            .unwrap();
+
+        let branches = list_branches(repo_path.to_str().unwrap()).unwrap();
+        let tracking = branches.iter().find(|b| b.name == "tracking").unwrap();
+        assert_eq!(tracking.upstream.as_deref(), Some("base"));
+        assert!(!tracking.upstream_gone);
+        assert_eq!(tracking.ahead, 1);
+        assert_eq!(tracking.behind, 0);
+
+        StdCommand::new("git")
+            .current_dir(repo_path)
+            .args(["branch", "-D", "base"])
+            .output()
+            .unwrap();
+
+        let branches = list_branches(repo_path.to_str().unwrap()).unwrap();
+        let tracking = branches.iter().find(|b| b.name == "tracking").unwrap();
+        assert!(tracking.upstream_gone);
+    }
+
     #[test]
     fn test_list_branches_invalid_path() {
         let result = list_branches("/nonexistent/path");
@@ -578,7 +1027,11 @@ mod tests {
             .unwrap();
 
         // Checkout the new branch
-        let result = checkout(repo_path.to_str().unwrap(), "checkout-target");
+        let result = checkout(
+            repo_path.to_str().unwrap(),
+            "checkout-target",
+            LocalChangesMode::Refuse,
+        );
         assert!(result.is_ok());
 
         // Verify the branch changed
@@ -592,10 +1045,191 @@ mod tests {
         let temp_dir = create_test_repo();
         let repo_path = temp_dir.path().to_str().unwrap();
 
-        let result = checkout(repo_path, "nonexistent-branch");
+        let result = checkout(repo_path, "nonexistent-branch", LocalChangesMode::Refuse);
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_checkout_branch_in_other_worktree_refused() {
+        let temp_dir = create_test_repo();
+        let repo_path = temp_dir.path().to_str().unwrap();
+        let worktree_path = temp_dir.path().parent().unwrap().join("checkout-in-use-wt");
+
+        StdCommand::new("git")
+            .current_dir(repo_path)
+            .args([
+                "worktree",
+                "add",
+                "-b",
+                "busy",
+                worktree_path.to_str().unwrap(),
+            ])
+            .output()
+            .unwrap();
+
+        let result = checkout(repo_path, "busy", LocalChangesMode::Refuse);
+        match result {
+            Err(AppError::BranchInUse(msg)) => assert!(msg.contains("checkout-in-use-wt")),
+            other => panic!("expected BranchInUse, got {:?}", other),
+        }
+
+        StdCommand::new("git")
+            .current_dir(repo_path)
+            .args(["worktree", "remove", worktree_path.to_str().unwrap()])
+            .output()
+            .unwrap();
+    }
+
+    #[test]
+    fn test_checkout_detects_overwritten_changes() {
+        let temp_dir = create_test_repo();
+        let repo_path = temp_dir.path();
+
+        StdCommand::new("git")
+            .current_dir(repo_path)
+            .args(["checkout", "-b", "diverged"])
+            .output()
+            .unwrap();
+        fs::write(repo_path.join("README.md"), "# Diverged").unwrap();
+        StdCommand::new("git")
+            .current_dir(repo_path)
+            .args(["commit", "-am", "Diverge"])
+            .output()
+            .unwrap();
+        StdCommand::new("git")
+            .current_dir(repo_path)
+            .args(["checkout", "-"])
+            .output()
+            .unwrap();
+
+        fs::write(repo_path.join("README.md"), "# Local edit").unwrap();
+
+        let result = checkout(
+            repo_path.to_str().unwrap(),
+            "diverged",
+            LocalChangesMode::Refuse,
+        );
+        match result {
+            Err(AppError::CheckoutConflict(files)) => assert_eq!(files, "README.md"),
+            other => panic!("expected CheckoutConflict, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_checkout_stash_mode_stashes_changes() {
+        let temp_dir = create_test_repo();
+        let repo_path = temp_dir.path();
+
+        StdCommand::new("git")
+            .current_dir(repo_path)
+            .args(["branch", "other"])
+            .output()
+            .unwrap();
+        fs::write(repo_path.join("README.md"), "# Local edit").unwrap();
+
+        let result = checkout(
+            repo_path.to_str().unwrap(),
+            "other",
+            LocalChangesMode::Stash,
+        )
+        .unwrap();
+        assert!(result.stashed);
+
+        let status_result = status(repo_path.to_str().unwrap()).unwrap();
+        assert_eq!(status_result.branch.as_deref(), Some("other"));
+        assert!(status_result.files.is_empty());
+    }
+
+    #[test]
+    fn test_checkout_creates_branch_from_remote() {
+        let origin_dir = create_test_repo();
+        let clone_dir = TempDir::new().unwrap();
+        let clone_path = clone_dir.path().join("clone");
+
+        StdCommand::new("git")
+            .current_dir(origin_dir.path())
+            .args(["branch", "remote-only"])
+            .output()
+            .unwrap();
+        StdCommand::new("git")
+            .args([
+                "clone",
+                origin_dir.path().to_str().unwrap(),
+                clone_path.to_str().unwrap(),
+            ])
+            .output()
+            .unwrap();
+
+        let result = checkout(
+            clone_path.to_str().unwrap(),
+            "remote-only",
+            LocalChangesMode::Refuse,
+        )
+        .unwrap();
+
+        assert_eq!(result.branch, "remote-only");
+        assert_eq!(result.created_from.as_deref(), Some("origin/remote-only"));
+
+        let branches = list_branches(clone_path.to_str().unwrap()).unwrap();
+        let current = branches.iter().find(|b| b.is_current).unwrap();
+        assert_eq!(current.name, "remote-only");
+        assert_eq!(current.upstream.as_deref(), Some("origin/remote-only"));
+    }
+
+    #[test]
+    fn test_failed_checkout_deletes_created_branch() {
+        let origin_dir = create_test_repo();
+        let clone_dir = TempDir::new().unwrap();
+        let clone_path = clone_dir.path().join("clone");
+
+        StdCommand::new("git")
+            .current_dir(origin_dir.path())
+            .args(["checkout", "-q", "-b", "remote-only"])
+            .output()
+            .unwrap();
+        fs::write(origin_dir.path().join("new.txt"), "remote").unwrap();
+        StdCommand::new("git")
+            .current_dir(origin_dir.path())
+            .args(["add", "new.txt"])
+            .output()
+            .unwrap();
+        StdCommand::new("git")
+            .current_dir(origin_dir.path())
+            .args(["commit", "-m", "Add new.txt"])
+            .output()
+            .unwrap();
+        StdCommand::new("git")
+            .current_dir(origin_dir.path())
+            .args(["checkout", "-q", "-"])
+            .output()
+            .unwrap();
+        StdCommand::new("git")
+            .args([
+                "clone",
+                origin_dir.path().to_str().unwrap(),
+                clone_path.to_str().unwrap(),
+            ])
+            .output()
+            .unwrap();
+        // Merge mode has no pre-check, so git itself refuses to overwrite it
+        fs::write(clone_path.join("new.txt"), "local").unwrap();
+
+        let result = checkout(
+            clone_path.to_str().unwrap(),
+            "remote-only",
+            LocalChangesMode::Merge,
+        );
+
+        assert!(result.is_err());
+        let repo = Repository::open(&clone_path).unwrap();
+        assert!(repo.find_branch("remote-only", BranchType::Local).is_err());
+        assert_ne!(repo.head().unwrap().shorthand(), Some("remote-only"));
+        assert_eq!(
+            fs::read_to_string(clone_path.join("new.txt")).unwrap(),
+            "local"
+        );
+    }
+
     #[test]
     fn test_fetch_no_remote() {
         let temp_dir = create_test_repo();
@@ -627,4 +1261,244 @@ mod tests {
         let result = push(repo_path);
         assert!(result.is_err());
     }
+
+    fn commit_file(repo_path: &str, name: &str, contents: &str) -> String {
+        fs::write(Path::new(repo_path).join(name), contents).unwrap();
+        stage(repo_path, name).unwrap();
+        commit(repo_path, &format!("Write {}", name)).unwrap();
+        head_oid(repo_path)
+    }
+
+    fn head_oid(repo_path: &str) -> String {
+        let repo = Repository::open(repo_path).unwrap();
+        let oid = repo.head().unwrap().target().unwrap();
+        oid.to_string()
+    }
+
+    #[test]
+    fn test_reset_soft_and_mixed() {
+        let temp_dir = create_test_repo();
+        let repo_path = temp_dir.path().to_str().unwrap();
+        let first = head_oid(repo_path);
+        let second = commit_file(repo_path, "a.txt", "a");
+
+        let result = reset(repo_path, "HEAD~1", ResetMode::Soft).unwrap();
+        assert_eq!(result.previous_head, second);
+        assert!(result.snapshot.is_none());
+        assert_eq!(head_oid(repo_path), first);
+        let files = status(repo_path).unwrap().files;
+        assert!(files[0].staged);
+
+        reset(repo_path, &second, ResetMode::Soft).unwrap();
+        reset(repo_path, "HEAD~1", ResetMode::Mixed).unwrap();
+        let files = status(repo_path).unwrap().files;
+        assert_eq!(files[0].status, "untracked");
+    }
+
+    #[test]
+    fn test_reset_hard_keeps_snapshot() {
+        let temp_dir = create_test_repo();
+        let repo_path = temp_dir.path().to_str().unwrap();
+        let first = head_oid(repo_path);
+        commit_file(repo_path, "a.txt", "a");
+        fs::write(temp_dir.path().join("README.md"), "uncommitted").unwrap();
+
+        let result = reset(repo_path, &first, ResetMode::Hard).unwrap();
+        assert_eq!(head_oid(repo_path), first);
+        assert!(status(repo_path).unwrap().files.is_empty());
+
+        let snapshot = result.snapshot.unwrap();
+        let entries = crate::git::trash_manager::list_trash(repo_path).unwrap();
+        assert_eq!(entries[0].name, snapshot);
+        assert_eq!(entries[0].head, result.previous_head);
+    }
+
+    #[test]
+    fn test_reset_invalid_revision() {
+        let temp_dir = create_test_repo();
+        let repo_path = temp_dir.path().to_str().unwrap();
+
+        let result = reset(repo_path, "no-such-revision", ResetMode::Hard);
+        assert!(result.is_err());
+        assert!(crate::git::trash_manager::list_trash(repo_path)
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_revert_commits() {
+        let temp_dir = create_test_repo();
+        let repo_path = temp_dir.path().to_str().unwrap();
+        let a = commit_file(repo_path, "a.txt", "a");
+        let b = commit_file(repo_path, "b.txt", "b");
+
+        let result = revert(repo_path, &[b, a]).unwrap();
+        assert!(result.completed);
+        assert!(result.status.files.is_empty());
+        assert!(!temp_dir.path().join("a.txt").exists());
+        assert!(!temp_dir.path().join("b.txt").exists());
+    }
+
+    #[test]
+    fn test_revert_conflict_then_abort() {
+        let temp_dir = create_test_repo();
+        let repo_path = temp_dir.path().to_str().unwrap();
+        let change = commit_file(repo_path, "README.md", "changed");
+        let before = commit_file(repo_path, "README.md", "changed again");
+
+        let result = revert(repo_path, &[change]).unwrap();
+        assert!(!result.completed);
+        assert!(result
+            .status
+            .files
+            .iter()
+            .any(|f| f.path == "README.md" && f.status == "conflicted"));
+
+        revert_abort(repo_path).unwrap();
+        assert_eq!(head_oid(repo_path), before);
+        assert!(status(repo_path).unwrap().files.is_empty());
+    }
+
+    #[test]
+    fn test_revert_continue_after_resolving() {
+        let temp_dir = create_test_repo();
+        let repo_path = temp_dir.path().to_str().unwrap();
+        let change = commit_file(repo_path, "README.md", "changed");
+        let before = commit_file(repo_path, "README.md", "changed again");
+
+        assert!(!revert(repo_path, &[change]).unwrap().completed);
+        fs::write(temp_dir.path().join("README.md"), "resolved").unwrap();
+        stage(repo_path, "README.md").unwrap();
+
+        let result = revert_continue(repo_path).unwrap();
+        assert!(result.completed);
+        assert_ne!(head_oid(repo_path), before);
+    }
+
+    #[test]
+    fn test_revert_nothing() {
+        let temp_dir = create_test_repo();
+        let repo_path = temp_dir.path().to_str().unwrap();
+
+        assert!(revert(repo_path, &[]).is_err());
+    }
+
+    #[test]
+    fn test_revert_rejects_options_as_commits() {
+        let temp_dir = create_test_repo();
+        let repo_path = temp_dir.path().to_str().unwrap();
+        let change = commit_file(repo_path, "README.md", "changed");
+
+        let result = revert(repo_path, &["--no-commit".to_string(), change.clone()]);
+        assert!(matches!(result, Err(AppError::Git(_))));
+        assert_eq!(head_oid(repo_path), change);
+    }
+
+    #[test]
+    fn test_cherry_pick_rejects_options_as_commits() {
+        let temp_dir = create_test_repo();
+        let repo_path = temp_dir.path().to_str().unwrap();
+        let before = head_oid(repo_path);
+
+        let result = cherry_pick(
+            repo_path,
+            &["--strategy=ours".to_string()],
+            CherryPickOptions::default(),
+        );
+        assert!(matches!(result, Err(AppError::Git(_))));
+        assert_eq!(head_oid(repo_path), before);
+    }
+
+    #[test]
+    fn test_cherry_pick_between_worktrees() {
+        let temp_dir = create_test_repo();
+        let repo_path = temp_dir.path().to_str().unwrap();
+        let wt_path = temp_dir
+            .path()
+            .parent()
+            .unwrap()
+            .join("cherry-pick-target-wt");
+        crate::git::worktree_manager::add_worktree(repo_path, &wt_path, "release", true).unwrap();
+        let fix = commit_file(repo_path, "fix.txt", "fix");
+        let wt = wt_path.to_str().unwrap();
+
+        let options = CherryPickOptions {
+            record_origin: true,
+            no_commit: false,
+        };
+        let result = cherry_pick(wt, std::slice::from_ref(&fix), options).unwrap();
+        assert!(result.completed);
+        assert!(wt_path.join("fix.txt").exists());
+
+        let repo = Repository::open(wt).unwrap();
+        let head = repo.head().unwrap().peel_to_commit().unwrap();
+        assert!(head
+            .message()
+            .unwrap()
+            .contains(&format!("cherry picked from commit {}", fix)));
+
+        fs::remove_dir_all(&wt_path).ok();
+    }
+
+    #[test]
+    fn test_cherry_pick_no_commit() {
+        let temp_dir = create_test_repo();
+        let repo_path = temp_dir.path().to_str().unwrap();
+        let base = head_oid(repo_path);
+        let change = commit_file(repo_path, "a.txt", "a");
+        reset(repo_path, &base, ResetMode::Mixed).unwrap();
+        fs::remove_file(temp_dir.path().join("a.txt")).unwrap();
+
+        let options = CherryPickOptions {
+            record_origin: false,
+            no_commit: true,
+        };
+        let result = cherry_pick(repo_path, &[change], options).unwrap();
+        assert!(result.completed);
+        assert_eq!(head_oid(repo_path), base);
+        assert!(result
+            .status
+            .files
+            .iter()
+            .any(|f| f.path == "a.txt" && f.staged));
+    }
+
+    #[test]
+    fn test_cherry_pick_conflict_then_abort() {
+        let temp_dir = create_test_repo();
+        let repo_path = temp_dir.path().to_str().unwrap();
+        let base = head_oid(repo_path);
+        let theirs = commit_file(repo_path, "README.md", "theirs");
+        reset(repo_path, &base, ResetMode::Hard).unwrap();
+        let ours = commit_file(repo_path, "README.md", "ours");
+
+        let result = cherry_pick(repo_path, &[theirs], CherryPickOptions::default()).unwrap();
+        assert!(!result.completed);
+        assert!(result
+            .status
+            .files
+            .iter()
+            .any(|f| f.path == "README.md" && f.status == "conflicted"));
+
+        cherry_pick_abort(repo_path).unwrap();
+        assert_eq!(head_oid(repo_path), ours);
+    }
+
+    #[test]
+    fn test_cherry_pick_continue_after_resolving() {
+        let temp_dir = create_test_repo();
+        let repo_path = temp_dir.path().to_str().unwrap();
+        let base = head_oid(repo_path);
+        let theirs = commit_file(repo_path, "README.md", "theirs");
+        reset(repo_path, &base, ResetMode::Hard).unwrap();
+        let ours = commit_file(repo_path, "README.md", "ours");
+
+        cherry_pick(repo_path, &[theirs], CherryPickOptions::default()).unwrap();
+        fs::write(temp_dir.path().join("README.md"), "both").unwrap();
+        stage(repo_path, "README.md").unwrap();
+
+        let result = cherry_pick_continue(repo_path).unwrap();
+        assert!(result.completed);
+        assert_ne!(head_oid(repo_path), ours);
+    }
 }
@@ -0,0 +1,265 @@
+use std::env;
+use std::fs;
+use std::io;
+use std::path::{Component, Path, PathBuf, Prefix};
+
+/// `fs::canonicalize`, but without the `\\?\` prefix Windows puts on every
+/// result where the path can do without it: git for Windows does not
+/// understand such verbatim paths
+fn canonicalize(path: &Path) -> io::Result<PathBuf> {
+    fs::canonicalize(path).map(simplify_verbatim)
+}
+
+/// Turns `\\?\C:\dir` into `C:\dir` and `\\?\UNC\server\share\dir` into
+/// `\\server\share\dir`; other paths are returned as they are. Only meant
+/// for canonical paths, which have no `.` or `..` components.
+fn simplify_verbatim(path: PathBuf) -> PathBuf {
+    let mut components = path.components();
+    let simple = match components.next() {
+        Some(Component::Prefix(prefix)) => match prefix.kind() {
+            Prefix::VerbatimDisk(drive) => format!("{}:", char::from(drive)),
+            Prefix::VerbatimUNC(server, share) => format!(
+                r"\\{}\{}",
+                server.to_string_lossy(),
+                share.to_string_lossy()
+            ),
+            _ => return path,
+        },
+        _ => return path,
+    };
+    let mut simplified = PathBuf::from(simple);
+    simplified.extend(components);
+    simplified
+}
+
+/// Resolves `path` to an absolute, canonical form, so that paths naming the
+/// same directory compare equal.
+///
+/// Existing paths are canonicalized, resolving symlinks. For a path that does
+/// not exist (a worktree deleted by hand, a destination not yet created) the
+/// deepest existing ancestor is canonicalized and the rest appended, with `.`
+/// and `..` resolved lexically. Relative paths are taken relative to the
+/// current directory. Paths are handled as `OsStr` throughout, so non-UTF-8
+/// names are preserved.
+pub fn normalize_path(path: impl AsRef<Path>) -> PathBuf {
+    let path = path.as_ref();
+    if let Ok(canonical) = canonicalize(path) {
+        return canonical;
+    }
+
+    let absolute = if path.is_absolute() {
+        path.to_path_buf()
+    } else {
+        env::current_dir()
+            .map(|cwd| cwd.join(path))
+            .unwrap_or_else(|_| path.to_path_buf())
+    };
+
+    let mut resolved = PathBuf::new();
+    for component in absolute.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                resolved.pop();
+            }
+            other => resolved.push(other.as_os_str()),
+        }
+    }
+
+    let mut existing = resolved.as_path();
+    let mut missing = Vec::new();
+    while !existing.exists() {
+        match (existing.parent(), existing.file_name()) {
+            (Some(parent), Some(name)) => {
+                missing.push(name);
+                existing = parent;
+            }
+            _ => return resolved,
+        }
+    }
+
+    let mut normalized = canonicalize(existing).unwrap_or_else(|_| existing.to_path_buf());
+    normalized.extend(missing.iter().rev());
+    normalized
+}
+
+/// Returns true if both paths name the same location once normalized
+pub fn same_path(a: impl AsRef<Path>, b: impl AsRef<Path>) -> bool {
+    normalize_path(a) == normalize_path(b)
+}
+
+/// Normalizes a worktree path given for the repository at `repo_path`,
+/// taking a relative path relative to the repository as git does
+pub fn resolve_worktree_path(repo_path: impl AsRef<Path>, path: impl AsRef<Path>) -> PathBuf {
+    normalize_path(repo_path.as_ref().join(path))
+}
+
+/// Converts the raw bytes git records for a path into a `PathBuf`, without
+/// loss on Unix where paths need not be UTF-8
+pub fn path_from_bytes(bytes: &[u8]) -> PathBuf {
+    #[cfg(unix)]
+    {
+        use std::ffi::OsStr;
+        use std::os::unix::ffi::OsStrExt;
+        PathBuf::from(OsStr::from_bytes(bytes))
+    }
+    #[cfg(not(unix))]
+    {
+        PathBuf::from(String::from_utf8_lossy(bytes).into_owned())
+    }
+}
+
+/// Converts a path relative to a worktree into the bytes git records for it,
+/// with `/` separators; the inverse of `path_from_bytes`
+pub fn path_to_bytes(path: &Path) -> Vec<u8> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::ffi::OsStrExt;
+        path.as_os_str().as_bytes().to_vec()
+    }
+    #[cfg(not(unix))]
+    {
+        path.to_string_lossy().replace('\\', "/").into_bytes()
+    }
+}
+
+/// Returns the raw bytes of a path that is not valid UTF-8, which its
+/// display string cannot represent, or `None` for ordinary paths
+pub fn non_utf8_bytes(path: &Path) -> Option<Vec<u8>> {
+    if path.to_str().is_some() {
+        return None;
+    }
+    #[cfg(unix)]
+    {
+        use std::os::unix::ffi::OsStrExt;
+        Some(path.as_os_str().as_bytes().to_vec())
+    }
+    #[cfg(not(unix))]
+    {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_normalize_existing_path() {
+        let temp_dir = TempDir::new().unwrap();
+        let canonical = temp_dir.path().canonicalize().unwrap();
+
+        assert_eq!(normalize_path(temp_dir.path()), canonical);
+    }
+
+    #[test]
+    fn test_trailing_slash_and_dot_components() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::create_dir(temp_dir.path().join("wt")).unwrap();
+        let plain = temp_dir.path().join("wt");
+
+        let trailing = format!("{}/", plain.to_str().unwrap());
+        assert!(same_path(&plain, trailing));
+        assert!(same_path(&plain, temp_dir.path().join("./wt/../wt")));
+    }
+
+    #[test]
+    fn test_missing_path_is_resolved_lexically() {
+        let temp_dir = TempDir::new().unwrap();
+        let canonical = temp_dir.path().canonicalize().unwrap();
+        let missing = temp_dir.path().join("gone/../deleted/wt/");
+
+        assert_eq!(
+            normalize_path(missing),
+            canonical.join("deleted").join("wt")
+        );
+    }
+
+    #[test]
+    fn test_relative_path_uses_current_dir() {
+        let cwd = env::current_dir().unwrap().canonicalize().unwrap();
+
+        assert_eq!(normalize_path("."), cwd);
+        assert_eq!(
+            normalize_path("no-such-dir/wt"),
+            cwd.join("no-such-dir").join("wt")
+        );
+    }
+
+    #[test]
+    fn test_relative_worktree_path_uses_repository() {
+        let temp_dir = TempDir::new().unwrap();
+        let canonical = temp_dir.path().canonicalize().unwrap();
+        let repo = temp_dir.path().join("repo");
+        fs::create_dir(&repo).unwrap();
+
+        assert_eq!(resolve_worktree_path(&repo, "../wt"), canonical.join("wt"));
+        assert_eq!(
+            resolve_worktree_path(&repo, temp_dir.path().join("other")),
+            canonical.join("other")
+        );
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn test_verbatim_prefix_is_removed() {
+        assert_eq!(
+            simplify_verbatim(PathBuf::from(r"\\?\C:\Users\me\wt")),
+            PathBuf::from(r"C:\Users\me\wt")
+        );
+        assert_eq!(
+            simplify_verbatim(PathBuf::from(r"\\?\UNC\server\share\wt")),
+            PathBuf::from(r"\\server\share\wt")
+        );
+        let temp_dir = TempDir::new().unwrap();
+        assert!(!normalize_path(temp_dir.path())
+            .to_string_lossy()
+            .starts_with(r"\\?\"));
+    }
+
+    #[test]
+    fn test_plain_paths_are_not_simplified() {
+        let path = env::current_dir().unwrap();
+        assert_eq!(simplify_verbatim(path.clone()), path);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_symlink_is_resolved() {
+        let temp_dir = TempDir::new().unwrap();
+        let target = temp_dir.path().join("target");
+        let link = temp_dir.path().join("link");
+        fs::create_dir(&target).unwrap();
+        std::os::unix::fs::symlink(&target, &link).unwrap();
+
+        assert!(same_path(&target, &link));
+        // Missing children of a symlinked directory resolve through it too
+        assert!(same_path(target.join("new"), link.join("new")));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_non_utf8_bytes_round_trip() {
+        let bytes = b"dir/file-\xff.txt";
+        let path = path_from_bytes(bytes);
+
+        assert_eq!(non_utf8_bytes(&path), Some(bytes.to_vec()));
+        assert_eq!(non_utf8_bytes(Path::new("dir/file.txt")), None);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_non_utf8_path() {
+        use std::ffi::OsStr;
+        use std::os::unix::ffi::OsStrExt;
+
+        let temp_dir = TempDir::new().unwrap();
+        let name = OsStr::from_bytes(b"wt-\xff");
+        let path = temp_dir.path().join(name);
+
+        let normalized = normalize_path(path.join(".."));
+        assert_eq!(normalized, temp_dir.path().canonicalize().unwrap());
+        assert_eq!(normalize_path(&path).file_name(), Some(name));
+    }
+}
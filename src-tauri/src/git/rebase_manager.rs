@@ -0,0 +1,559 @@
+use crate::commands::rebase::{RebaseAction, RebaseProgress, RebaseState, RebaseStep};
+use crate::error::{AppError, AppResult};
+use crate::git::executable::git_path;
+use crate::git::operations::status;
+use crate::git::runner::{git, GitOutput};
+use git2::{Oid, Repository, Sort};
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Files written for a rebase started by the app, in the worktree's git dir
+fn plan_dir(repo: &Repository) -> PathBuf {
+    repo.path().join("wtview-rebase")
+}
+
+/// State git keeps while an interactive rebase is in progress
+fn rebase_dir(repo: &Repository) -> PathBuf {
+    repo.path().join("rebase-merge")
+}
+
+/// Quotes a path for the `sh -c` command lines git runs editors and `exec`
+/// steps with
+fn shell_quote(path: &Path) -> String {
+    format!("'{}'", path.display().to_string().replace('\'', r"'\''"))
+}
+
+/// Lists the commits a rebase onto `upstream` would replay, oldest first,
+/// all as `pick` steps. Merge commits are left out, as git drops them too.
+pub fn plan(worktree_path: &str, upstream: &str) -> AppResult<Vec<RebaseStep>> {
+    let repo = Repository::open(worktree_path)?;
+    let upstream = repo.revparse_single(upstream)?.peel_to_commit()?;
+
+    let mut revwalk = repo.revwalk()?;
+    revwalk.push_head()?;
+    revwalk.hide(upstream.id())?;
+    revwalk.set_sorting(Sort::TOPOLOGICAL | Sort::REVERSE)?;
+
+    let mut steps = Vec::new();
+    for oid in revwalk {
+        let commit = repo.find_commit(oid?)?;
+        if commit.parent_count() > 1 {
+            continue;
+        }
+        steps.push(RebaseStep {
+            action: RebaseAction::Pick,
+            oid: commit.id().to_string(),
+            summary: commit.summary().unwrap_or_default().to_string(),
+            message: None,
+        });
+    }
+
+    Ok(steps)
+}
+
+/// Writes the todo list for `steps` and returns its path.
+///
+/// git has no todo command for "pick with this message", so new messages
+/// become an `exec git commit --amend` line after their step, running the
+/// same git executable as the app.
+fn write_todo(repo: &Repository, steps: &[RebaseStep]) -> AppResult<PathBuf> {
+    let dir = plan_dir(repo);
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir)?;
+    let git = shell_quote(&git_path()?);
+
+    let mut todo = String::new();
+    for (i, step) in steps.iter().enumerate() {
+        let oid = repo.revparse_single(&step.oid)?.peel_to_commit()?.id();
+        let command = match step.action {
+            RebaseAction::Pick | RebaseAction::Reword => "pick",
+            RebaseAction::Squash => "squash",
+            RebaseAction::Fixup => "fixup",
+            RebaseAction::Drop => "drop",
+            RebaseAction::Edit => "edit",
+        };
+        todo.push_str(&format!("{} {}\n", command, oid));
+
+        if let Some(message) = step
+            .message
+            .as_ref()
+            .filter(|_| step.action != RebaseAction::Drop)
+        {
+            let file = dir.join(format!("message-{}", i));
+            fs::write(&file, message)?;
+            todo.push_str(&format!(
+                "exec {} commit --amend --quiet -F {}\n",
+                git,
+                shell_quote(&file)
+            ));
+        }
+    }
+
+    let path = dir.join("todo");
+    fs::write(&path, todo)?;
+    Ok(path)
+}
+
+/// Reports where a rebase in progress has stopped, or `None` if there is none
+pub fn progress(worktree_path: &str) -> AppResult<Option<RebaseProgress>> {
+    let repo = Repository::open(worktree_path)?;
+    let dir = rebase_dir(&repo);
+    if !dir.exists() {
+        return Ok(None);
+    }
+
+    let read_number = |name: &str| {
+        fs::read_to_string(dir.join(name))
+            .ok()
+            .and_then(|contents| contents.trim().parse().ok())
+            .unwrap_or(0)
+    };
+    let status = status(worktree_path)?;
+    let state = if status.files.iter().any(|f| f.status == "conflicted") {
+        RebaseState::Conflicts
+    } else {
+        RebaseState::Stopped
+    };
+
+    Ok(Some(RebaseProgress {
+        state,
+        done: read_number("msgnum"),
+        total: read_number("end"),
+        stopped_at: fs::read_to_string(dir.join("stopped-sha"))
+            .ok()
+            .map(|sha| sha.trim().to_string()),
+        status,
+    }))
+}
+
+/// Turns the output of a rebase command into progress, cleaning up the
+/// plan files once the rebase has finished
+fn outcome(worktree_path: &str, output: GitOutput) -> AppResult<RebaseProgress> {
+    if let Some(progress) = progress(worktree_path)? {
+        return Ok(progress);
+    }
+
+    output.check()?;
+
+    let repo = Repository::open(worktree_path)?;
+    let dir = plan_dir(&repo);
+    let total = fs::read_to_string(dir.join("todo"))
+        .map(|todo| todo.lines().count())
+        .unwrap_or(0);
+    let _ = fs::remove_dir_all(dir);
+
+    Ok(RebaseProgress {
+        state: RebaseState::Completed,
+        done: total,
+        total,
+        stopped_at: None,
+        status: status(worktree_path)?,
+    })
+}
+
+/// Checks that the steps name every commit in the range being rebased
+/// exactly once, and that rewords come with their new message. git would
+/// silently drop a commit left out of the plan; dropping takes a `Drop` step.
+fn validate_steps(
+    repo: &Repository,
+    worktree_path: &str,
+    upstream: &str,
+    steps: &[RebaseStep],
+) -> AppResult<()> {
+    let planned = plan(worktree_path, upstream)?
+        .iter()
+        .map(|step| Oid::from_str(&step.oid))
+        .collect::<Result<Vec<_>, _>>()?;
+    let range: HashSet<Oid> = planned.iter().copied().collect();
+
+    let mut seen = HashSet::new();
+    for step in steps {
+        let oid = repo.revparse_single(&step.oid)?.peel_to_commit()?.id();
+        if !range.contains(&oid) {
+            return Err(AppError::Other(format!(
+                "Commit {} is not among the commits being rebased",
+                step.oid
+            )));
+        }
+        if !seen.insert(oid) {
+            return Err(AppError::Other(format!(
+                "Commit {} is listed more than once",
+                step.oid
+            )));
+        }
+        if step.action == RebaseAction::Reword && step.message.is_none() {
+            return Err(AppError::Other(format!(
+                "Rewording commit {} needs a new message",
+                step.oid
+            )));
+        }
+    }
+
+    if let Some(missing) = planned.iter().find(|oid| !seen.contains(*oid)) {
+        return Err(AppError::Other(format!(
+            "Commit {} is missing from the plan; use a drop step to remove it",
+            missing
+        )));
+    }
+
+    Ok(())
+}
+
+/// Rebases the worktree's branch onto `upstream` following an edited plan,
+/// without opening an editor.
+///
+/// The plan may only reorder and change the commits [`plan`] lists. Squashes
+/// without a new message keep the combined messages. The rebase may stop at
+/// `edit` steps or on conflicts; see [`continue_rebase`].
+pub fn execute(
+    worktree_path: &str,
+    upstream: &str,
+    steps: &[RebaseStep],
+) -> AppResult<RebaseProgress> {
+    let repo = Repository::open(worktree_path)?;
+    if rebase_dir(&repo).exists() {
+        return Err(AppError::Other(
+            "A rebase is already in progress in this worktree".to_string(),
+        ));
+    }
+
+    let first = steps.iter().find(|step| step.action != RebaseAction::Drop);
+    if first.is_some_and(|step| matches!(step.action, RebaseAction::Squash | RebaseAction::Fixup)) {
+        return Err(AppError::Other(
+            "The first commit cannot be squashed or fixed up".to_string(),
+        ));
+    }
+
+    let upstream = repo
+        .revparse_single(upstream)?
+        .peel_to_commit()?
+        .id()
+        .to_string();
+    validate_steps(&repo, worktree_path, &upstream, steps)?;
+    let todo = write_todo(&repo, steps)?;
+
+    let output = git(worktree_path)?
+        .env("GIT_SEQUENCE_EDITOR", format!("cp {}", shell_quote(&todo)))
+        .env("GIT_EDITOR", "true")
+        .args(["rebase", "--interactive", &upstream])
+        .output()?;
+
+    outcome(worktree_path, output)
+}
+
+/// Continues a stopped rebase, after amending an `edit` step or staging
+/// resolved conflicts
+pub fn continue_rebase(worktree_path: &str) -> AppResult<RebaseProgress> {
+    let output = git(worktree_path)?
+        .env("GIT_EDITOR", "true")
+        .args(["rebase", "--continue"])
+        .output()?;
+
+    outcome(worktree_path, output)
+}
+
+/// Abandons a rebase in progress, restoring the branch as it was
+pub fn abort(worktree_path: &str) -> AppResult<()> {
+    git(worktree_path)?.args(["rebase", "--abort"]).run()?;
+
+    let repo = Repository::open(worktree_path)?;
+    let _ = fs::remove_dir_all(plan_dir(&repo));
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::process::Command as StdCommand;
+    use tempfile::TempDir;
+
+    fn create_test_repo() -> TempDir {
+        let temp_dir = TempDir::new().unwrap();
+        let repo_path = temp_dir.path();
+
+        StdCommand::new("git")
+            .current_dir(repo_path)
+            .args(["init"])
+            .output()
+            .unwrap();
+
+        StdCommand::new("git")
+            .current_dir(repo_path)
+            .args(["config", "user.email", "test@test.com"])
+            .output()
+            .unwrap();
+
+        StdCommand::new("git")
+            .current_dir(repo_path)
+            .args(["config", "user.name", "Test User"])
+            .output()
+            .unwrap();
+
+        fs::write(repo_path.join("README.md"), "# Test Repository").unwrap();
+
+        StdCommand::new("git")
+            .current_dir(repo_path)
+            .args(["add", "."])
+            .output()
+            .unwrap();
+
+        StdCommand::new("git")
+            .current_dir(repo_path)
+            .args(["commit", "-m", "Initial commit"])
+            .output()
+            .unwrap();
+
+        temp_dir
+    }
+
+    fn commit_file(repo_path: &Path, name: &str, contents: &str, message: &str) -> String {
+        fs::write(repo_path.join(name), contents).unwrap();
+        StdCommand::new("git")
+            .current_dir(repo_path)
+            .args(["add", name])
+            .output()
+            .unwrap();
+        StdCommand::new("git")
+            .current_dir(repo_path)
+            .args(["commit", "-m", message])
+            .output()
+            .unwrap();
+        head_oid(repo_path)
+    }
+
+    fn head_oid(repo_path: &Path) -> String {
+        let repo = Repository::open(repo_path).unwrap();
+        let oid = repo.head().unwrap().target().unwrap();
+        oid.to_string()
+    }
+
+    fn messages_since(repo_path: &Path, base: &str) -> Vec<String> {
+        plan(repo_path.to_str().unwrap(), base)
+            .unwrap()
+            .into_iter()
+            .map(|step| {
+                let repo = Repository::open(repo_path).unwrap();
+                let oid = git2::Oid::from_str(&step.oid).unwrap();
+                let message = repo
+                    .find_commit(oid)
+                    .unwrap()
+                    .message()
+                    .unwrap()
+                    .to_string();
+                message.trim_end().to_string()
+            })
+            .collect()
+    }
+
+    fn set_action(steps: &mut [RebaseStep], index: usize, action: RebaseAction) {
+        steps[index].action = action;
+    }
+
+    #[test]
+    fn test_plan_lists_commits_oldest_first() {
+        let temp_dir = create_test_repo();
+        let repo_path = temp_dir.path();
+        let base = head_oid(repo_path);
+        let a = commit_file(repo_path, "a.txt", "a", "Add a");
+        let b = commit_file(repo_path, "b.txt", "b", "Add b");
+
+        let steps = plan(repo_path.to_str().unwrap(), &base).unwrap();
+        assert_eq!(steps.len(), 2);
+        assert_eq!(steps[0].oid, a);
+        assert_eq!(steps[0].summary, "Add a");
+        assert_eq!(steps[0].action, RebaseAction::Pick);
+        assert_eq!(steps[1].oid, b);
+    }
+
+    #[test]
+    fn test_execute_squash_with_message() {
+        let temp_dir = create_test_repo();
+        let repo_path = temp_dir.path();
+        let wt = repo_path.to_str().unwrap();
+        let base = head_oid(repo_path);
+        commit_file(repo_path, "a.txt", "a", "Add a");
+        commit_file(repo_path, "b.txt", "b", "Add b");
+
+        let mut steps = plan(wt, &base).unwrap();
+        set_action(&mut steps, 1, RebaseAction::Squash);
+        steps[1].message = Some("Add a and b".to_string());
+
+        let progress = execute(wt, &base, &steps).unwrap();
+        assert_eq!(progress.state, RebaseState::Completed);
+        assert_eq!(messages_since(repo_path, &base), vec!["Add a and b"]);
+        assert!(repo_path.join("b.txt").exists());
+        assert!(!plan_dir(&Repository::open(wt).unwrap()).exists());
+    }
+
+    #[test]
+    fn test_execute_reorder_reword_and_drop() {
+        let temp_dir = create_test_repo();
+        let repo_path = temp_dir.path();
+        let wt = repo_path.to_str().unwrap();
+        let base = head_oid(repo_path);
+        commit_file(repo_path, "a.txt", "a", "Add a");
+        commit_file(repo_path, "b.txt", "b", "Add b");
+        commit_file(repo_path, "c.txt", "c", "Add c");
+
+        let mut steps = plan(wt, &base).unwrap();
+        steps.swap(0, 1);
+        set_action(&mut steps, 0, RebaseAction::Reword);
+        steps[0].message = Some("Add b, it's first now".to_string());
+        set_action(&mut steps, 2, RebaseAction::Drop);
+
+        let progress = execute(wt, &base, &steps).unwrap();
+        assert_eq!(progress.state, RebaseState::Completed);
+        assert_eq!(
+            messages_since(repo_path, &base),
+            vec!["Add b, it's first now", "Add a"]
+        );
+        assert!(!repo_path.join("c.txt").exists());
+    }
+
+    #[test]
+    fn test_execute_stops_at_edit() {
+        let temp_dir = create_test_repo();
+        let repo_path = temp_dir.path();
+        let wt = repo_path.to_str().unwrap();
+        let base = head_oid(repo_path);
+        let a = commit_file(repo_path, "a.txt", "a", "Add a");
+        commit_file(repo_path, "b.txt", "b", "Add b");
+
+        let mut steps = plan(wt, &base).unwrap();
+        set_action(&mut steps, 0, RebaseAction::Edit);
+
+        let stopped = execute(wt, &base, &steps).unwrap();
+        assert_eq!(stopped.state, RebaseState::Stopped);
+        assert_eq!(stopped.done, 1);
+        assert_eq!(stopped.total, 2);
+        assert!(a.starts_with(stopped.stopped_at.as_deref().unwrap()));
+        assert!(progress(wt).unwrap().is_some());
+
+        let finished = continue_rebase(wt).unwrap();
+        assert_eq!(finished.state, RebaseState::Completed);
+        assert!(progress(wt).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_execute_conflict_then_abort() {
+        let temp_dir = create_test_repo();
+        let repo_path = temp_dir.path();
+        let wt = repo_path.to_str().unwrap();
+        let base = head_oid(repo_path);
+        commit_file(repo_path, "README.md", "one", "First edit");
+        let before = commit_file(repo_path, "README.md", "two", "Second edit");
+
+        let mut steps = plan(wt, &base).unwrap();
+        steps.swap(0, 1);
+
+        let stopped = execute(wt, &base, &steps).unwrap();
+        assert_eq!(stopped.state, RebaseState::Conflicts);
+        assert!(stopped
+            .status
+            .files
+            .iter()
+            .any(|f| f.path == "README.md" && f.status == "conflicted"));
+
+        let again = execute(wt, &base, &steps);
+        assert!(matches!(again, Err(AppError::Other(_))));
+
+        abort(wt).unwrap();
+        assert_eq!(head_oid(repo_path), before);
+        assert!(progress(wt).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_execute_rejects_leading_squash() {
+        let temp_dir = create_test_repo();
+        let repo_path = temp_dir.path();
+        let wt = repo_path.to_str().unwrap();
+        let base = head_oid(repo_path);
+        commit_file(repo_path, "a.txt", "a", "Add a");
+
+        let mut steps = plan(wt, &base).unwrap();
+        set_action(&mut steps, 0, RebaseAction::Fixup);
+
+        let result = execute(wt, &base, &steps);
+        assert!(matches!(result, Err(AppError::Other(_))));
+    }
+
+    #[test]
+    fn test_execute_rejects_reword_without_message() {
+        let temp_dir = create_test_repo();
+        let repo_path = temp_dir.path();
+        let wt = repo_path.to_str().unwrap();
+        let base = head_oid(repo_path);
+        commit_file(repo_path, "a.txt", "a", "Add a");
+
+        let mut steps = plan(wt, &base).unwrap();
+        set_action(&mut steps, 0, RebaseAction::Reword);
+
+        let result = execute(wt, &base, &steps);
+        assert!(matches!(result, Err(AppError::Other(ref m)) if m.contains("new message")));
+        assert!(progress(wt).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_execute_rejects_commits_outside_range() {
+        let temp_dir = create_test_repo();
+        let repo_path = temp_dir.path();
+        let wt = repo_path.to_str().unwrap();
+        let base = head_oid(repo_path);
+        StdCommand::new("git")
+            .current_dir(repo_path)
+            .args(["checkout", "-q", "-b", "other"])
+            .output()
+            .unwrap();
+        let foreign = commit_file(repo_path, "other.txt", "other", "Other work");
+        StdCommand::new("git")
+            .current_dir(repo_path)
+            .args(["checkout", "-q", "-"])
+            .output()
+            .unwrap();
+        commit_file(repo_path, "a.txt", "a", "Add a");
+        let before = head_oid(repo_path);
+
+        let mut steps = plan(wt, &base).unwrap();
+        let mut extra = steps[0].clone();
+        extra.oid = foreign;
+        steps.push(extra);
+        let result = execute(wt, &base, &steps);
+        assert!(matches!(result, Err(AppError::Other(ref m)) if m.contains("not among")));
+
+        let mut steps = plan(wt, &base).unwrap();
+        steps.push(steps[0].clone());
+        let result = execute(wt, &base, &steps);
+        assert!(matches!(result, Err(AppError::Other(ref m)) if m.contains("more than once")));
+
+        let mut steps = plan(wt, &base).unwrap();
+        steps.pop();
+        let result = execute(wt, &base, &steps);
+        assert!(
+            matches!(result, Err(AppError::Other(ref m)) if m.contains("missing from the plan"))
+        );
+
+        assert_eq!(head_oid(repo_path), before);
+        assert!(!repo_path.join("other.txt").exists());
+    }
+
+    #[test]
+    fn test_todo_runs_discovered_git() {
+        let temp_dir = create_test_repo();
+        let repo_path = temp_dir.path();
+        let wt = repo_path.to_str().unwrap();
+        let base = head_oid(repo_path);
+        commit_file(repo_path, "a.txt", "a", "Add a");
+
+        let mut steps = plan(wt, &base).unwrap();
+        set_action(&mut steps, 0, RebaseAction::Reword);
+        steps[0].message = Some("Reworded".to_string());
+        let repo = Repository::open(wt).unwrap();
+        let todo = fs::read_to_string(write_todo(&repo, &steps).unwrap()).unwrap();
+
+        let exec = todo.lines().find(|line| line.starts_with("exec ")).unwrap();
+        let git = shell_quote(&git_path().unwrap());
+        assert!(exec.starts_with(&format!("exec {} commit --amend", git)));
+    }
+}
@@ -0,0 +1,245 @@
+use crate::commands::reflog::ReflogEntry;
+use crate::error::{AppError, AppResult};
+use crate::git::branch_manager::{create_branch, delete_branch};
+use crate::git::worktree_manager::add_worktree;
+use git2::Repository;
+use std::path::Path;
+
+/// Expands a short branch name to its full ref; `None` means HEAD
+fn full_refname(reference: Option<&str>) -> String {
+    match reference {
+        None => "HEAD".to_string(),
+        Some(name) if name == "HEAD" || name.starts_with("refs/") => name.to_string(),
+        Some(name) => format!("refs/heads/{}", name),
+    }
+}
+
+/// Reads the reflog of `reference` as seen from a worktree, newest first.
+///
+/// Without a reference this is the worktree's own HEAD reflog, which each
+/// linked worktree keeps separately; branch reflogs are shared.
+pub fn read_reflog(
+    worktree_path: impl AsRef<Path>,
+    reference: Option<&str>,
+    limit: Option<usize>,
+) -> AppResult<Vec<ReflogEntry>> {
+    let repo = Repository::open(worktree_path.as_ref())?;
+    let reflog = repo.reflog(&full_refname(reference))?;
+
+    let entries = reflog
+        .iter()
+        .enumerate()
+        .take(limit.unwrap_or(usize::MAX))
+        .map(|(index, entry)| ReflogEntry {
+            index,
+            old_oid: Some(entry.id_old())
+                .filter(|oid| !oid.is_zero())
+                .map(|oid| oid.to_string()),
+            new_oid: entry.id_new().to_string(),
+            message: entry.message().map(|m| m.to_string()),
+            committer: entry.committer().name().unwrap_or_default().to_string(),
+            time: entry.committer().when().seconds(),
+        })
+        .collect();
+
+    Ok(entries)
+}
+
+fn reflog_oid(repo_path: &str, reference: Option<&str>, index: usize) -> AppResult<String> {
+    let repo = Repository::open(repo_path)?;
+    let refname = full_refname(reference);
+    let reflog = repo.reflog(&refname)?;
+
+    reflog
+        .get(index)
+        .map(|entry| entry.id_new().to_string())
+        .ok_or_else(|| AppError::Other(format!("No reflog entry {}@{{{}}}", refname, index)))
+}
+
+/// Creates a branch at the commit a reflog entry moved `reference` to
+pub fn create_branch_from_reflog(
+    worktree_path: &str,
+    reference: Option<&str>,
+    index: usize,
+    name: &str,
+) -> AppResult<()> {
+    let oid = reflog_oid(worktree_path, reference, index)?;
+    create_branch(worktree_path, name, Some(&oid))
+}
+
+/// Creates a branch at a reflog entry of the worktree or repository at
+/// `repo_path` and checks it out in a new worktree
+pub fn add_worktree_from_reflog(
+    repo_path: &str,
+    reference: Option<&str>,
+    index: usize,
+    worktree_path: impl AsRef<Path>,
+    branch: &str,
+) -> AppResult<()> {
+    create_branch_from_reflog(repo_path, reference, index, branch)?;
+
+    if let Err(e) = add_worktree(repo_path, worktree_path, branch, false) {
+        let _ = delete_branch(repo_path, branch, true);
+        return Err(e);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use git2::BranchType;
+    use std::fs;
+    use std::process::Command as StdCommand;
+    use tempfile::TempDir;
+
+    fn create_test_repo() -> TempDir {
+        let temp_dir = TempDir::new().unwrap();
+        let repo_path = temp_dir.path();
+
+        StdCommand::new("git")
+            .current_dir(repo_path)
+            .args(["init"])
+            .output()
+            .unwrap();
+
+        StdCommand::new("git")
+            .current_dir(repo_path)
+            .args(["config", "user.email", "test@test.com"])
+            .output()
+            .unwrap();
+
+        StdCommand::new("git")
+            .current_dir(repo_path)
+            .args(["config", "user.name", "Test User"])
+            .output()
+            .unwrap();
+
+        fs::write(repo_path.join("README.md"), "# Test Repository").unwrap();
+
+        StdCommand::new("git")
+            .current_dir(repo_path)
+            .args(["add", "."])
+            .output()
+            .unwrap();
+
+        StdCommand::new("git")
+            .current_dir(repo_path)
+            .args(["commit", "-m", "Initial commit"])
+            .output()
+            .unwrap();
+
+        temp_dir
+    }
+
+    fn commit_file(repo_path: &Path, name: &str) {
+        fs::write(repo_path.join(name), name).unwrap();
+        StdCommand::new("git")
+            .current_dir(repo_path)
+            .args(["add", name])
+            .output()
+            .unwrap();
+        StdCommand::new("git")
+            .current_dir(repo_path)
+            .args(["commit", "-m", name])
+            .output()
+            .unwrap();
+    }
+
+    fn head_oid(repo_path: &Path) -> String {
+        let repo = Repository::open(repo_path).unwrap();
+        let oid = repo.head().unwrap().target().unwrap();
+        oid.to_string()
+    }
+
+    #[test]
+    fn test_read_head_reflog() {
+        let temp_dir = create_test_repo();
+        let first = head_oid(temp_dir.path());
+        commit_file(temp_dir.path(), "a.txt");
+
+        let entries = read_reflog(temp_dir.path(), None, None).unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].index, 0);
+        assert_eq!(entries[0].new_oid, head_oid(temp_dir.path()));
+        assert_eq!(entries[0].old_oid.as_deref(), Some(first.as_str()));
+        assert!(entries[0].message.as_deref().unwrap().contains("a.txt"));
+        assert_eq!(entries[0].committer, "Test User");
+        assert!(entries[1].old_oid.is_none());
+
+        let limited = read_reflog(temp_dir.path(), None, Some(1)).unwrap();
+        assert_eq!(limited.len(), 1);
+    }
+
+    #[test]
+    fn test_worktree_has_own_head_reflog() {
+        let temp_dir = create_test_repo();
+        let repo_path = temp_dir.path().to_str().unwrap();
+        let wt_path = temp_dir.path().parent().unwrap().join("reflog-own-head-wt");
+        add_worktree(repo_path, &wt_path, "feature", true).unwrap();
+        commit_file(&wt_path, "b.txt");
+
+        let main_entries = read_reflog(temp_dir.path(), None, None).unwrap();
+        let wt_entries = read_reflog(&wt_path, None, None).unwrap();
+        assert_eq!(main_entries.len(), 1);
+        assert_eq!(wt_entries[0].new_oid, head_oid(&wt_path));
+
+        let branch_entries = read_reflog(temp_dir.path(), Some("feature"), None).unwrap();
+        assert_eq!(branch_entries[0].new_oid, head_oid(&wt_path));
+
+        fs::remove_dir_all(&wt_path).ok();
+    }
+
+    #[test]
+    fn test_create_branch_from_reflog() {
+        let temp_dir = create_test_repo();
+        let repo_path = temp_dir.path().to_str().unwrap();
+        let first = head_oid(temp_dir.path());
+        commit_file(temp_dir.path(), "a.txt");
+        StdCommand::new("git")
+            .current_dir(repo_path)
+            .args(["reset", "--hard", "HEAD~1"])
+            .output()
+            .unwrap();
+
+        // HEAD@{1} is the commit the reset discarded
+        create_branch_from_reflog(repo_path, None, 1, "recovered").unwrap();
+
+        let repo = Repository::open(repo_path).unwrap();
+        let branch = repo.find_branch("recovered", BranchType::Local).unwrap();
+        let tip = branch.get().target().unwrap().to_string();
+        assert_ne!(tip, first);
+        assert!(repo
+            .find_commit(branch.get().target().unwrap())
+            .unwrap()
+            .message()
+            .unwrap()
+            .contains("a.txt"));
+    }
+
+    #[test]
+    fn test_create_branch_from_missing_entry() {
+        let temp_dir = create_test_repo();
+        let repo_path = temp_dir.path().to_str().unwrap();
+
+        let result = create_branch_from_reflog(repo_path, None, 5, "nope");
+        assert!(matches!(result, Err(AppError::Other(_))));
+    }
+
+    #[test]
+    fn test_add_worktree_from_reflog() {
+        let temp_dir = create_test_repo();
+        let repo_path = temp_dir.path().to_str().unwrap();
+        let first = head_oid(temp_dir.path());
+        commit_file(temp_dir.path(), "a.txt");
+        let wt_path = temp_dir.path().parent().unwrap().join("reflog-add-wt");
+
+        add_worktree_from_reflog(repo_path, None, 1, &wt_path, "old-state").unwrap();
+
+        assert_eq!(head_oid(&wt_path), first);
+        assert!(!wt_path.join("a.txt").exists());
+
+        fs::remove_dir_all(&wt_path).ok();
+    }
+}
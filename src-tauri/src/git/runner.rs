@@ -0,0 +1,315 @@
+//! Runs git subprocesses.
+//!
+//! Every git the app spawns goes through [`Git`]: it uses the discovered
+//! executable, forces the C locale so output can be parsed, never lets git
+//! wait for input on a terminal, and kills commands that outlive their
+//! timeout. Failures carry the command line, exit code and stderr.
+
+use crate::error::{AppError, AppResult};
+use crate::git::askpass;
+use crate::git::executable::{git_command, git_command_for, Feature};
+use std::ffi::OsStr;
+use std::io::{Read, Write};
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+/// Timeout for local operations, which only take this long when stuck
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(10 * 60);
+
+/// Timeout for operations that talk to a remote, including the time spent
+/// answering credential prompts
+pub const NETWORK_TIMEOUT: Duration = Duration::from_secs(30 * 60);
+
+/// Longest wait between checks on whether the command has exited
+const MAX_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// A git command to run in a directory
+pub struct Git {
+    cmd: Command,
+    /// The command line as shown in errors
+    line: String,
+    input: Option<Vec<u8>>,
+    timeout: Duration,
+}
+
+/// What a finished command printed, and how it exited
+#[derive(Debug)]
+pub struct GitOutput {
+    /// The command line, as shown in errors
+    pub command: String,
+    /// `None` if the command was killed by a signal
+    pub exit_code: Option<i32>,
+    pub stdout: String,
+    pub stderr: String,
+}
+
+/// A git command using the discovered executable, run in `dir`
+pub fn git(dir: impl AsRef<Path>) -> AppResult<Git> {
+    Ok(Git::new(git_command()?, dir.as_ref()))
+}
+
+/// Like `git`, but first checks git is new enough for `feature`
+pub fn git_for(feature: Feature, dir: impl AsRef<Path>) -> AppResult<Git> {
+    Ok(Git::new(git_command_for(feature)?, dir.as_ref()))
+}
+
+/// Quotes an argument for display if it would otherwise be ambiguous
+fn quote(arg: &str) -> String {
+    if !arg.is_empty() && !arg.contains(|c: char| c.is_whitespace() || c == '\'' || c == '"') {
+        return arg.to_string();
+    }
+    format!("'{}'", arg.replace('\'', r"'\''"))
+}
+
+fn read_in_background(mut pipe: impl Read + Send + 'static) -> JoinHandle<Vec<u8>> {
+    thread::spawn(move || {
+        let mut buf = Vec::new();
+        let _ = pipe.read_to_end(&mut buf);
+        buf
+    })
+}
+
+impl Git {
+    fn new(mut cmd: Command, dir: &Path) -> Self {
+        cmd.current_dir(dir)
+            .env("LC_ALL", "C")
+            // Fail rather than wait for input on a terminal nobody sees
+            .env("GIT_TERMINAL_PROMPT", "0");
+        Git {
+            cmd,
+            line: "git".to_string(),
+            input: None,
+            timeout: DEFAULT_TIMEOUT,
+        }
+    }
+
+    pub fn arg(&mut self, arg: impl AsRef<OsStr>) -> &mut Self {
+        let arg = arg.as_ref();
+        self.line.push(' ');
+        self.line.push_str(&quote(&arg.to_string_lossy()));
+        self.cmd.arg(arg);
+        self
+    }
+
+    pub fn args<I, S>(&mut self, args: I) -> &mut Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<OsStr>,
+    {
+        for arg in args {
+            self.arg(arg);
+        }
+        self
+    }
+
+    pub fn env(&mut self, key: impl AsRef<OsStr>, value: impl AsRef<OsStr>) -> &mut Self {
+        self.cmd.env(key, value);
+        self
+    }
+
+    /// Feeds `input` to the command's stdin, which is otherwise empty
+    pub fn stdin(&mut self, input: impl Into<Vec<u8>>) -> &mut Self {
+        self.input = Some(input.into());
+        self
+    }
+
+    pub fn timeout(&mut self, timeout: Duration) -> &mut Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Lets git and ssh ask for credentials and host key confirmation
+    /// through the UI
+    pub fn askpass(&mut self) -> &mut Self {
+        askpass::configure(&mut self.cmd);
+        self
+    }
+
+    /// Runs the command to completion, whatever its exit code. Fails only if
+    /// it cannot be started or runs past its timeout.
+    pub fn output(&mut self) -> AppResult<GitOutput> {
+        let mut child = self
+            .cmd
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()?;
+
+        let stdout = child.stdout.take().map(read_in_background);
+        let stderr = child.stderr.take().map(read_in_background);
+        // Written from another thread, as a command that does not read its
+        // input would otherwise block us once the pipe is full
+        if let Some(mut stdin) = child.stdin.take() {
+            let input = self.input.take().unwrap_or_default();
+            thread::spawn(move || {
+                let _ = stdin.write_all(&input);
+            });
+        }
+
+        let deadline = Instant::now() + self.timeout;
+        let mut interval = Duration::from_millis(1);
+        let status = loop {
+            if let Some(status) = child.try_wait()? {
+                break status;
+            }
+            if Instant::now() >= deadline {
+                let _ = child.kill();
+                let _ = child.wait();
+                // The readers are left behind, as processes started by git
+                // may still hold the pipes open
+                return Err(AppError::Timeout {
+                    command: self.line.clone(),
+                    seconds: self.timeout.as_secs(),
+                });
+            }
+            thread::sleep(interval);
+            interval = (interval * 2).min(MAX_POLL_INTERVAL);
+        };
+
+        let collect = |reader: Option<JoinHandle<Vec<u8>>>| {
+            let bytes = reader
+                .and_then(|reader| reader.join().ok())
+                .unwrap_or_default();
+            String::from_utf8_lossy(&bytes).to_string()
+        };
+
+        Ok(GitOutput {
+            command: self.line.clone(),
+            exit_code: status.code(),
+            stdout: collect(stdout),
+            stderr: collect(stderr),
+        })
+    }
+
+    /// Runs the command and returns its stdout, failing unless it succeeds
+    pub fn run(&mut self) -> AppResult<String> {
+        Ok(self.output()?.check()?.stdout)
+    }
+}
+
+impl GitOutput {
+    pub fn success(&self) -> bool {
+        self.exit_code == Some(0)
+    }
+
+    /// The error describing this command's failure
+    pub fn error(&self) -> AppError {
+        AppError::GitCommand {
+            command: self.command.clone(),
+            exit_code: self.exit_code,
+            stderr: self.stderr.trim().to_string(),
+        }
+    }
+
+    /// Returns the output if the command succeeded, or else its error
+    pub fn check(self) -> AppResult<Self> {
+        if self.success() {
+            Ok(self)
+        } else {
+            Err(self.error())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn create_test_repo() -> TempDir {
+        let temp_dir = TempDir::new().unwrap();
+        git(temp_dir.path()).unwrap().arg("init").run().unwrap();
+        temp_dir
+    }
+
+    #[test]
+    fn test_run_sets_environment() {
+        let temp_dir = create_test_repo();
+
+        let env = git(temp_dir.path())
+            .unwrap()
+            .args(["-c", "alias.show-env=!env", "show-env"])
+            .env("WTVIEW_TEST", "1")
+            .run()
+            .unwrap();
+
+        let lines: Vec<&str> = env.lines().collect();
+        assert!(lines.contains(&"LC_ALL=C"));
+        assert!(lines.contains(&"GIT_TERMINAL_PROMPT=0"));
+        assert!(lines.contains(&"WTVIEW_TEST=1"));
+    }
+
+    #[test]
+    fn test_run_feeds_stdin() {
+        let temp_dir = create_test_repo();
+
+        let oid = git(temp_dir.path())
+            .unwrap()
+            .args(["hash-object", "--stdin"])
+            .stdin("hello\n")
+            .run()
+            .unwrap();
+
+        assert_eq!(oid.trim(), "ce013625030ba8dba906f756967f9e9ca394464a");
+    }
+
+    #[test]
+    fn test_failure_reports_command_and_exit_code() {
+        let temp_dir = create_test_repo();
+
+        let output = git(temp_dir.path())
+            .unwrap()
+            .args(["rev-parse", "--verify", "no such branch"])
+            .output()
+            .unwrap();
+        assert!(!output.success());
+        assert_eq!(output.exit_code, Some(128));
+
+        let err = output.check().unwrap_err();
+        match &err {
+            AppError::GitCommand {
+                command,
+                exit_code,
+                stderr,
+            } => {
+                assert_eq!(command, "git rev-parse --verify 'no such branch'");
+                assert_eq!(*exit_code, Some(128));
+                assert_eq!(stderr, "fatal: Needed a single revision");
+            }
+            other => panic!("unexpected error: {other:?}"),
+        }
+        assert_eq!(
+            err.to_string(),
+            "Command failed: `git rev-parse --verify 'no such branch'` exited with code 128: \
+             fatal: Needed a single revision"
+        );
+    }
+
+    #[test]
+    fn test_timeout_kills_command() {
+        let temp_dir = create_test_repo();
+
+        let started = Instant::now();
+        let result = git(temp_dir.path())
+            .unwrap()
+            .args(["-c", "alias.hang=!sleep 5", "hang"])
+            .timeout(Duration::from_millis(200))
+            .output();
+
+        assert!(started.elapsed() < Duration::from_secs(4));
+        assert!(matches!(
+            result,
+            Err(AppError::Timeout { ref command, .. }) if command == "git -c 'alias.hang=!sleep 5' hang"
+        ));
+    }
+
+    #[test]
+    fn test_quote() {
+        assert_eq!(quote("worktree"), "worktree");
+        assert_eq!(quote("my file"), "'my file'");
+        assert_eq!(quote("it's"), r"'it'\''s'");
+        assert_eq!(quote(""), "''");
+    }
+}
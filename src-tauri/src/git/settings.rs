@@ -0,0 +1,44 @@
+use crate::commands::backend::GitBackend;
+use crate::error::{AppError, AppResult};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+
+/// App-wide settings, in the user's config directory
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Settings {
+    /// Git executable to run instead of looking one up
+    #[serde(default)]
+    pub git_path: Option<PathBuf>,
+    #[serde(default)]
+    pub backend: GitBackend,
+}
+
+fn settings_path() -> Option<PathBuf> {
+    // Tests run with the default backend and git from PATH, whatever the
+    // user has saved, and never overwrite the user's settings
+    if cfg!(test) {
+        return None;
+    }
+    dirs::config_dir().map(|dir| dir.join("wtview").join("settings.json"))
+}
+
+/// Reads the settings, falling back to the defaults if there are none or
+/// they cannot be read
+pub fn load() -> Settings {
+    settings_path()
+        .and_then(|path| fs::read_to_string(path).ok())
+        .and_then(|contents| serde_json::from_str(&contents).ok())
+        .unwrap_or_default()
+}
+
+pub fn save(settings: &Settings) -> AppResult<()> {
+    let path = settings_path()
+        .ok_or_else(|| AppError::Other("No config directory to save settings in".to_string()))?;
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let contents = serde_json::to_string_pretty(settings).map_err(std::io::Error::from)?;
+    fs::write(path, contents)?;
+    Ok(())
+}
@@ -0,0 +1,441 @@
+use crate::commands::sparse::{SparseCheckoutInfo, SparseProfile};
+use crate::error::{AppError, AppResult};
+use crate::git::branch_manager::delete_branch;
+use crate::git::executable::Feature;
+use crate::git::paths::resolve_worktree_path;
+use crate::git::runner::{git, git_for};
+use crate::git::worktree_manager::{
+    add_worktree_without_checkout, common_dir, validate_repository,
+};
+use git2::{IndexEntryExtendedFlag, Repository};
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Runs git in a worktree, feeding `input` to its stdin, and returns stdout
+fn run_git(worktree_path: &Path, args: &[&str], input: Option<&str>) -> AppResult<String> {
+    git_for(Feature::SparseCheckout, worktree_path)?
+        .args(args)
+        .stdin(input.unwrap_or_default())
+        .run()
+}
+
+/// Reads a boolean from the worktree's effective config, which includes its
+/// `config.worktree` where sparse checkout settings live
+fn config_bool(worktree_path: &Path, key: &str) -> bool {
+    run_git(
+        worktree_path,
+        &["config", "--type=bool", "--get", key],
+        None,
+    )
+    .is_ok_and(|value| value.trim() == "true")
+}
+
+/// Index paths excluded by sparse checkout. libgit2 ignores the
+/// skip-worktree bit, so without filtering these their missing files look
+/// deleted.
+pub fn skip_worktree_paths(repo: &Repository) -> AppResult<HashSet<Vec<u8>>> {
+    let index = repo.index()?;
+    Ok(index
+        .iter()
+        .filter(|entry| {
+            IndexEntryExtendedFlag::from_bits_truncate(entry.flags_extended).is_skip_worktree()
+        })
+        .map(|entry| entry.path)
+        .collect())
+}
+
+fn info(worktree_path: &Path) -> AppResult<SparseCheckoutInfo> {
+    let enabled = config_bool(worktree_path, "core.sparseCheckout");
+    if !enabled {
+        return Ok(SparseCheckoutInfo {
+            enabled,
+            cone: false,
+            patterns: Vec::new(),
+        });
+    }
+
+    let patterns = run_git(worktree_path, &["sparse-checkout", "list"], None)?
+        .lines()
+        .map(String::from)
+        .collect();
+
+    Ok(SparseCheckoutInfo {
+        enabled,
+        cone: config_bool(worktree_path, "core.sparseCheckoutCone"),
+        patterns,
+    })
+}
+
+pub fn sparse_checkout_info(worktree_path: &str) -> AppResult<SparseCheckoutInfo> {
+    info(Path::new(worktree_path))
+}
+
+/// Replaces the worktree's sparse directories, enabling cone-mode sparse
+/// checkout if needed. Only files at the root and inside these directories
+/// stay checked out.
+fn set(worktree_path: &Path, patterns: &[String]) -> AppResult<()> {
+    let input = patterns.join("\n");
+    run_git(
+        worktree_path,
+        &["sparse-checkout", "set", "--cone", "--stdin"],
+        Some(&input),
+    )?;
+    Ok(())
+}
+
+pub fn set_patterns(worktree_path: &str, patterns: &[String]) -> AppResult<()> {
+    set(Path::new(worktree_path), patterns)
+}
+
+/// Enables cone-mode sparse checkout, keeping existing cone patterns; with
+/// none, only files at the root stay checked out
+pub fn enable(worktree_path: &str) -> AppResult<()> {
+    let path = Path::new(worktree_path);
+    let current = info(path)?;
+    let patterns = if current.cone {
+        current.patterns
+    } else {
+        Vec::new()
+    };
+    set(path, &patterns)
+}
+
+pub fn disable(worktree_path: &str) -> AppResult<()> {
+    run_git(
+        Path::new(worktree_path),
+        &["sparse-checkout", "disable"],
+        None,
+    )?;
+    Ok(())
+}
+
+/// Adds directories to an enabled sparse checkout
+pub fn add_patterns(worktree_path: &str, patterns: &[String]) -> AppResult<()> {
+    let input = patterns.join("\n");
+    run_git(
+        Path::new(worktree_path),
+        &["sparse-checkout", "add", "--stdin"],
+        Some(&input),
+    )?;
+    Ok(())
+}
+
+/// Location of the saved profiles, shared by all worktrees of a repository
+fn profiles_path(repo: &Repository) -> PathBuf {
+    common_dir(repo).join("wtview").join("sparse-profiles.json")
+}
+
+fn load_profiles(repo: &Repository) -> AppResult<Vec<SparseProfile>> {
+    match fs::read_to_string(profiles_path(repo)) {
+        Ok(contents) => Ok(serde_json::from_str(&contents).map_err(std::io::Error::from)?),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Vec::new()),
+        Err(e) => Err(e.into()),
+    }
+}
+
+fn save_profiles(repo: &Repository, profiles: &[SparseProfile]) -> AppResult<()> {
+    let path = profiles_path(repo);
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let contents = serde_json::to_string_pretty(profiles).map_err(std::io::Error::from)?;
+    fs::write(path, contents)?;
+    Ok(())
+}
+
+pub fn list_profiles(repo_path: &str) -> AppResult<Vec<SparseProfile>> {
+    let repo = validate_repository(repo_path)?;
+    load_profiles(&repo)
+}
+
+/// Saves a profile, replacing any existing one with the same name
+pub fn save_profile(repo_path: &str, name: &str, patterns: &[String]) -> AppResult<()> {
+    if name.trim().is_empty() {
+        return Err(AppError::Other(
+            "Sparse profile name cannot be empty".to_string(),
+        ));
+    }
+
+    let repo = validate_repository(repo_path)?;
+    let mut profiles = load_profiles(&repo)?;
+    profiles.retain(|profile| profile.name != name);
+    profiles.push(SparseProfile {
+        name: name.to_string(),
+        patterns: patterns.to_vec(),
+    });
+    profiles.sort_by(|a, b| a.name.cmp(&b.name));
+    save_profiles(&repo, &profiles)
+}
+
+pub fn delete_profile(repo_path: &str, name: &str) -> AppResult<()> {
+    let repo = validate_repository(repo_path)?;
+    let mut profiles = load_profiles(&repo)?;
+    let count = profiles.len();
+    profiles.retain(|profile| profile.name != name);
+    if profiles.len() == count {
+        return Err(AppError::Other(format!("No sparse profile named {}", name)));
+    }
+    save_profiles(&repo, &profiles)
+}
+
+/// Adds a worktree that only ever checks out the directories of a saved
+/// profile; a plain `git worktree add` would check out the entire tree first
+pub fn add_sparse_worktree(
+    repo_path: &str,
+    worktree_path: impl AsRef<Path>,
+    branch: &str,
+    create_branch: bool,
+    profile: &str,
+) -> AppResult<()> {
+    let repo = validate_repository(repo_path)?;
+    let patterns = load_profiles(&repo)?
+        .into_iter()
+        .find(|p| p.name == profile)
+        .map(|p| p.patterns)
+        .ok_or_else(|| AppError::Other(format!("No sparse profile named {}", profile)))?;
+
+    let worktree_path = resolve_worktree_path(repo_path, worktree_path);
+    add_worktree_without_checkout(repo_path, &worktree_path, branch, create_branch)?;
+    if let Err(error) = set(&worktree_path, &patterns)
+        .and_then(|()| run_git(&worktree_path, &["checkout"], None).map(drop))
+    {
+        let created_branch = create_branch.then_some(branch);
+        return Err(roll_back_sparse_worktree(
+            repo_path,
+            &worktree_path,
+            error,
+            created_branch,
+        ));
+    }
+
+    Ok(())
+}
+
+/// Removes the worktree `add_sparse_worktree` added and the branch it
+/// created before failing with `error`, adding whatever could not be undone
+/// to it
+fn roll_back_sparse_worktree(
+    repo_path: &str,
+    worktree_path: &Path,
+    error: AppError,
+    created_branch: Option<&str>,
+) -> AppError {
+    let mut left_behind = Vec::new();
+    let removed = git(repo_path).and_then(|mut cmd| {
+        cmd.args(["worktree", "remove", "--force"])
+            .arg(worktree_path)
+            .run()
+    });
+    if removed.is_err() {
+        left_behind.push(format!("worktree {} was kept", worktree_path.display()));
+    } else if let Some(branch) = created_branch {
+        // The branch cannot be deleted while the worktree still has it
+        if delete_branch(repo_path, branch, true).is_err() {
+            left_behind.push(format!("created branch '{}' was kept", branch));
+        }
+    }
+
+    if left_behind.is_empty() {
+        error
+    } else {
+        AppError::Other(format!("{} ({})", error, left_behind.join("; ")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::worktree_manager::add_worktree;
+    use std::fs;
+    use std::process::Command as StdCommand;
+    use tempfile::TempDir;
+
+    fn create_test_repo() -> TempDir {
+        let temp_dir = TempDir::new().unwrap();
+        let repo_path = temp_dir.path();
+
+        StdCommand::new("git")
+            .current_dir(repo_path)
+            .args(["init"])
+            .output()
+            .unwrap();
+
+        StdCommand::new("git")
+            .current_dir(repo_path)
+            .args(["config", "user.email", "test@test.com"])
+            .output()
+            .unwrap();
+
+        StdCommand::new("git")
+            .current_dir(repo_path)
+            .args(["config", "user.name", "Test User"])
+            .output()
+            .unwrap();
+
+        fs::write(repo_path.join("README.md"), "# Test Repository").unwrap();
+        for dir in ["app/web", "app/api", "docs"] {
+            fs::create_dir_all(repo_path.join(dir)).unwrap();
+            fs::write(repo_path.join(dir).join("file.txt"), dir).unwrap();
+        }
+
+        StdCommand::new("git")
+            .current_dir(repo_path)
+            .args(["add", "."])
+            .output()
+            .unwrap();
+
+        StdCommand::new("git")
+            .current_dir(repo_path)
+            .args(["commit", "-m", "Initial commit"])
+            .output()
+            .unwrap();
+
+        temp_dir
+    }
+
+    fn strings(items: &[&str]) -> Vec<String> {
+        items.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_sparse_checkout_disabled_by_default() {
+        let temp_dir = create_test_repo();
+
+        let info = sparse_checkout_info(temp_dir.path().to_str().unwrap()).unwrap();
+        assert!(!info.enabled);
+        assert!(info.patterns.is_empty());
+    }
+
+    #[test]
+    fn test_set_add_and_disable_patterns() {
+        let temp_dir = create_test_repo();
+        let repo_path = temp_dir.path().to_str().unwrap();
+
+        set_patterns(repo_path, &strings(&["app/web"])).unwrap();
+        let info = sparse_checkout_info(repo_path).unwrap();
+        assert!(info.enabled);
+        assert!(info.cone);
+        assert_eq!(info.patterns, vec!["app/web"]);
+        assert!(temp_dir.path().join("README.md").exists());
+        assert!(temp_dir.path().join("app/web/file.txt").exists());
+        assert!(!temp_dir.path().join("app/api/file.txt").exists());
+        assert!(!temp_dir.path().join("docs/file.txt").exists());
+
+        add_patterns(repo_path, &strings(&["docs"])).unwrap();
+        let info = sparse_checkout_info(repo_path).unwrap();
+        assert_eq!(info.patterns, vec!["app/web", "docs"]);
+        assert!(temp_dir.path().join("docs/file.txt").exists());
+
+        disable(repo_path).unwrap();
+        assert!(!sparse_checkout_info(repo_path).unwrap().enabled);
+        assert!(temp_dir.path().join("app/api/file.txt").exists());
+    }
+
+    #[test]
+    fn test_enable_keeps_only_root_files() {
+        let temp_dir = create_test_repo();
+        let repo_path = temp_dir.path().to_str().unwrap();
+
+        enable(repo_path).unwrap();
+
+        let info = sparse_checkout_info(repo_path).unwrap();
+        assert!(info.enabled);
+        assert!(info.patterns.is_empty());
+        assert!(temp_dir.path().join("README.md").exists());
+        assert!(!temp_dir.path().join("docs").exists());
+    }
+
+    #[test]
+    fn test_sparse_checkout_is_per_worktree() {
+        let temp_dir = create_test_repo();
+        let repo_path = temp_dir.path().to_str().unwrap();
+        let wt_path = temp_dir.path().parent().unwrap().join("sparse-per-wt");
+        add_worktree(repo_path, &wt_path, "sparse-per", true).unwrap();
+
+        set_patterns(wt_path.to_str().unwrap(), &strings(&["docs"])).unwrap();
+
+        assert!(!sparse_checkout_info(repo_path).unwrap().enabled);
+        assert!(temp_dir.path().join("app/web/file.txt").exists());
+        assert!(!wt_path.join("app/web/file.txt").exists());
+
+        fs::remove_dir_all(&wt_path).ok();
+    }
+
+    #[test]
+    fn test_save_list_and_delete_profiles() {
+        let temp_dir = create_test_repo();
+        let repo_path = temp_dir.path().to_str().unwrap();
+
+        save_profile(repo_path, "web", &strings(&["app/web"])).unwrap();
+        save_profile(repo_path, "api", &strings(&["app/api"])).unwrap();
+        save_profile(repo_path, "web", &strings(&["app/web", "docs"])).unwrap();
+
+        let profiles = list_profiles(repo_path).unwrap();
+        let names: Vec<&str> = profiles.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, vec!["api", "web"]);
+        assert_eq!(profiles[1].patterns, vec!["app/web", "docs"]);
+
+        delete_profile(repo_path, "api").unwrap();
+        assert_eq!(list_profiles(repo_path).unwrap().len(), 1);
+        assert!(matches!(
+            delete_profile(repo_path, "api"),
+            Err(AppError::Other(_))
+        ));
+        assert!(save_profile(repo_path, " ", &[]).is_err());
+    }
+
+    #[test]
+    fn test_add_sparse_worktree() {
+        let temp_dir = create_test_repo();
+        let repo_path = temp_dir.path().to_str().unwrap();
+        let wt_path = temp_dir.path().parent().unwrap().join("sparse-add-wt");
+        save_profile(repo_path, "api", &strings(&["app/api"])).unwrap();
+
+        add_sparse_worktree(repo_path, &wt_path, "sparse-add", true, "api").unwrap();
+
+        assert!(wt_path.join("README.md").exists());
+        assert!(wt_path.join("app/api/file.txt").exists());
+        assert!(!wt_path.join("app/web").exists());
+        assert!(!wt_path.join("docs").exists());
+        let info = sparse_checkout_info(wt_path.to_str().unwrap()).unwrap();
+        assert_eq!(info.patterns, vec!["app/api"]);
+        let status = crate::git::operations::status(&wt_path).unwrap();
+        // Files outside the profile are not reported as deleted
+        assert!(status.files.is_empty());
+        assert!(!crate::git::worktree_manager::has_uncommitted_changes(&wt_path).unwrap());
+
+        fs::remove_dir_all(&wt_path).ok();
+    }
+
+    #[test]
+    fn test_add_sparse_worktree_unknown_profile() {
+        let temp_dir = create_test_repo();
+        let repo_path = temp_dir.path().to_str().unwrap();
+        let wt_path = temp_dir.path().parent().unwrap().join("sparse-unknown-wt");
+
+        let result = add_sparse_worktree(repo_path, &wt_path, "sparse-unknown", true, "nope");
+        assert!(matches!(result, Err(AppError::Other(_))));
+        assert!(!wt_path.exists());
+    }
+
+    #[test]
+    fn test_add_sparse_worktree_cleans_up_on_failure() {
+        let temp_dir = create_test_repo();
+        let repo_path = temp_dir.path().to_str().unwrap();
+        let wt_dir = TempDir::new().unwrap();
+        let wt_path = wt_dir.path().join("wt");
+        // Cone mode refuses patterns that leave the worktree
+        save_profile(repo_path, "bad", &strings(&["../outside"])).unwrap();
+
+        let result = add_sparse_worktree(repo_path, &wt_path, "sparse-bad", true, "bad");
+
+        assert!(result.is_err());
+        assert!(!wt_path.exists());
+        let repo = Repository::open(repo_path).unwrap();
+        assert_eq!(repo.worktrees().unwrap().len(), 0);
+        assert!(repo
+            .find_branch("sparse-bad", git2::BranchType::Local)
+            .is_err());
+    }
+}
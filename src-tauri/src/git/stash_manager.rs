@@ -0,0 +1,532 @@
+use crate::commands::git_ops::FileStatus;
+use crate::commands::stash::StashInfo;
+use crate::error::{AppError, AppResult};
+use crate::git::paths::{normalize_path, same_path};
+use crate::git::runner::git;
+use git2::{Delta, Oid, Repository, Signature, Tree};
+use std::path::Path;
+
+/// Notes ref recording which worktree each stash was pushed from.
+///
+/// Stashes live in the common git directory and are shared by every
+/// worktree, but git itself only records the branch in the stash message.
+const STASH_NOTES_REF: &str = "refs/notes/wtview-stash";
+
+/// Extracts the branch from a stash message such as `WIP on main: ...` or
+/// `On feature: message`. Detached HEAD stashes yield `None`.
+fn branch_from_message(message: &str) -> Option<String> {
+    let rest = message
+        .strip_prefix("WIP on ")
+        .or_else(|| message.strip_prefix("On "))?;
+    let (branch, _) = rest.split_once(':')?;
+    if branch == "(no branch)" {
+        return None;
+    }
+    Some(branch.to_string())
+}
+
+fn stash_entries(repo: &mut Repository) -> AppResult<Vec<(usize, String, Oid)>> {
+    let mut entries = Vec::new();
+    repo.stash_foreach(|index, message, oid| {
+        entries.push((index, message.to_string(), *oid));
+        true
+    })?;
+    Ok(entries)
+}
+
+fn stash_oid(repo: &mut Repository, index: usize) -> AppResult<Oid> {
+    stash_entries(repo)?
+        .into_iter()
+        .find(|(i, _, _)| *i == index)
+        .map(|(_, _, oid)| oid)
+        .ok_or_else(|| AppError::Other(format!("Stash not found: stash@{{{}}}", index)))
+}
+
+/// Signature of the configured user, falling back to a wtview identity when
+/// none is configured
+pub fn app_signature(repo: &Repository) -> AppResult<Signature<'static>> {
+    match repo.signature() {
+        Ok(sig) => Ok(sig),
+        Err(_) => Ok(Signature::now("wtview", "wtview@localhost")?),
+    }
+}
+
+/// Rewrites the recorded origin of stashes pushed from a worktree that has
+/// moved from `old_path` to `new_path`
+pub fn relocate_stash_worktree(
+    repo: &Repository,
+    old_path: &Path,
+    new_path: &Path,
+) -> AppResult<()> {
+    let Ok(notes) = repo.notes(Some(STASH_NOTES_REF)) else {
+        return Ok(());
+    };
+
+    let mut moved = Vec::new();
+    for note in notes {
+        let (_, stash_oid) = note?;
+        let recorded = repo
+            .find_note(Some(STASH_NOTES_REF), stash_oid)?
+            .message()
+            .map(String::from);
+        if recorded.is_some_and(|p| same_path(p, old_path)) {
+            moved.push(stash_oid);
+        }
+    }
+
+    let new_path = new_path.to_string_lossy();
+    let sig = app_signature(repo)?;
+    for stash_oid in moved {
+        repo.note(
+            &sig,
+            &sig,
+            Some(STASH_NOTES_REF),
+            stash_oid,
+            &new_path,
+            true,
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Lists all stashes of the repository, newest first
+pub fn list_stashes(worktree_path: impl AsRef<Path>) -> AppResult<Vec<StashInfo>> {
+    let mut repo = Repository::open(worktree_path.as_ref())?;
+    let entries = stash_entries(&mut repo)?;
+
+    let mut stashes = Vec::new();
+    for (index, message, oid) in entries {
+        let time = repo.find_commit(oid).map(|c| c.time().seconds()).ok();
+        let worktree_path = repo
+            .find_note(Some(STASH_NOTES_REF), oid)
+            .ok()
+            .and_then(|note| note.message().map(String::from));
+
+        stashes.push(StashInfo {
+            index,
+            oid: oid.to_string(),
+            branch: branch_from_message(&message),
+            message,
+            worktree_path,
+            time,
+        });
+    }
+
+    Ok(stashes)
+}
+
+/// Stashes local changes of a worktree, optionally limited to `paths`.
+///
+/// Returns the new stash, or `None` if there was nothing to stash.
+pub fn push_stash(
+    worktree_path: &str,
+    message: Option<&str>,
+    include_untracked: bool,
+    keep_index: bool,
+    paths: &[String],
+) -> AppResult<Option<StashInfo>> {
+    let repo = Repository::open(worktree_path)?;
+    let previous = repo.refname_to_id("refs/stash").ok();
+
+    let mut cmd = git(worktree_path)?;
+    cmd.args(["stash", "push"]);
+
+    if let Some(message) = message {
+        cmd.args(["-m", message]);
+    }
+    if include_untracked {
+        cmd.arg("--include-untracked");
+    }
+    if keep_index {
+        cmd.arg("--keep-index");
+    }
+    if !paths.is_empty() {
+        cmd.arg("--");
+        cmd.args(paths);
+    }
+
+    cmd.run()?;
+
+    let Ok(oid) = repo.refname_to_id("refs/stash") else {
+        return Ok(None);
+    };
+    if Some(oid) == previous {
+        return Ok(None);
+    }
+
+    let workdir = normalize_path(repo.workdir().unwrap_or(Path::new(worktree_path)));
+    let workdir = workdir.to_string_lossy();
+    let sig = app_signature(&repo)?;
+    repo.note(&sig, &sig, Some(STASH_NOTES_REF), oid, &workdir, true)?;
+
+    Ok(list_stashes(worktree_path)?.into_iter().next())
+}
+
+fn run_stash_command(
+    worktree_path: &str,
+    action: &str,
+    index: usize,
+    restore_index: bool,
+) -> AppResult<()> {
+    let mut cmd = git(worktree_path)?;
+    cmd.args(["stash", action]);
+
+    if restore_index {
+        cmd.arg("--index");
+    }
+
+    cmd.arg(format!("stash@{{{}}}", index));
+    cmd.run()?;
+
+    Ok(())
+}
+
+fn forget_stash_worktree(repo: &Repository, oid: Oid) -> AppResult<()> {
+    if repo.find_note(Some(STASH_NOTES_REF), oid).is_ok() {
+        let sig = app_signature(repo)?;
+        repo.note_delete(oid, Some(STASH_NOTES_REF), &sig, &sig)?;
+    }
+    Ok(())
+}
+
+fn validate_stash_index(worktree_path: &str, index: usize) -> AppResult<()> {
+    let mut repo = Repository::open(worktree_path)?;
+    stash_oid(&mut repo, index).map(|_| ())
+}
+
+/// Applies a stash to a worktree, keeping it in the stash list
+pub fn apply_stash(worktree_path: &str, index: usize, restore_index: bool) -> AppResult<()> {
+    validate_stash_index(worktree_path, index)?;
+    run_stash_command(worktree_path, "apply", index, restore_index)
+}
+
+/// Applies a stash to a worktree and removes it from the stash list
+pub fn pop_stash(worktree_path: &str, index: usize, restore_index: bool) -> AppResult<()> {
+    let mut repo = Repository::open(worktree_path)?;
+    let oid = stash_oid(&mut repo, index)?;
+    run_stash_command(worktree_path, "pop", index, restore_index)?;
+    forget_stash_worktree(&repo, oid)
+}
+
+/// Removes a stash without applying it
+pub fn drop_stash(worktree_path: &str, index: usize) -> AppResult<()> {
+    let mut repo = Repository::open(worktree_path)?;
+    let oid = stash_oid(&mut repo, index)?;
+    run_stash_command(worktree_path, "drop", index, false)?;
+    forget_stash_worktree(&repo, oid)
+}
+
+fn diff_files(
+    repo: &Repository,
+    old_tree: Option<&Tree>,
+    new_tree: Option<&Tree>,
+    staged: bool,
+    files: &mut Vec<FileStatus>,
+) -> AppResult<()> {
+    let diff = repo.diff_tree_to_tree(old_tree, new_tree, None)?;
+
+    for delta in diff.deltas() {
+        let status = match delta.status() {
+            Delta::Added => "added",
+            Delta::Deleted => "deleted",
+            Delta::Renamed => "renamed",
+            Delta::Typechange => "typechange",
+            _ => "modified",
+        };
+        let path = delta
+            .new_file()
+            .path()
+            .or_else(|| delta.old_file().path())
+            .unwrap_or(Path::new(""));
+
+        files.push(FileStatus::new(path, status, staged));
+    }
+
+    Ok(())
+}
+
+/// Lists the files changed by a stash.
+///
+/// Changes recorded in the stash's index are reported as staged, working
+/// tree changes as unstaged, and stashed untracked files as `untracked`.
+pub fn show_stash(worktree_path: &str, index: usize) -> AppResult<Vec<FileStatus>> {
+    let mut repo = Repository::open(worktree_path)?;
+    let oid = stash_oid(&mut repo, index)?;
+
+    let stash_commit = repo.find_commit(oid)?;
+    let base_tree = stash_commit.parent(0)?.tree()?;
+    let index_tree = stash_commit.parent(1)?.tree()?;
+    let worktree_tree = stash_commit.tree()?;
+
+    let mut files = Vec::new();
+    diff_files(&repo, Some(&base_tree), Some(&index_tree), true, &mut files)?;
+    diff_files(
+        &repo,
+        Some(&index_tree),
+        Some(&worktree_tree),
+        false,
+        &mut files,
+    )?;
+
+    if let Ok(untracked) = stash_commit.parent(2) {
+        let untracked_tree = untracked.tree()?;
+        let mut untracked_files = Vec::new();
+        diff_files(
+            &repo,
+            None,
+            Some(&untracked_tree),
+            false,
+            &mut untracked_files,
+        )?;
+        for mut file in untracked_files {
+            file.status = "untracked".to_string();
+            files.push(file);
+        }
+    }
+
+    Ok(files)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::process::Command as StdCommand;
+    use tempfile::TempDir;
+
+    fn create_test_repo() -> TempDir {
+        let temp_dir = TempDir::new().unwrap();
+        let repo_path = temp_dir.path();
+
+        StdCommand::new("git")
+            .current_dir(repo_path)
+            .args(["init"])
+            .output()
+            .unwrap();
+
+        StdCommand::new("git")
+            .current_dir(repo_path)
+            .args(["config", "user.email", "test@test.com"])
+            .output()
+            .unwrap();
+
+        StdCommand::new("git")
+            .current_dir(repo_path)
+            .args(["config", "user.name", "Test User"])
+            .output()
+            .unwrap();
+
+        fs::write(repo_path.join("README.md"), "# Test Repository").unwrap();
+
+        StdCommand::new("git")
+            .current_dir(repo_path)
+            .args(["add", "."])
+            .output()
+            .unwrap();
+
+        StdCommand::new("git")
+            .current_dir(repo_path)
+            .args(["commit", "-m", "Initial commit"])
+            .output()
+            .unwrap();
+
+        temp_dir
+    }
+
+    #[test]
+    fn test_branch_from_message() {
+        assert_eq!(
+            branch_from_message("WIP on main: abc1234 Initial commit"),
+            Some("main".to_string())
+        );
+        assert_eq!(
+            branch_from_message("On feature/x: my message"),
+            Some("feature/x".to_string())
+        );
+        assert_eq!(branch_from_message("WIP on (no branch): abc1234 msg"), None);
+        assert_eq!(branch_from_message("custom"), None);
+    }
+
+    #[test]
+    fn test_push_and_list_stash() {
+        let temp_dir = create_test_repo();
+        let repo_path = temp_dir.path().to_str().unwrap();
+
+        fs::write(temp_dir.path().join("README.md"), "# Changed").unwrap();
+
+        let stash = push_stash(repo_path, Some("my work"), false, false, &[])
+            .unwrap()
+            .unwrap();
+        assert_eq!(stash.index, 0);
+        assert!(stash.message.contains("my work"));
+        assert!(stash.branch.is_some());
+        assert!(stash.worktree_path.is_some());
+
+        let stashes = list_stashes(repo_path).unwrap();
+        assert_eq!(stashes.len(), 1);
+
+        let content = fs::read_to_string(temp_dir.path().join("README.md")).unwrap();
+        assert_eq!(content, "# Test Repository");
+    }
+
+    #[test]
+    fn test_push_stash_nothing_to_stash() {
+        let temp_dir = create_test_repo();
+        let repo_path = temp_dir.path().to_str().unwrap();
+
+        let stash = push_stash(repo_path, None, false, false, &[]).unwrap();
+        assert!(stash.is_none());
+    }
+
+    #[test]
+    fn test_push_stash_include_untracked_and_paths() {
+        let temp_dir = create_test_repo();
+        let repo_path = temp_dir.path().to_str().unwrap();
+
+        fs::write(temp_dir.path().join("README.md"), "# Changed").unwrap();
+        fs::write(temp_dir.path().join("new.txt"), "new").unwrap();
+
+        push_stash(repo_path, None, true, false, &["new.txt".to_string()])
+            .unwrap()
+            .unwrap();
+
+        assert!(!temp_dir.path().join("new.txt").exists());
+        let content = fs::read_to_string(temp_dir.path().join("README.md")).unwrap();
+        assert_eq!(content, "# Changed");
+
+        let files = show_stash(repo_path, 0).unwrap();
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].path, "new.txt");
+        assert_eq!(files[0].status, "untracked");
+    }
+
+    #[test]
+    fn test_show_stash_staged_and_unstaged() {
+        let temp_dir = create_test_repo();
+        let repo_path = temp_dir.path().to_str().unwrap();
+
+        fs::write(temp_dir.path().join("staged.txt"), "staged").unwrap();
+        StdCommand::new("git")
+            .current_dir(repo_path)
+            .args(["add", "staged.txt"])
+            .output()
+            .unwrap();
+        fs::write(temp_dir.path().join("README.md"), "# Changed").unwrap();
+
+        push_stash(repo_path, None, false, false, &[])
+            .unwrap()
+            .unwrap();
+
+        let files = show_stash(repo_path, 0).unwrap();
+        let staged = files.iter().find(|f| f.path == "staged.txt").unwrap();
+        assert!(staged.staged);
+        assert_eq!(staged.status, "added");
+        let unstaged = files.iter().find(|f| f.path == "README.md").unwrap();
+        assert!(!unstaged.staged);
+        assert_eq!(unstaged.status, "modified");
+    }
+
+    #[test]
+    fn test_apply_pop_and_drop_stash() {
+        let temp_dir = create_test_repo();
+        let repo_path = temp_dir.path().to_str().unwrap();
+        let readme = temp_dir.path().join("README.md");
+
+        fs::write(&readme, "# First").unwrap();
+        push_stash(repo_path, Some("first"), false, false, &[]).unwrap();
+        fs::write(&readme, "# Second").unwrap();
+        push_stash(repo_path, Some("second"), false, false, &[]).unwrap();
+
+        apply_stash(repo_path, 1, false).unwrap();
+        assert_eq!(fs::read_to_string(&readme).unwrap(), "# First");
+        assert_eq!(list_stashes(repo_path).unwrap().len(), 2);
+
+        StdCommand::new("git")
+            .current_dir(repo_path)
+            .args(["checkout", "--", "README.md"])
+            .output()
+            .unwrap();
+
+        pop_stash(repo_path, 0, false).unwrap();
+        assert_eq!(fs::read_to_string(&readme).unwrap(), "# Second");
+
+        let stashes = list_stashes(repo_path).unwrap();
+        assert_eq!(stashes.len(), 1);
+        assert!(stashes[0].message.contains("first"));
+
+        drop_stash(repo_path, 0).unwrap();
+        assert!(list_stashes(repo_path).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_stash_records_linked_worktree() {
+        let temp_dir = create_test_repo();
+        let repo_path = temp_dir.path().to_str().unwrap();
+        let worktree_path = temp_dir.path().parent().unwrap().join("stash-wt");
+
+        StdCommand::new("git")
+            .current_dir(repo_path)
+            .args([
+                "worktree",
+                "add",
+                "-b",
+                "stash-branch",
+                worktree_path.to_str().unwrap(),
+            ])
+            .output()
+            .unwrap();
+
+        fs::write(worktree_path.join("README.md"), "# From worktree").unwrap();
+        push_stash(worktree_path.to_str().unwrap(), None, false, false, &[]).unwrap();
+
+        // Stashes are shared, so the main worktree sees it with its origin
+        let stashes = list_stashes(repo_path).unwrap();
+        assert_eq!(stashes.len(), 1);
+        assert_eq!(stashes[0].branch.as_deref(), Some("stash-branch"));
+        assert!(stashes[0]
+            .worktree_path
+            .as_deref()
+            .unwrap()
+            .contains("stash-wt"));
+
+        StdCommand::new("git")
+            .current_dir(repo_path)
+            .args([
+                "worktree",
+                "remove",
+                "--force",
+                worktree_path.to_str().unwrap(),
+            ])
+            .output()
+            .unwrap();
+    }
+
+    #[test]
+    fn test_stash_invalid_index() {
+        let temp_dir = create_test_repo();
+        let repo_path = temp_dir.path().to_str().unwrap();
+
+        assert!(apply_stash(repo_path, 0, false).is_err());
+        assert!(drop_stash(repo_path, 3).is_err());
+        assert!(show_stash(repo_path, 0).is_err());
+    }
+
+    #[test]
+    fn test_relocate_stash_worktree() {
+        let temp_dir = create_test_repo();
+        let repo_path = temp_dir.path().to_str().unwrap();
+
+        fs::write(temp_dir.path().join("README.md"), "# Changed").unwrap();
+        let stash = push_stash(repo_path, None, false, false, &[])
+            .unwrap()
+            .unwrap();
+        let old_path = stash.worktree_path.unwrap();
+
+        let repo = Repository::open(repo_path).unwrap();
+        relocate_stash_worktree(&repo, Path::new(&old_path), Path::new("/moved/worktree")).unwrap();
+
+        let stashes = list_stashes(repo_path).unwrap();
+        assert_eq!(stashes[0].worktree_path.as_deref(), Some("/moved/worktree"));
+    }
+}
@@ -0,0 +1,318 @@
+use crate::commands::submodules::SubmoduleInfo;
+use crate::error::AppResult;
+use crate::git::askpass;
+use crate::git::runner::{git, Git, NETWORK_TIMEOUT};
+use git2::{Repository, SubmoduleIgnore, SubmoduleStatus};
+
+/// Reports the submodules of a worktree, including ones whose directories
+/// are still empty because they were never checked out there
+pub fn list_submodules(worktree_path: &str) -> AppResult<Vec<SubmoduleInfo>> {
+    let repo = Repository::open(worktree_path)?;
+
+    let mut submodules = Vec::new();
+    for submodule in repo.submodules()? {
+        let name = submodule.name().unwrap_or_default().to_string();
+        let status = repo.submodule_status(&name, SubmoduleIgnore::None)?;
+
+        submodules.push(SubmoduleInfo {
+            path: submodule.path().to_string_lossy().to_string(),
+            url: submodule.url().map(String::from),
+            recorded_oid: submodule.index_id().map(|oid| oid.to_string()),
+            checked_out_oid: submodule.workdir_id().map(|oid| oid.to_string()),
+            // `IN_CONFIG` comes from the config shared by all worktrees, so
+            // it cannot tell whether this worktree has the submodule
+            initialized: !status.contains(SubmoduleStatus::WD_UNINITIALIZED),
+            commit_changed: status.contains(SubmoduleStatus::WD_MODIFIED),
+            dirty: status.intersects(
+                SubmoduleStatus::WD_INDEX_MODIFIED
+                    | SubmoduleStatus::WD_WD_MODIFIED
+                    | SubmoduleStatus::WD_UNTRACKED,
+            ),
+            name,
+        });
+    }
+
+    submodules.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(submodules)
+}
+
+/// Builds `git submodule <args> -- <paths>` for a worktree; no paths means
+/// all submodules
+fn submodule_command(worktree_path: &str, args: &[&str], paths: &[String]) -> AppResult<Git> {
+    let mut cmd = git(worktree_path)?;
+    // Updating clones submodules that are not checked out yet, which may
+    // need credentials
+    cmd.arg("submodule")
+        .args(args)
+        .arg("--")
+        .args(paths)
+        .askpass()
+        .timeout(NETWORK_TIMEOUT);
+    Ok(cmd)
+}
+
+fn run_submodule_command(mut cmd: Git) -> AppResult<()> {
+    let output = cmd.output()?;
+    if !output.success() {
+        return Err(askpass::command_error(&output));
+    }
+    Ok(())
+}
+
+pub fn init_submodules(worktree_path: &str, paths: &[String]) -> AppResult<()> {
+    run_submodule_command(submodule_command(worktree_path, &["init"], paths)?)
+}
+
+fn update_command(
+    worktree_path: &str,
+    paths: &[String],
+    init: bool,
+    recursive: bool,
+) -> AppResult<Git> {
+    let mut args = vec!["update"];
+    if init {
+        args.push("--init");
+    }
+    if recursive {
+        args.push("--recursive");
+    }
+    submodule_command(worktree_path, &args, paths)
+}
+
+/// Checks out the recorded commit of each submodule in this worktree,
+/// cloning it first if needed
+pub fn update_submodules(
+    worktree_path: &str,
+    paths: &[String],
+    init: bool,
+    recursive: bool,
+) -> AppResult<()> {
+    run_submodule_command(update_command(worktree_path, paths, init, recursive)?)
+}
+
+/// Copies submodule URLs from `.gitmodules` to the repository config, after
+/// they have changed upstream
+pub fn sync_submodules(worktree_path: &str, paths: &[String], recursive: bool) -> AppResult<()> {
+    let mut args = vec!["sync"];
+    if recursive {
+        args.push("--recursive");
+    }
+    run_submodule_command(submodule_command(worktree_path, &args, paths)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::AppError;
+    use crate::git::worktree_manager::add_worktree;
+    use std::fs;
+    use std::path::Path;
+    use std::process::Command as StdCommand;
+    use tempfile::TempDir;
+
+    fn create_test_repo() -> TempDir {
+        let temp_dir = TempDir::new().unwrap();
+        let repo_path = temp_dir.path();
+
+        StdCommand::new("git")
+            .current_dir(repo_path)
+            .args(["init"])
+            .output()
+            .unwrap();
+
+        StdCommand::new("git")
+            .current_dir(repo_path)
+            .args(["config", "user.email", "test@test.com"])
+            .output()
+            .unwrap();
+
+        StdCommand::new("git")
+            .current_dir(repo_path)
+            .args(["config", "user.name", "Test User"])
+            .output()
+            .unwrap();
+
+        fs::write(repo_path.join("README.md"), "# Test Repository").unwrap();
+
+        StdCommand::new("git")
+            .current_dir(repo_path)
+            .args(["add", "."])
+            .output()
+            .unwrap();
+
+        StdCommand::new("git")
+            .current_dir(repo_path)
+            .args(["commit", "-m", "Initial commit"])
+            .output()
+            .unwrap();
+
+        temp_dir
+    }
+
+    /// Config that lets git clone submodules from local paths, which it
+    /// refuses by default. Set per command, as the test process environment
+    /// is shared by tests running in parallel.
+    const ALLOW_FILE_PROTOCOL: [(&str, &str); 3] = [
+        ("GIT_CONFIG_COUNT", "1"),
+        ("GIT_CONFIG_KEY_0", "protocol.file.allow"),
+        ("GIT_CONFIG_VALUE_0", "always"),
+    ];
+
+    /// Returns the superproject and the repository its `sub` submodule
+    /// points at
+    fn create_repo_with_submodule() -> (TempDir, TempDir) {
+        let temp_dir = create_test_repo();
+        let sub_repo = create_test_repo();
+
+        StdCommand::new("git")
+            .current_dir(temp_dir.path())
+            .envs(ALLOW_FILE_PROTOCOL)
+            .args(["submodule", "add"])
+            .arg(sub_repo.path())
+            .arg("sub")
+            .output()
+            .unwrap();
+        StdCommand::new("git")
+            .current_dir(temp_dir.path())
+            .args(["commit", "-m", "Add submodule"])
+            .output()
+            .unwrap();
+
+        (temp_dir, sub_repo)
+    }
+
+    /// Commits in a submodule clone, which has no identity configured
+    fn commit_in(dir: &Path, name: &str) {
+        fs::write(dir.join(name), name).unwrap();
+        StdCommand::new("git")
+            .current_dir(dir)
+            .args(["add", name])
+            .output()
+            .unwrap();
+        StdCommand::new("git")
+            .current_dir(dir)
+            .args([
+                "-c",
+                "user.name=Test User",
+                "-c",
+                "user.email=test@test.com",
+            ])
+            .args(["commit", "-m", name])
+            .output()
+            .unwrap();
+    }
+
+    #[test]
+    fn test_list_submodules() {
+        let (temp_dir, _sub_repo) = create_repo_with_submodule();
+        let repo_path = temp_dir.path().to_str().unwrap();
+
+        let submodules = list_submodules(repo_path).unwrap();
+        assert_eq!(submodules.len(), 1);
+        let sub = &submodules[0];
+        assert_eq!(sub.name, "sub");
+        assert_eq!(sub.path, "sub");
+        assert!(sub.initialized);
+        assert!(sub.recorded_oid.is_some());
+        assert_eq!(sub.checked_out_oid, sub.recorded_oid);
+        assert!(!sub.commit_changed);
+        assert!(!sub.dirty);
+    }
+
+    #[test]
+    fn test_list_submodules_none() {
+        let temp_dir = create_test_repo();
+        let submodules = list_submodules(temp_dir.path().to_str().unwrap()).unwrap();
+        assert!(submodules.is_empty());
+    }
+
+    #[test]
+    fn test_update_submodules_in_new_worktree() {
+        let (temp_dir, _sub_repo) = create_repo_with_submodule();
+        let repo_path = temp_dir.path().to_str().unwrap();
+        let wt_path = temp_dir
+            .path()
+            .parent()
+            .unwrap()
+            .join("submodule-update-wt");
+        add_worktree(repo_path, &wt_path, "submodule-update", true).unwrap();
+        let wt = wt_path.to_str().unwrap();
+
+        let before = list_submodules(wt).unwrap();
+        assert!(!before[0].initialized);
+        assert!(before[0].checked_out_oid.is_none());
+        assert!(!wt_path.join("sub/README.md").exists());
+
+        let mut update = update_command(wt, &[], true, false).unwrap();
+        for (key, value) in ALLOW_FILE_PROTOCOL {
+            update.env(key, value);
+        }
+        run_submodule_command(update).unwrap();
+
+        let after = list_submodules(wt).unwrap();
+        assert!(after[0].initialized);
+        assert_eq!(after[0].checked_out_oid, after[0].recorded_oid);
+        assert!(wt_path.join("sub/README.md").exists());
+
+        StdCommand::new("git")
+            .current_dir(repo_path)
+            .args(["worktree", "remove", "--force", "--force"])
+            .arg(&wt_path)
+            .output()
+            .unwrap();
+        fs::remove_dir_all(&wt_path).ok();
+    }
+
+    #[test]
+    fn test_list_submodules_dirty_and_moved() {
+        let (temp_dir, _sub_repo) = create_repo_with_submodule();
+        let repo_path = temp_dir.path().to_str().unwrap();
+        let sub_path = temp_dir.path().join("sub");
+
+        fs::write(sub_path.join("scratch.txt"), "untracked").unwrap();
+        let submodules = list_submodules(repo_path).unwrap();
+        assert!(submodules[0].dirty);
+        assert!(!submodules[0].commit_changed);
+
+        commit_in(&sub_path, "scratch.txt");
+        let submodules = list_submodules(repo_path).unwrap();
+        assert!(submodules[0].commit_changed);
+        assert_ne!(submodules[0].checked_out_oid, submodules[0].recorded_oid);
+    }
+
+    #[test]
+    fn test_sync_submodules() {
+        let (temp_dir, _sub_repo) = create_repo_with_submodule();
+        let repo_path = temp_dir.path().to_str().unwrap();
+
+        StdCommand::new("git")
+            .current_dir(repo_path)
+            .args([
+                "config",
+                "-f",
+                ".gitmodules",
+                "submodule.sub.url",
+                "/moved/sub",
+            ])
+            .output()
+            .unwrap();
+        sync_submodules(repo_path, &["sub".to_string()], false).unwrap();
+
+        let repo = Repository::open(repo_path).unwrap();
+        let url = repo
+            .config()
+            .unwrap()
+            .get_string("submodule.sub.url")
+            .unwrap();
+        assert_eq!(url, "/moved/sub");
+    }
+
+    #[test]
+    fn test_init_unknown_submodule() {
+        let (temp_dir, _sub_repo) = create_repo_with_submodule();
+        let repo_path = temp_dir.path().to_str().unwrap();
+
+        let result = init_submodules(repo_path, &["missing".to_string()]);
+        assert!(matches!(result, Err(AppError::GitCommand { .. })));
+    }
+}
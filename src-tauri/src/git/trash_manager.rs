@@ -0,0 +1,525 @@
+use crate::commands::trash::TrashEntry;
+use crate::error::{AppError, AppResult};
+use crate::git::branch_manager::{branch_worktree, unix_now, SECONDS_PER_DAY};
+use crate::git::paths::resolve_worktree_path;
+use crate::git::runner::git;
+use crate::git::sparse_manager::skip_worktree_paths;
+use crate::git::stash_manager::app_signature;
+use crate::git::worktree_manager::validate_repository;
+use git2::{BranchType, Commit, ErrorCode, IndexAddOption, Repository};
+use std::path::Path;
+
+/// Namespace holding the snapshots of force-removed worktrees.
+///
+/// Refs outside `refs/heads` and `refs/tags` are shared by all worktrees and
+/// keep the snapshot reachable, so it survives garbage collection.
+const TRASH_REF_PREFIX: &str = "refs/wtview/trash/";
+
+fn run_git(dir: impl AsRef<Path>, args: &[&str]) -> AppResult<()> {
+    git(dir)?.args(args).run()?;
+    Ok(())
+}
+
+/// Runs `git worktree add [--detach] <worktree_path> <commit>`
+fn worktree_add(
+    repo_path: &str,
+    worktree_path: &Path,
+    detach: bool,
+    commit: &str,
+) -> AppResult<()> {
+    let mut cmd = git(repo_path)?;
+    cmd.args(["worktree", "add"]);
+    if detach {
+        cmd.arg("--detach");
+    }
+    cmd.arg(worktree_path).arg(commit).run()?;
+    Ok(())
+}
+
+/// Reads the metadata recorded in a snapshot commit message
+fn trash_entry(name: &str, commit: &Commit) -> TrashEntry {
+    let message = commit.message().unwrap_or_default();
+    let field = |key: &str| {
+        message
+            .lines()
+            .find_map(|line| line.strip_prefix(key))
+            .map(String::from)
+    };
+
+    TrashEntry {
+        name: name.to_string(),
+        path: field("path: ").unwrap_or_default(),
+        branch: field("branch: "),
+        head: commit
+            .parent_id(0)
+            .map(|oid| oid.to_string())
+            .unwrap_or_default(),
+        time: commit.time().seconds(),
+    }
+}
+
+fn find_trash_commit<'r>(repo: &'r Repository, name: &str) -> AppResult<Commit<'r>> {
+    repo.find_reference(&format!("{}{}", TRASH_REF_PREFIX, name))
+        .and_then(|r| r.peel_to_commit())
+        .map_err(|_| AppError::Other(format!("Trash entry not found: {}", name)))
+}
+
+/// Turns a worktree name into a valid ref name component. git refuses
+/// spaces, control characters, `~^:?*[\`, `..`, `@{`, and components that
+/// start with `.` or end with `.lock`.
+fn ref_component(name: &str) -> String {
+    let mut component: String = name
+        .chars()
+        .map(|c| {
+            if c.is_control() || " ~^:?*[\\/".contains(c) {
+                '-'
+            } else {
+                c
+            }
+        })
+        .collect();
+    while component.contains("..") {
+        component = component.replace("..", ".");
+    }
+    component = component.replace("@{", "-{");
+    while let Some(stripped) = component.strip_suffix(".lock") {
+        component = stripped.to_string();
+    }
+    let component = component.trim_start_matches('.').trim_end_matches('.');
+    if component.is_empty() {
+        "worktree".to_string()
+    } else {
+        component.to_string()
+    }
+}
+
+/// Snapshots the uncommitted and untracked changes of a worktree under
+/// `refs/wtview/trash/<name>-<timestamp>-<short oid>` before it is removed.
+/// Characters not allowed in ref names are replaced in `<name>`; the commit
+/// message keeps the original.
+///
+/// The snapshot is a commit of the full working tree (ignored files
+/// excluded) whose parent is the worktree's HEAD, so commits only reachable
+/// from a detached HEAD are kept too. A worktree whose branch has no commits
+/// yet gets a snapshot without a parent. Returns the name of the trash entry.
+pub fn trash_worktree(worktree_path: &Path, name: &str) -> AppResult<String> {
+    let repo = Repository::open(worktree_path)?;
+    // Read HEAD itself, as an unborn branch cannot be resolved
+    let head = repo.find_reference("HEAD")?;
+    let branch = head
+        .symbolic_target()
+        .and_then(|target| target.strip_prefix("refs/heads/"))
+        .map(String::from);
+    let head_commit = match repo.head() {
+        Ok(head) => Some(head.peel_to_commit()?),
+        Err(e) if e.code() == ErrorCode::UnbornBranch => None,
+        Err(e) => return Err(e.into()),
+    };
+
+    // Stage everything in memory only; the index is never written back
+    let mut index = repo.index()?;
+    index.add_all(["*"], IndexAddOption::DEFAULT, None)?;
+    // Files left out by sparse checkout are missing, not deleted
+    let sparse = skip_worktree_paths(&repo)?;
+    index.update_all(
+        ["*"],
+        Some(&mut |path: &Path, _: &[u8]| {
+            i32::from(sparse.contains(path.as_os_str().as_encoded_bytes()))
+        }),
+    )?;
+    let tree = repo.find_tree(index.write_tree()?)?;
+
+    let mut message = format!(
+        "Trashed worktree {}\n\npath: {}\n",
+        name,
+        worktree_path.display()
+    );
+    if let Some(branch) = &branch {
+        message.push_str(&format!("branch: {}\n", branch));
+    }
+
+    let sig = app_signature(&repo)?;
+    let parents: Vec<&Commit> = head_commit.iter().collect();
+    let oid = repo.commit(None, &sig, &sig, &message, &tree, &parents)?;
+
+    // The oid tells apart snapshots taken within the same second; taking the
+    // same snapshot twice just finds it already in the trash
+    let entry = format!("{}-{}-{:.8}", ref_component(name), unix_now(), oid);
+    let refname = format!("{}{}", TRASH_REF_PREFIX, entry);
+    if repo.refname_to_id(&refname).ok() != Some(oid) {
+        repo.reference(&refname, oid, false, "wtview: trash worktree")?;
+    }
+
+    Ok(entry)
+}
+
+/// Lists trashed worktrees, newest first
+pub fn list_trash(repo_path: &str) -> AppResult<Vec<TrashEntry>> {
+    let repo = validate_repository(repo_path)?;
+
+    let mut entries = Vec::new();
+    for reference in repo.references_glob(&format!("{}*", TRASH_REF_PREFIX))? {
+        let reference = reference?;
+        let Some(name) = reference
+            .name()
+            .and_then(|n| n.strip_prefix(TRASH_REF_PREFIX))
+        else {
+            continue;
+        };
+        if let Ok(commit) = reference.peel_to_commit() {
+            entries.push(trash_entry(name, &commit));
+        }
+    }
+
+    entries.sort_by_key(|entry| std::cmp::Reverse(entry.time));
+    Ok(entries)
+}
+
+/// Restores a trashed worktree into a new worktree at `worktree_path` and
+/// removes it from the trash.
+///
+/// The original branch is checked out again if it still points at the
+/// trashed HEAD and is free; otherwise the worktree is created detached at
+/// that commit. The snapshot's changes are restored as unstaged changes, or
+/// as untracked files on an unborn branch if the worktree had no commits.
+/// A relative `worktree_path` is taken relative to the repository.
+pub fn restore_trash(
+    repo_path: &str,
+    name: &str,
+    worktree_path: impl AsRef<Path>,
+) -> AppResult<()> {
+    let repo = validate_repository(repo_path)?;
+    let worktree_path = &resolve_worktree_path(repo_path, worktree_path);
+    let commit = find_trash_commit(&repo, name)?;
+    let entry = trash_entry(name, &commit);
+
+    let mut reusable_branch = None;
+    if let Some(branch) = &entry.branch {
+        let tip = repo
+            .find_branch(branch, BranchType::Local)
+            .ok()
+            .and_then(|b| b.get().target());
+        if tip.is_some_and(|oid| oid.to_string() == entry.head)
+            && branch_worktree(&repo, branch)?.is_none()
+        {
+            reusable_branch = Some(branch.as_str());
+        }
+    }
+
+    if entry.head.is_empty() {
+        // The worktree had no commits: check out the snapshot's files on an
+        // unborn branch again, leaving them untracked
+        let branch = entry
+            .branch
+            .as_deref()
+            .filter(|branch| repo.find_branch(branch, BranchType::Local).is_err())
+            .unwrap_or(name);
+        let snapshot = commit.id().to_string();
+        worktree_add(repo_path, worktree_path, true, &snapshot)?;
+        run_git(worktree_path, &["checkout", "-q", "--orphan", branch])?;
+        run_git(worktree_path, &["reset", "-q"])?;
+    } else {
+        match reusable_branch {
+            Some(branch) => worktree_add(repo_path, worktree_path, false, branch)?,
+            None => worktree_add(repo_path, worktree_path, true, &entry.head)?,
+        }
+
+        let source = format!("--source={}", commit.id());
+        run_git(
+            worktree_path,
+            &["restore", &source, "--worktree", "--", "."],
+        )?;
+    }
+
+    repo.find_reference(&format!("{}{}", TRASH_REF_PREFIX, name))?
+        .delete()?;
+
+    Ok(())
+}
+
+/// Deletes trashed worktrees older than `retention_days`, returning their
+/// names
+pub fn purge_trash(repo_path: &str, retention_days: u32) -> AppResult<Vec<String>> {
+    let repo = validate_repository(repo_path)?;
+    let cutoff = unix_now() - i64::from(retention_days) * SECONDS_PER_DAY;
+
+    let mut purged = Vec::new();
+    for entry in list_trash(repo_path)? {
+        if entry.time > cutoff {
+            continue;
+        }
+        repo.find_reference(&format!("{}{}", TRASH_REF_PREFIX, entry.name))?
+            .delete()?;
+        purged.push(entry.name);
+    }
+
+    Ok(purged)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::worktree::RemovalConfirmation;
+    use crate::git::worktree_manager::{add_worktree, remove_worktree};
+    use std::fs;
+    use std::process::Command as StdCommand;
+    use tempfile::TempDir;
+
+    fn create_test_repo() -> TempDir {
+        let temp_dir = TempDir::new().unwrap();
+        let repo_path = temp_dir.path();
+
+        StdCommand::new("git")
+            .current_dir(repo_path)
+            .args(["init"])
+            .output()
+            .unwrap();
+
+        StdCommand::new("git")
+            .current_dir(repo_path)
+            .args(["config", "user.email", "test@test.com"])
+            .output()
+            .unwrap();
+
+        StdCommand::new("git")
+            .current_dir(repo_path)
+            .args(["config", "user.name", "Test User"])
+            .output()
+            .unwrap();
+
+        fs::write(repo_path.join("README.md"), "# Test Repository").unwrap();
+
+        StdCommand::new("git")
+            .current_dir(repo_path)
+            .args(["add", "."])
+            .output()
+            .unwrap();
+
+        StdCommand::new("git")
+            .current_dir(repo_path)
+            .args(["commit", "-m", "Initial commit"])
+            .output()
+            .unwrap();
+
+        temp_dir
+    }
+
+    #[test]
+    fn test_force_remove_moves_changes_to_trash() {
+        let temp_dir = create_test_repo();
+        let repo_path = temp_dir.path().to_str().unwrap();
+
+        let worktree_path = temp_dir.path().parent().unwrap().join("trash-remove-wt");
+        let wt_path = worktree_path.to_str().unwrap();
+        add_worktree(repo_path, wt_path, "trash-remove", true).unwrap();
+        fs::write(worktree_path.join("README.md"), "# Changed").unwrap();
+        fs::write(worktree_path.join("new.txt"), "untracked").unwrap();
+
+        let confirm = RemovalConfirmation {
+            uncommitted_changes: true,
+            ..Default::default()
+        };
+        remove_worktree(repo_path, wt_path, confirm).unwrap();
+        assert!(!worktree_path.exists());
+
+        let entries = list_trash(repo_path).unwrap();
+        assert_eq!(entries.len(), 1);
+        assert!(entries[0].name.starts_with("trash-remove-wt-"));
+        assert_eq!(entries[0].path, wt_path);
+        assert_eq!(entries[0].branch.as_deref(), Some("trash-remove"));
+
+        let repo = Repository::open(repo_path).unwrap();
+        let head = repo.head().unwrap().target().unwrap();
+        assert_eq!(entries[0].head, head.to_string());
+    }
+
+    #[test]
+    fn test_clean_remove_does_not_trash() {
+        let temp_dir = create_test_repo();
+        let repo_path = temp_dir.path().to_str().unwrap();
+
+        let worktree_path = temp_dir.path().parent().unwrap().join("trash-clean-wt");
+        let wt_path = worktree_path.to_str().unwrap();
+        add_worktree(repo_path, wt_path, "trash-clean", true).unwrap();
+
+        let confirm = RemovalConfirmation {
+            uncommitted_changes: true,
+            ..Default::default()
+        };
+        remove_worktree(repo_path, wt_path, confirm).unwrap();
+
+        assert!(list_trash(repo_path).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_restore_trash() {
+        let temp_dir = create_test_repo();
+        let repo_path = temp_dir.path().to_str().unwrap();
+
+        let worktree_path = temp_dir.path().parent().unwrap().join("trash-restore-wt");
+        let wt_path = worktree_path.to_str().unwrap();
+        add_worktree(repo_path, wt_path, "trash-restore", true).unwrap();
+        fs::write(worktree_path.join("README.md"), "# Changed").unwrap();
+        fs::write(worktree_path.join("new.txt"), "untracked").unwrap();
+
+        let name = trash_worktree(&worktree_path, "trash-restore-wt").unwrap();
+        // Trashing leaves the worktree untouched
+        assert!(worktree_path.join("new.txt").exists());
+        StdCommand::new("git")
+            .current_dir(repo_path)
+            .args(["worktree", "remove", "--force", wt_path])
+            .output()
+            .unwrap();
+
+        let restored_path = temp_dir.path().parent().unwrap().join("trash-restored-wt");
+        let restored = restored_path.to_str().unwrap();
+        restore_trash(repo_path, &name, restored).unwrap();
+
+        assert_eq!(
+            fs::read_to_string(restored_path.join("README.md")).unwrap(),
+            "# Changed"
+        );
+        assert_eq!(
+            fs::read_to_string(restored_path.join("new.txt")).unwrap(),
+            "untracked"
+        );
+        let restored_repo = Repository::open(restored).unwrap();
+        assert_eq!(
+            restored_repo.head().unwrap().shorthand(),
+            Some("trash-restore")
+        );
+        assert!(list_trash(repo_path).unwrap().is_empty());
+
+        fs::remove_dir_all(&restored_path).unwrap();
+    }
+
+    #[test]
+    fn test_restore_trash_to_relative_path() {
+        let temp_dir = create_test_repo();
+        let repo_path = temp_dir.path().to_str().unwrap();
+        fs::write(temp_dir.path().join("README.md"), "# Changed").unwrap();
+        let name = trash_worktree(temp_dir.path(), "main").unwrap();
+
+        restore_trash(repo_path, &name, "restored/wt").unwrap();
+
+        assert_eq!(
+            fs::read_to_string(temp_dir.path().join("restored/wt/README.md")).unwrap(),
+            "# Changed"
+        );
+    }
+
+    #[test]
+    fn test_restore_missing_trash_entry() {
+        let temp_dir = create_test_repo();
+        let repo_path = temp_dir.path().to_str().unwrap();
+
+        let result = restore_trash(repo_path, "missing", "/nonexistent/restore");
+        assert!(matches!(result, Err(AppError::Other(_))));
+    }
+
+    #[test]
+    fn test_purge_trash() {
+        let temp_dir = create_test_repo();
+        let repo_path = temp_dir.path().to_str().unwrap();
+        fs::write(temp_dir.path().join("README.md"), "# Changed").unwrap();
+
+        let name = trash_worktree(temp_dir.path(), "main").unwrap();
+
+        assert!(purge_trash(repo_path, 30).unwrap().is_empty());
+        assert_eq!(purge_trash(repo_path, 0).unwrap(), vec![name]);
+        assert!(list_trash(repo_path).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_trash_twice_within_a_second() {
+        let temp_dir = create_test_repo();
+        fs::write(temp_dir.path().join("README.md"), "# Changed").unwrap();
+
+        let first = trash_worktree(temp_dir.path(), "main").unwrap();
+        fs::write(temp_dir.path().join("README.md"), "# Changed again").unwrap();
+        let second = trash_worktree(temp_dir.path(), "main").unwrap();
+
+        assert_ne!(first, second);
+        let entries = list_trash(temp_dir.path().to_str().unwrap()).unwrap();
+        assert_eq!(entries.len(), 2);
+    }
+
+    #[test]
+    fn test_ref_component() {
+        assert_eq!(ref_component("my wt"), "my-wt");
+        assert_eq!(ref_component("a~b^c:d?e*f[g\\h/i"), "a-b-c-d-e-f-g-h-i");
+        assert_eq!(ref_component(".hidden..wt.lock"), "hidden.wt");
+        assert_eq!(ref_component("wt@{1}"), "wt-{1}");
+        assert_eq!(ref_component("..."), "worktree");
+    }
+
+    #[test]
+    fn test_trash_worktree_with_space_in_name() {
+        let temp_dir = create_test_repo();
+        let repo_path = temp_dir.path().to_str().unwrap();
+        let worktree_dir = TempDir::new().unwrap();
+        let worktree_path = worktree_dir.path().join("my wt");
+        let wt_path = worktree_path.to_str().unwrap();
+        add_worktree(repo_path, wt_path, "trash-space", true).unwrap();
+        fs::write(worktree_path.join("README.md"), "# Changed").unwrap();
+
+        // Named after the directory, as a hard reset does
+        let name = trash_worktree(&worktree_path, "my wt").unwrap();
+
+        assert!(name.starts_with("my-wt-"));
+        let entries = list_trash(repo_path).unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].path, wt_path);
+        let repo = Repository::open(repo_path).unwrap();
+        let commit = find_trash_commit(&repo, &name).unwrap();
+        assert!(commit
+            .message()
+            .unwrap()
+            .starts_with("Trashed worktree my wt\n"));
+    }
+
+    #[test]
+    fn test_trash_and_restore_unborn_worktree() {
+        let temp_dir = create_test_repo();
+        let repo_path = temp_dir.path().to_str().unwrap();
+        let worktree_dir = TempDir::new().unwrap();
+        let worktree_path = worktree_dir.path().join("trash-unborn-wt");
+        let wt_path = worktree_path.to_str().unwrap();
+
+        add_worktree(repo_path, wt_path, "trash-unborn", true).unwrap();
+        StdCommand::new("git")
+            .current_dir(&worktree_path)
+            .args(["checkout", "-q", "--orphan", "unborn"])
+            .output()
+            .unwrap();
+        StdCommand::new("git")
+            .current_dir(&worktree_path)
+            .args(["rm", "-rq", "--cached", "."])
+            .output()
+            .unwrap();
+        fs::write(worktree_path.join("draft.txt"), "draft").unwrap();
+
+        let confirm = RemovalConfirmation {
+            uncommitted_changes: true,
+            ..Default::default()
+        };
+        remove_worktree(repo_path, wt_path, confirm).unwrap();
+        let entries = list_trash(repo_path).unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].head, "");
+        assert_eq!(entries[0].branch.as_deref(), Some("unborn"));
+
+        restore_trash(repo_path, &entries[0].name, wt_path).unwrap();
+        assert_eq!(
+            fs::read_to_string(worktree_path.join("draft.txt")).unwrap(),
+            "draft"
+        );
+        let restored = Repository::open(&worktree_path).unwrap();
+        assert!(restored.head_detached().is_ok_and(|detached| !detached));
+        assert!(restored.head().is_err());
+        assert_eq!(
+            restored.find_reference("HEAD").unwrap().symbolic_target(),
+            Some("refs/heads/unborn")
+        );
+    }
+}
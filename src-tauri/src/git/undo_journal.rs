@@ -0,0 +1,659 @@
+use crate::commands::undo::{BranchChange, HeadState, UndoEntry, UndoResult};
+use crate::error::{AppError, AppResult};
+use crate::git::branch_manager::{checked_out_branches, unix_now};
+use crate::git::runner::git;
+use crate::git::worktree_manager::{common_dir, validate_repository};
+use git2::{BranchType, Repository};
+use std::collections::BTreeMap;
+use std::fs::{self, OpenOptions};
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime};
+
+/// Number of operations kept in the journal; older ones can no longer be undone
+const MAX_ENTRIES: usize = 50;
+
+/// How long to wait for another process to finish with the journal
+const LOCK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Age after which a lock file is taken to be left behind by a process that
+/// died while holding it
+const STALE_LOCK_AGE: Duration = Duration::from_secs(60);
+
+/// Ref state captured around an operation
+struct Snapshot {
+    head: Option<HeadState>,
+    branches: BTreeMap<String, String>,
+}
+
+/// Location of the journal, shared by all worktrees of a repository
+fn journal_path(repo: &Repository) -> PathBuf {
+    common_dir(repo).join("wtview").join("undo.json")
+}
+
+/// Held while the journal is read, changed and written back, so concurrent
+/// operations do not lose each other's entries. The journal is shared by
+/// every app instance working on the repository, so this is a lock file
+/// next to it rather than a lock in this process; it is deleted on drop.
+struct JournalLock {
+    path: PathBuf,
+}
+
+impl Drop for JournalLock {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+fn lock_journal(repo: &Repository) -> AppResult<JournalLock> {
+    let path = journal_path(repo).with_extension("json.lock");
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+
+    let deadline = Instant::now() + LOCK_TIMEOUT;
+    loop {
+        match OpenOptions::new().write(true).create_new(true).open(&path) {
+            Ok(_) => return Ok(JournalLock { path }),
+            Err(e) if e.kind() == ErrorKind::AlreadyExists => {}
+            Err(e) => return Err(e.into()),
+        }
+
+        let stale = fs::metadata(&path)
+            .and_then(|metadata| metadata.modified())
+            .ok()
+            .and_then(|modified| SystemTime::now().duration_since(modified).ok())
+            .is_some_and(|age| age > STALE_LOCK_AGE);
+        if stale {
+            let _ = fs::remove_file(&path);
+            continue;
+        }
+        if Instant::now() >= deadline {
+            return Err(AppError::Other(format!(
+                "The undo journal is in use by another process ({} exists)",
+                path.display()
+            )));
+        }
+        thread::sleep(Duration::from_millis(20));
+    }
+}
+
+/// Reads the journal. One that cannot be parsed is set aside as
+/// `undo.json.corrupt-<timestamp>` and replaced by an empty journal, so a
+/// damaged file does not disable undo for good.
+fn load(repo: &Repository) -> AppResult<Vec<UndoEntry>> {
+    let path = journal_path(repo);
+    let contents = match fs::read_to_string(&path) {
+        Ok(contents) => contents,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e.into()),
+    };
+
+    match serde_json::from_str(&contents) {
+        Ok(entries) => Ok(entries),
+        Err(_) => {
+            let backup = path.with_extension(format!("json.corrupt-{}", unix_now()));
+            fs::rename(&path, backup)?;
+            Ok(Vec::new())
+        }
+    }
+}
+
+/// Replaces the journal by writing a temporary file and renaming it over
+/// the old one, so a crash never leaves a partly written journal behind
+fn save(repo: &Repository, entries: &[UndoEntry]) -> AppResult<()> {
+    let path = journal_path(repo);
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let contents = serde_json::to_string_pretty(entries).map_err(std::io::Error::from)?;
+    let temp = path.with_extension(format!("json.tmp-{}", std::process::id()));
+    fs::write(&temp, contents)?;
+    fs::rename(&temp, &path)?;
+    Ok(())
+}
+
+fn run_git(dir: &Path, args: &[&str]) -> AppResult<()> {
+    git(dir)?.args(args).run()?;
+    Ok(())
+}
+
+fn local_branches(repo: &Repository) -> AppResult<BTreeMap<String, String>> {
+    let mut branches = BTreeMap::new();
+    for branch in repo.branches(Some(BranchType::Local))? {
+        let (branch, _) = branch?;
+        if let (Some(name), Some(oid)) = (branch.name()?, branch.get().target()) {
+            branches.insert(name.to_string(), oid.to_string());
+        }
+    }
+    Ok(branches)
+}
+
+fn head_state(worktree_path: &Path) -> AppResult<HeadState> {
+    let repo = Repository::open(worktree_path)?;
+    let head = repo.find_reference("HEAD")?;
+
+    Ok(match head.symbolic_target() {
+        Some(target) => HeadState {
+            branch: Some(target.trim_start_matches("refs/heads/").to_string()),
+            oid: head
+                .resolve()
+                .ok()
+                .and_then(|r| r.target())
+                .map(|o| o.to_string()),
+        },
+        None => HeadState {
+            branch: None,
+            oid: head.target().map(|o| o.to_string()),
+        },
+    })
+}
+
+fn snapshot(repo_path: &str, worktree: Option<&Path>) -> AppResult<Snapshot> {
+    let repo = Repository::open(repo_path)?;
+    Ok(Snapshot {
+        head: worktree.map(head_state).transpose()?,
+        branches: local_branches(&repo)?,
+    })
+}
+
+fn branch_changes(before: &Snapshot, after: &Snapshot) -> Vec<BranchChange> {
+    let mut names: Vec<&String> = before
+        .branches
+        .keys()
+        .chain(after.branches.keys())
+        .collect();
+    names.sort();
+    names.dedup();
+
+    names
+        .into_iter()
+        .filter(|name| before.branches.get(*name) != after.branches.get(*name))
+        .map(|name| BranchChange {
+            name: name.clone(),
+            before: before.branches.get(name).cloned(),
+            after: after.branches.get(name).cloned(),
+        })
+        .collect()
+}
+
+/// Runs a mutating operation and, if it succeeds and changed any local
+/// branch or the worktree's HEAD, records the states before and after it in
+/// the undo journal of the repository at `repo_path`.
+///
+/// Failing to write the journal never fails the operation itself.
+pub fn journal<T>(
+    repo_path: &str,
+    worktree: Option<&Path>,
+    operation: &str,
+    run: impl FnOnce() -> AppResult<T>,
+) -> AppResult<T> {
+    let before = snapshot(repo_path, worktree).ok();
+    let result = run()?;
+
+    if let (Some(before), Ok(after)) = (before, snapshot(repo_path, worktree)) {
+        let branches = branch_changes(&before, &after);
+        if !branches.is_empty() || before.head != after.head {
+            let entry = UndoEntry {
+                operation: operation.to_string(),
+                time: unix_now(),
+                worktree: worktree.map(|p| p.display().to_string()),
+                head_before: before.head,
+                head_after: after.head,
+                branches,
+            };
+            let _ = Repository::open(repo_path)
+                .map_err(AppError::from)
+                .and_then(|repo| {
+                    let _lock = lock_journal(&repo)?;
+                    let mut entries = load(&repo)?;
+                    entries.push(entry);
+                    let excess = entries.len().saturating_sub(MAX_ENTRIES);
+                    entries.drain(..excess);
+                    save(&repo, &entries)
+                });
+        }
+    }
+
+    Ok(result)
+}
+
+/// Returns the operation `undo_last` would undo
+pub fn last_entry(repo_path: &str) -> AppResult<Option<UndoEntry>> {
+    let repo = validate_repository(repo_path)?;
+    let _lock = lock_journal(&repo)?;
+    Ok(load(&repo)?.pop())
+}
+
+/// Undoing a commit or soft reset keeps the index as it is, and undoing a
+/// mixed reset only resets the index; undoing anything else keeps only the
+/// changes that were uncommitted before it
+fn reset_mode(operation: &str) -> &'static str {
+    match operation {
+        "git_commit" | "git_reset_soft" => "--soft",
+        "git_reset_mixed" => "--mixed",
+        _ => "--keep",
+    }
+}
+
+/// Points the branch of `change` at `target`, deleting it for `None`
+fn set_branch(repo_path: &Path, change: &BranchChange, target: Option<&String>) -> AppResult<()> {
+    let refname = format!("refs/heads/{}", change.name);
+    match target {
+        Some(oid) => run_git(repo_path, &["update-ref", &refname, oid]),
+        None => run_git(repo_path, &["update-ref", "-d", &refname]),
+    }
+}
+
+fn restore_branch(repo_path: &Path, change: &BranchChange) -> AppResult<()> {
+    set_branch(repo_path, change, change.before.as_ref())
+}
+
+/// Restores the worktree HEAD and local branches recorded before the most
+/// recent journaled operation, then removes it from the journal.
+///
+/// Refuses if any of them has changed since, so later work is never thrown
+/// away. Branches checked out in a worktree other than the operation's are
+/// left alone and reported as skipped.
+pub fn undo_last(repo_path: &str) -> AppResult<UndoResult> {
+    let repo = validate_repository(repo_path)?;
+    let _lock = lock_journal(&repo)?;
+    let mut entries = load(&repo)?;
+    let entry = entries
+        .last()
+        .cloned()
+        .ok_or_else(|| AppError::Other("Nothing to undo".to_string()))?;
+    let worktree = entry.worktree.as_deref().map(Path::new);
+
+    let current = local_branches(&repo)?;
+    let mut changed: Vec<String> = entry
+        .branches
+        .iter()
+        .filter(|change| current.get(&change.name) != change.after.as_ref())
+        .map(|change| change.name.clone())
+        .collect();
+    if let (Some(worktree), Some(after)) = (worktree, &entry.head_after) {
+        if head_state(worktree).ok().as_ref() != Some(after) {
+            changed.push("HEAD".to_string());
+        }
+    }
+    if !changed.is_empty() {
+        return Err(AppError::Other(format!(
+            "Cannot undo {}: {} changed since",
+            entry.operation,
+            changed.join(", ")
+        )));
+    }
+
+    let repo_dir = Path::new(repo_path);
+    let mut restored_branches = Vec::new();
+    let mut head_restored = false;
+
+    if let (Some(worktree), Some(before), Some(after)) =
+        (worktree, &entry.head_before, &entry.head_after)
+    {
+        if before != after {
+            match (&before.branch, &after.branch) {
+                (Some(branch), Some(current)) if branch == current => {
+                    match &before.oid {
+                        Some(oid) => {
+                            run_git(worktree, &["reset", reset_mode(&entry.operation), oid])?
+                        }
+                        None => run_git(worktree, &["update-ref", "-d", "HEAD"])?,
+                    }
+                    restored_branches.push(branch.clone());
+                }
+                (Some(branch), _) => {
+                    let change = entry.branches.iter().find(|c| &c.name == branch);
+                    if let Some(change) = change {
+                        restore_branch(repo_dir, change)?;
+                        restored_branches.push(branch.clone());
+                    }
+                    if let Err(e) = run_git(worktree, &["checkout", branch]) {
+                        // Leave the branch where the operation left it, so
+                        // the entry can still be undone once checkout works
+                        if let Some(change) = change {
+                            set_branch(repo_dir, change, change.after.as_ref())?;
+                        }
+                        return Err(e);
+                    }
+                }
+                (None, _) => {
+                    if let Some(oid) = &before.oid {
+                        run_git(worktree, &["checkout", "--detach", oid])?;
+                    }
+                }
+            }
+            head_restored = true;
+        }
+    }
+
+    let checked_out: Vec<String> = checked_out_branches(&repo)?
+        .into_iter()
+        .map(|(branch, _)| branch)
+        .collect();
+    let mut skipped_branches = Vec::new();
+    for change in &entry.branches {
+        if restored_branches.contains(&change.name) {
+            continue;
+        }
+        if checked_out.contains(&change.name) {
+            skipped_branches.push(change.name.clone());
+            continue;
+        }
+        restore_branch(repo_dir, change)?;
+        restored_branches.push(change.name.clone());
+    }
+
+    entries.pop();
+    save(&repo, &entries)?;
+
+    Ok(UndoResult {
+        operation: entry.operation,
+        head_restored,
+        restored_branches,
+        skipped_branches,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::branches::LocalChangesMode;
+    use crate::git::branch_manager::{create_branch, delete_branch};
+    use crate::git::operations::{checkout, commit, stage, status};
+    use std::fs;
+    use std::process::Command as StdCommand;
+    use tempfile::TempDir;
+
+    fn create_test_repo() -> TempDir {
+        let temp_dir = TempDir::new().unwrap();
+        let repo_path = temp_dir.path();
+
+        StdCommand::new("git")
+            .current_dir(repo_path)
+            .args(["init"])
+            .output()
+            .unwrap();
+
+        StdCommand::new("git")
+            .current_dir(repo_path)
+            .args(["config", "user.email", "test@test.com"])
+            .output()
+            .unwrap();
+
+        StdCommand::new("git")
+            .current_dir(repo_path)
+            .args(["config", "user.name", "Test User"])
+            .output()
+            .unwrap();
+
+        fs::write(repo_path.join("README.md"), "# Test Repository").unwrap();
+
+        StdCommand::new("git")
+            .current_dir(repo_path)
+            .args(["add", "."])
+            .output()
+            .unwrap();
+
+        StdCommand::new("git")
+            .current_dir(repo_path)
+            .args(["commit", "-m", "Initial commit"])
+            .output()
+            .unwrap();
+
+        temp_dir
+    }
+
+    fn current_head(repo_path: &str) -> HeadState {
+        head_state(Path::new(repo_path)).unwrap()
+    }
+
+    #[test]
+    fn test_undo_commit_keeps_changes_staged() {
+        let temp_dir = create_test_repo();
+        let repo_path = temp_dir.path().to_str().unwrap();
+        let before = current_head(repo_path);
+
+        fs::write(temp_dir.path().join("new.txt"), "content").unwrap();
+        stage(repo_path, "new.txt").unwrap();
+        journal(repo_path, Some(temp_dir.path()), "git_commit", || {
+            commit(repo_path, "Add file")
+        })
+        .unwrap();
+        assert_ne!(current_head(repo_path), before);
+
+        let result = undo_last(repo_path).unwrap();
+        assert_eq!(result.operation, "git_commit");
+        assert!(result.head_restored);
+        assert_eq!(current_head(repo_path), before);
+
+        let files = status(repo_path).unwrap().files;
+        assert!(files.iter().any(|f| f.path == "new.txt" && f.staged));
+        assert!(last_entry(repo_path).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_undo_checkout() {
+        let temp_dir = create_test_repo();
+        let repo_path = temp_dir.path().to_str().unwrap();
+        create_branch(repo_path, "feature", None).unwrap();
+        let before = current_head(repo_path);
+
+        journal(repo_path, Some(temp_dir.path()), "checkout_branch", || {
+            checkout(repo_path, "feature", LocalChangesMode::Refuse)
+        })
+        .unwrap();
+        assert_eq!(current_head(repo_path).branch.as_deref(), Some("feature"));
+
+        let result = undo_last(repo_path).unwrap();
+        assert!(result.head_restored);
+        assert!(result.restored_branches.is_empty());
+        assert_eq!(current_head(repo_path), before);
+    }
+
+    #[test]
+    fn test_undo_delete_branch() {
+        let temp_dir = create_test_repo();
+        let repo_path = temp_dir.path().to_str().unwrap();
+        create_branch(repo_path, "feature", None).unwrap();
+        let repo = Repository::open(repo_path).unwrap();
+        let tip = repo
+            .find_branch("feature", BranchType::Local)
+            .unwrap()
+            .get()
+            .target();
+
+        journal(repo_path, None, "delete_branch", || {
+            delete_branch(repo_path, "feature", false)
+        })
+        .unwrap();
+        assert!(repo.find_branch("feature", BranchType::Local).is_err());
+
+        let result = undo_last(repo_path).unwrap();
+        assert!(!result.head_restored);
+        assert_eq!(result.restored_branches, vec!["feature"]);
+        let restored = repo.find_branch("feature", BranchType::Local).unwrap();
+        assert_eq!(restored.get().target(), tip);
+    }
+
+    #[test]
+    fn test_undo_skips_branch_checked_out_elsewhere() {
+        let temp_dir = create_test_repo();
+        let repo_path = temp_dir.path().to_str().unwrap();
+
+        journal(repo_path, None, "create_branch", || {
+            create_branch(repo_path, "feature", None)
+        })
+        .unwrap();
+        StdCommand::new("git")
+            .current_dir(repo_path)
+            .args(["checkout", "-q", "feature"])
+            .output()
+            .unwrap();
+
+        let result = undo_last(repo_path).unwrap();
+        assert_eq!(result.skipped_branches, vec!["feature"]);
+        assert!(result.restored_branches.is_empty());
+    }
+
+    #[test]
+    fn test_undo_refuses_when_changed_since() {
+        let temp_dir = create_test_repo();
+        let repo_path = temp_dir.path().to_str().unwrap();
+
+        fs::write(temp_dir.path().join("a.txt"), "a").unwrap();
+        stage(repo_path, "a.txt").unwrap();
+        journal(repo_path, Some(temp_dir.path()), "git_commit", || {
+            commit(repo_path, "First")
+        })
+        .unwrap();
+
+        fs::write(temp_dir.path().join("b.txt"), "b").unwrap();
+        stage(repo_path, "b.txt").unwrap();
+        commit(repo_path, "Not journaled").unwrap();
+
+        let err = undo_last(repo_path).unwrap_err();
+        assert!(err.to_string().contains("changed since"));
+        assert!(last_entry(repo_path).unwrap().is_some());
+    }
+
+    #[test]
+    fn test_undo_with_empty_journal() {
+        let temp_dir = create_test_repo();
+        let repo_path = temp_dir.path().to_str().unwrap();
+
+        let err = undo_last(repo_path).unwrap_err();
+        assert_eq!(err.to_string(), "Nothing to undo");
+    }
+
+    #[test]
+    fn test_failed_operation_is_not_journaled() {
+        let temp_dir = create_test_repo();
+        let repo_path = temp_dir.path().to_str().unwrap();
+
+        let result = journal(repo_path, None, "delete_branch", || {
+            delete_branch(repo_path, "missing", false)
+        });
+        assert!(result.is_err());
+        assert!(last_entry(repo_path).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_concurrent_operations_are_all_journaled() {
+        let temp_dir = create_test_repo();
+        let repo_path = temp_dir.path().to_str().unwrap();
+
+        std::thread::scope(|scope| {
+            for i in 0..8 {
+                scope.spawn(move || {
+                    let name = format!("branch-{}", i);
+                    journal(repo_path, None, "create_branch", || {
+                        create_branch(repo_path, &name, None)
+                    })
+                    .unwrap();
+                });
+            }
+        });
+
+        let repo = Repository::open(repo_path).unwrap();
+        assert_eq!(load(&repo).unwrap().len(), 8);
+    }
+
+    #[test]
+    fn test_journal_waits_for_lock_held_elsewhere() {
+        let temp_dir = create_test_repo();
+        let repo_path = temp_dir.path().to_str().unwrap();
+        let repo = Repository::open(repo_path).unwrap();
+        let lock = journal_path(&repo).with_extension("json.lock");
+        fs::create_dir_all(lock.parent().unwrap()).unwrap();
+        // As another app instance would hold it
+        fs::write(&lock, "").unwrap();
+
+        std::thread::scope(|scope| {
+            scope.spawn(|| {
+                thread::sleep(Duration::from_millis(200));
+                fs::remove_file(&lock).unwrap();
+            });
+            journal(repo_path, None, "create_branch", || {
+                create_branch(repo_path, "feature", None)
+            })
+            .unwrap();
+        });
+
+        assert_eq!(load(&repo).unwrap().len(), 1);
+        assert!(!lock.exists());
+    }
+
+    #[test]
+    fn test_stale_journal_lock_is_broken() {
+        let temp_dir = create_test_repo();
+        let repo_path = temp_dir.path().to_str().unwrap();
+        let repo = Repository::open(repo_path).unwrap();
+        let lock = journal_path(&repo).with_extension("json.lock");
+        fs::create_dir_all(lock.parent().unwrap()).unwrap();
+        let file = fs::File::create(&lock).unwrap();
+        file.set_modified(SystemTime::now() - 2 * STALE_LOCK_AGE)
+            .unwrap();
+
+        assert!(last_entry(repo_path).unwrap().is_none());
+        assert!(!lock.exists());
+    }
+
+    #[test]
+    fn test_corrupt_journal_is_set_aside() {
+        let temp_dir = create_test_repo();
+        let repo_path = temp_dir.path().to_str().unwrap();
+        let repo = Repository::open(repo_path).unwrap();
+        let path = journal_path(&repo);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, "[{\"operation\": \"git_co").unwrap();
+
+        assert!(last_entry(repo_path).unwrap().is_none());
+        journal(repo_path, None, "create_branch", || {
+            create_branch(repo_path, "feature", None)
+        })
+        .unwrap();
+
+        assert_eq!(
+            last_entry(repo_path).unwrap().unwrap().operation,
+            "create_branch"
+        );
+        let backups = fs::read_dir(path.parent().unwrap())
+            .unwrap()
+            .filter(|entry| {
+                let name = entry.as_ref().unwrap().file_name();
+                name.to_string_lossy().starts_with("undo.json.corrupt-")
+            })
+            .count();
+        assert_eq!(backups, 1);
+    }
+
+    #[test]
+    fn test_failed_checkout_keeps_branch_and_entry() {
+        let temp_dir = create_test_repo();
+        let repo_path = temp_dir.path().to_str().unwrap();
+        let before = current_head(repo_path);
+        let original = before.branch.clone().unwrap();
+
+        journal(repo_path, Some(temp_dir.path()), "rename_branch", || {
+            run_git(temp_dir.path(), &["branch", "-m", "renamed"])
+        })
+        .unwrap();
+
+        // An index lock makes checkout fail after the branch was recreated
+        let lock = temp_dir.path().join(".git").join("index.lock");
+        fs::write(&lock, "").unwrap();
+        assert!(undo_last(repo_path).is_err());
+        let repo = Repository::open(repo_path).unwrap();
+        assert!(repo.find_branch(&original, BranchType::Local).is_err());
+        assert_eq!(current_head(repo_path).branch.as_deref(), Some("renamed"));
+        assert!(last_entry(repo_path).unwrap().is_some());
+
+        fs::remove_file(&lock).unwrap();
+        let result = undo_last(repo_path).unwrap();
+        assert!(result.head_restored);
+        assert_eq!(current_head(repo_path), before);
+    }
+}
@@ -1,8 +1,19 @@
-use crate::commands::worktree::WorktreeInfo;
+use crate::commands::worktree::{
+    RemovalConfirmation, RemovalPreflight, RepairedLink, WorktreeInfo, WorktreeLinkIssue,
+    WorktreeLinkProblem,
+};
 use crate::error::{AppError, AppResult};
-use git2::{Repository, StatusOptions};
-use std::path::Path;
-use std::process::Command;
+use crate::git::backend;
+use crate::git::executable::Feature;
+use crate::git::paths::{non_utf8_bytes, path_from_bytes, resolve_worktree_path, same_path};
+use crate::git::runner::{git, git_for};
+use crate::git::sparse_manager::skip_worktree_paths;
+use crate::git::stash_manager::{list_stashes, relocate_stash_worktree};
+use crate::git::trash_manager::trash_worktree;
+use git2::{Config, Oid, Repository, StatusOptions, Worktree, WorktreeLockStatus};
+use std::ffi::{OsStr, OsString};
+use std::fs;
+use std::path::{Path, PathBuf};
 
 /// Validates that the given path is a valid git repository
 pub fn validate_repository(repo_path: &str) -> AppResult<Repository> {
@@ -18,16 +29,36 @@ pub fn validate_repository(repo_path: &str) -> AppResult<Repository> {
         .map_err(|_| AppError::NotARepository(repo_path.to_string()))
 }
 
+/// Returns the common git directory shared by all worktrees of a repository.
+///
+/// For a linked worktree this resolves its `commondir` file; for the main
+/// worktree it is simply the repository's git directory.
+pub fn common_dir(repo: &Repository) -> PathBuf {
+    let git_dir = repo.path();
+    match fs::read_to_string(git_dir.join("commondir")) {
+        Ok(contents) => git_dir.join(contents.trim()),
+        Err(_) => git_dir.to_path_buf(),
+    }
+}
+
+/// Opens the main repository, even when given a linked worktree
+pub fn main_repository(repo: &Repository) -> AppResult<Repository> {
+    Ok(Repository::open(common_dir(repo))?)
+}
+
 /// Checks if a worktree has uncommitted changes
-pub fn has_uncommitted_changes(worktree_path: &str) -> AppResult<bool> {
-    let repo = Repository::open(worktree_path)?;
+pub fn has_uncommitted_changes(worktree_path: impl AsRef<Path>) -> AppResult<bool> {
+    let repo = Repository::open(worktree_path.as_ref())?;
 
     let mut opts = StatusOptions::new();
     opts.include_untracked(true);
     opts.recurse_untracked_dirs(false);
 
     let statuses = repo.statuses(Some(&mut opts))?;
-    Ok(!statuses.is_empty())
+    let sparse = skip_worktree_paths(&repo)?;
+    Ok(statuses.iter().any(|entry| {
+        !(sparse.contains(entry.path_bytes()) && entry.status() == git2::Status::WT_DELETED)
+    }))
 }
 
 pub fn list_worktrees(repo_path: &str) -> AppResult<Vec<WorktreeInfo>> {
@@ -44,16 +75,15 @@ pub fn list_worktrees(repo_path: &str) -> AppResult<Vec<WorktreeInfo>> {
 
     worktrees.push(WorktreeInfo {
         path: workdir.to_string_lossy().to_string(),
+        path_bytes: non_utf8_bytes(workdir),
         branch: main_branch,
         is_main: true,
         is_locked: false,
     });
 
     // Get linked worktrees
-    for name in repo.worktrees()?.iter().flatten() {
-        if let Ok(wt) = repo.find_worktree(name) {
-            let wt_path = wt.path().to_string_lossy().to_string();
-
+    for name in worktree_names(&repo) {
+        if let Some(wt) = open_worktree(&repo, &name) {
             // Try to get branch info from the worktree
             let branch = if let Ok(wt_repo) = Repository::open(wt.path()) {
                 wt_repo
@@ -65,10 +95,24 @@ pub fn list_worktrees(repo_path: &str) -> AppResult<Vec<WorktreeInfo>> {
             };
 
             worktrees.push(WorktreeInfo {
-                path: wt_path,
+                path: wt.path().to_string_lossy().to_string(),
+                path_bytes: non_utf8_bytes(wt.path()),
                 branch,
                 is_main: false,
-                is_locked: wt.is_locked().map(|s| matches!(s, git2::WorktreeLockStatus::Locked(_))).unwrap_or(false),
+                is_locked: wt
+                    .is_locked()
+                    .map(|s| matches!(s, WorktreeLockStatus::Locked(_)))
+                    .unwrap_or(false),
+            });
+        } else if let Some(wt_path) = recorded_worktree_path(&repo, &name) {
+            // Keep entries libgit2 cannot load so broken links stay visible
+            let admin_dir = common_dir(&repo).join("worktrees").join(&name);
+            worktrees.push(WorktreeInfo {
+                path: wt_path.to_string_lossy().to_string(),
+                path_bytes: non_utf8_bytes(&wt_path),
+                branch: None,
+                is_main: false,
+                is_locked: admin_dir.join("locked").exists(),
             });
         }
     }
@@ -78,126 +122,566 @@ pub fn list_worktrees(repo_path: &str) -> AppResult<Vec<WorktreeInfo>> {
 
 pub fn add_worktree(
     repo_path: &str,
-    worktree_path: &str,
+    worktree_path: impl AsRef<Path>,
     branch: &str,
     create_branch: bool,
+) -> AppResult<()> {
+    run_worktree_add(repo_path, worktree_path, branch, create_branch, false)
+}
+
+/// Adds a worktree with HEAD set but no files checked out and an empty
+/// index, so it can be configured (e.g. made sparse) before the checkout
+pub fn add_worktree_without_checkout(
+    repo_path: &str,
+    worktree_path: impl AsRef<Path>,
+    branch: &str,
+    create_branch: bool,
+) -> AppResult<()> {
+    run_worktree_add(repo_path, worktree_path, branch, create_branch, true)
+}
+
+fn run_worktree_add(
+    repo_path: &str,
+    worktree_path: impl AsRef<Path>,
+    branch: &str,
+    create_branch: bool,
+    no_checkout: bool,
 ) -> AppResult<()> {
     // Validate the repository first
     validate_repository(repo_path)?;
 
-    let mut cmd = Command::new("git");
-    cmd.current_dir(repo_path);
+    // git resolves relative paths against the repository, not our directory
+    let worktree_path = resolve_worktree_path(repo_path, worktree_path);
+
+    let mut cmd = git(repo_path)?;
     cmd.args(["worktree", "add"]);
+    if no_checkout {
+        cmd.arg("--no-checkout");
+    }
 
     if create_branch {
-        cmd.args(["-b", branch, worktree_path]);
+        cmd.args(["-b", branch]);
+        cmd.arg(&worktree_path);
     } else {
-        cmd.args([worktree_path, branch]);
+        cmd.arg(&worktree_path);
+        cmd.arg(branch);
     }
 
     let output = cmd.output()?;
 
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        let error_msg = stderr.to_string();
+    // Check for specific error conditions
+    if !output.success() && output.stderr.contains("already checked out") {
+        return Err(AppError::BranchInUse(branch.to_string()));
+    }
+    output.check()?;
+
+    Ok(())
+}
+
+/// Returns the upstream of a local branch and the commit it points at, if
+/// both are configured and present
+fn resolve_upstream(repo: &Repository, refname: &str) -> Option<(String, Oid)> {
+    let upstream_ref = repo.branch_upstream_name(refname).ok()?;
+    let upstream_ref = upstream_ref.as_str()?;
+    let oid = repo.refname_to_id(upstream_ref).ok()?;
+    let name = upstream_ref
+        .strip_prefix("refs/remotes/")
+        .unwrap_or(upstream_ref)
+        .to_string();
+    Some((name, oid))
+}
 
-        // Check for specific error conditions
-        if error_msg.contains("already checked out") {
-            return Err(AppError::BranchInUse(branch.to_string()));
+/// Counts the commits of a worktree's HEAD that would only survive in the
+/// worktree: those ahead of the upstream, or, without an upstream, those not
+/// contained in any other local or remote-tracking branch.
+fn unpushed_commits(wt_repo: &Repository) -> AppResult<(Option<String>, u32)> {
+    let Ok(head) = wt_repo.head() else {
+        return Ok((None, 0));
+    };
+    let Some(head_oid) = head.target() else {
+        return Ok((None, 0));
+    };
+
+    let upstream = head
+        .is_branch()
+        .then(|| head.name().and_then(|name| resolve_upstream(wt_repo, name)))
+        .flatten();
+    if let Some((name, upstream_oid)) = upstream {
+        let (ahead, _) = wt_repo.graph_ahead_behind(head_oid, upstream_oid)?;
+        return Ok((Some(name), ahead as u32));
+    }
+
+    let mut walk = wt_repo.revwalk()?;
+    walk.push(head_oid)?;
+    for reference in wt_repo.references()?.flatten() {
+        if !(reference.is_branch() || reference.is_remote()) || reference.name() == head.name() {
+            continue;
+        }
+        if let Some(oid) = reference.target() {
+            walk.hide(oid)?;
         }
+    }
+    Ok((None, walk.count() as u32))
+}
 
-        return Err(AppError::Command(error_msg));
+/// Size on disk of a file or directory tree, without following symlinks
+fn disk_usage(path: &Path) -> u64 {
+    let Ok(meta) = fs::symlink_metadata(path) else {
+        return 0;
+    };
+    if !meta.is_dir() {
+        return meta.len();
     }
+    fs::read_dir(path)
+        .map(|entries| entries.flatten().map(|e| disk_usage(&e.path())).sum())
+        .unwrap_or(0)
+}
 
-    Ok(())
+/// Total size of the ignored files in a worktree, such as build artifacts
+fn ignored_size(wt_repo: &Repository) -> AppResult<u64> {
+    let Some(workdir) = wt_repo.workdir() else {
+        return Ok(0);
+    };
+
+    let mut opts = StatusOptions::new();
+    opts.include_ignored(true);
+    opts.recurse_ignored_dirs(false);
+
+    let statuses = wt_repo.statuses(Some(&mut opts))?;
+    Ok(statuses
+        .iter()
+        .filter(|entry| entry.status().is_ignored())
+        .filter_map(|entry| entry.path().map(|p| disk_usage(&workdir.join(p))))
+        .sum())
 }
 
-pub fn remove_worktree(repo_path: &str, worktree_path: &str, force: bool) -> AppResult<()> {
-    // Validate the repository first
-    let repo = validate_repository(repo_path)?;
+/// Returns the trimmed lock reason of a worktree, or `None` if it is not
+/// locked. A worktree locked without a reason yields an empty string.
+fn lock_reason(wt: &Worktree) -> AppResult<Option<String>> {
+    match wt.is_locked()? {
+        WorktreeLockStatus::Locked(reason) => Ok(Some(
+            reason.map(|r| r.trim().to_string()).unwrap_or_default(),
+        )),
+        WorktreeLockStatus::Unlocked => Ok(None),
+    }
+}
 
-    // Check if the worktree exists
-    let worktree_exists = repo
-        .worktrees()?
+/// Error for a locked worktree, naming the lock reason or else its path
+fn locked_error(reason: String, worktree_path: &Path) -> AppError {
+    if reason.is_empty() {
+        AppError::WorktreeLocked(worktree_path.display().to_string())
+    } else {
+        AppError::WorktreeLocked(reason)
+    }
+}
+
+/// Reports everything that removing a linked worktree would discard or
+/// override, so the caller can ask for confirmation first.
+///
+/// Stashes are only counted: they live in the common git directory and
+/// survive the removal.
+pub fn removal_preflight(
+    repo_path: &str,
+    worktree_path: impl AsRef<Path>,
+) -> AppResult<RemovalPreflight> {
+    let worktree_path = &resolve_worktree_path(repo_path, worktree_path);
+    let repo = validate_repository(repo_path)?;
+    let wt = find_worktree_by_path(&repo, worktree_path)?;
+    let lock_reason = lock_reason(&wt)?;
+
+    let wt_repo = Repository::open(worktree_path)?;
+    let branch = wt_repo
+        .head()
+        .ok()
+        .filter(|h| h.is_branch())
+        .and_then(|h| h.shorthand().map(String::from));
+    let (upstream, unpushed_commits) = unpushed_commits(&wt_repo)?;
+
+    let stash_count = list_stashes(worktree_path)?
         .iter()
-        .flatten()
-        .any(|name| {
-            repo.find_worktree(name)
-                .map(|wt| wt.path().to_string_lossy() == worktree_path)
-                .unwrap_or(false)
-        });
+        .filter(|stash| match &stash.worktree_path {
+            Some(path) => same_path(path, worktree_path),
+            None => branch.is_some() && stash.branch == branch,
+        })
+        .count();
+
+    Ok(RemovalPreflight {
+        path: worktree_path.display().to_string(),
+        branch,
+        is_locked: lock_reason.is_some(),
+        lock_reason: lock_reason.filter(|r| !r.is_empty()),
+        has_uncommitted_changes: has_uncommitted_changes(worktree_path)?,
+        upstream,
+        unpushed_commits,
+        ignored_size_bytes: ignored_size(&wt_repo)?,
+        stash_count,
+    })
+}
 
-    if !worktree_exists {
-        return Err(AppError::WorktreeNotFound(worktree_path.to_string()));
+/// Fails with the error [`remove_worktree`] would give if a risk of
+/// removing the worktree is not accepted by `confirm`; otherwise returns the
+/// worktree's lock reason, if it is locked
+fn refuse_unconfirmed_removal(
+    wt: &Worktree,
+    worktree_path: &Path,
+    confirm: RemovalConfirmation,
+) -> AppResult<Option<String>> {
+    let lock_reason = lock_reason(wt)?;
+    if let Some(reason) = &lock_reason {
+        if !confirm.locked {
+            return Err(locked_error(reason.clone(), worktree_path));
+        }
+    }
+
+    if !confirm.uncommitted_changes && has_uncommitted_changes(worktree_path)? {
+        return Err(AppError::UncommittedChanges);
     }
 
-    // Check for uncommitted changes if not forcing
-    if !force {
-        if has_uncommitted_changes(worktree_path)? {
-            return Err(AppError::UncommittedChanges);
+    if !confirm.unpushed_commits {
+        let (_, unpushed) = unpushed_commits(&Repository::open(worktree_path)?)?;
+        if unpushed > 0 {
+            return Err(AppError::UnpushedCommits(unpushed));
         }
     }
 
-    let mut cmd = Command::new("git");
-    cmd.current_dir(repo_path);
+    Ok(lock_reason)
+}
+
+/// Checks whether [`remove_worktree`] would refuse to remove the worktree
+/// with `confirm`, without removing it
+pub fn check_removal(
+    repo_path: &str,
+    worktree_path: impl AsRef<Path>,
+    confirm: RemovalConfirmation,
+) -> AppResult<()> {
+    let worktree_path = &resolve_worktree_path(repo_path, worktree_path);
+    let repo = validate_repository(repo_path)?;
+    let wt = find_worktree_by_path(&repo, worktree_path)?;
+    refuse_unconfirmed_removal(&wt, worktree_path, confirm)?;
+    Ok(())
+}
+
+/// Removes a linked worktree.
+///
+/// Each risk found by [`removal_preflight`] (a lock, uncommitted changes or
+/// unpushed commits) refuses the removal unless `confirm` accepts it.
+/// Discarded changes are first moved to the trash, see [`trash_worktree`].
+pub fn remove_worktree(
+    repo_path: &str,
+    worktree_path: impl AsRef<Path>,
+    confirm: RemovalConfirmation,
+) -> AppResult<()> {
+    let worktree_path = &resolve_worktree_path(repo_path, worktree_path);
+    // Validate the repository first
+    let repo = validate_repository(repo_path)?;
+    let wt = find_worktree_by_path(&repo, worktree_path)?;
+    let lock_reason = refuse_unconfirmed_removal(&wt, worktree_path, confirm)?;
+
+    // Keep a recoverable snapshot of the changes about to be discarded
+    if confirm.uncommitted_changes && wt.path().exists() && has_uncommitted_changes(worktree_path)?
+    {
+        trash_worktree(worktree_path, wt.name().unwrap_or("worktree"))?;
+    }
+
+    let mut cmd = git(repo_path)?;
     cmd.args(["worktree", "remove"]);
 
-    if force {
+    if lock_reason.is_some() {
+        // git requires the force flag twice to remove a locked worktree
+        cmd.args(["--force", "--force"]);
+    } else if confirm.uncommitted_changes {
         cmd.arg("--force");
     }
 
-    cmd.arg(worktree_path);
+    cmd.arg(wt.path());
+    cmd.run()?;
 
-    let output = cmd.output()?;
+    Ok(())
+}
 
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        return Err(AppError::Command(stderr.to_string()));
-    }
+pub fn lock_worktree(
+    repo_path: &str,
+    worktree_path: impl AsRef<Path>,
+    reason: Option<&str>,
+) -> AppResult<()> {
+    let worktree_path = &resolve_worktree_path(repo_path, worktree_path);
+    let repo = validate_repository(repo_path)?;
+    let wt = find_worktree_by_path(&repo, worktree_path)?;
+
+    backend::current().lock(Path::new(repo_path), &wt, reason)?;
 
     Ok(())
 }
 
-pub fn lock_worktree(repo_path: &str, worktree_path: &str, reason: Option<&str>) -> AppResult<()> {
-    validate_repository(repo_path)?;
+pub fn unlock_worktree(repo_path: &str, worktree_path: impl AsRef<Path>) -> AppResult<()> {
+    let worktree_path = &resolve_worktree_path(repo_path, worktree_path);
+    let repo = validate_repository(repo_path)?;
+    let wt = find_worktree_by_path(&repo, worktree_path)?;
 
-    let mut cmd = Command::new("git");
-    cmd.current_dir(repo_path);
-    cmd.args(["worktree", "lock"]);
+    backend::current().unlock(Path::new(repo_path), &wt)?;
 
-    if let Some(reason) = reason {
-        cmd.args(["--reason", reason]);
-    }
+    Ok(())
+}
 
-    cmd.arg(worktree_path);
+/// Finds the linked worktree checked out at `worktree_path`, comparing
+/// normalized paths
+fn find_worktree_by_path(repo: &Repository, worktree_path: &Path) -> AppResult<Worktree> {
+    for name in worktree_names(repo) {
+        if let Some(wt) = open_worktree(repo, &name) {
+            if same_path(wt.path(), worktree_path) {
+                return Ok(wt);
+            }
+        }
+    }
+    Err(AppError::WorktreeNotFound(
+        worktree_path.display().to_string(),
+    ))
+}
 
-    let output = cmd.output()?;
+/// Points each initialized submodule of a moved worktree back at its git
+/// directory, which lives under the worktree's own git directory and is not
+/// moved along with it.
+fn reconnect_submodules(worktree_path: &Path) -> AppResult<()> {
+    let wt_repo = Repository::open(worktree_path)?;
+
+    for submodule in wt_repo.submodules()? {
+        let Some(name) = submodule.name() else {
+            continue;
+        };
+        let git_dir = wt_repo.path().join("modules").join(name);
+        let work_dir = worktree_path.join(submodule.path());
+        if !git_dir.is_dir() || !work_dir.is_dir() {
+            continue;
+        }
 
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        return Err(AppError::Command(stderr.to_string()));
+        fs::write(
+            work_dir.join(".git"),
+            format!("gitdir: {}\n", git_dir.to_string_lossy()),
+        )?;
+        let mut config = Config::open(&git_dir.join("config"))?;
+        config.set_str("core.worktree", &work_dir.to_string_lossy())?;
     }
 
     Ok(())
 }
 
-pub fn unlock_worktree(repo_path: &str, worktree_path: &str) -> AppResult<()> {
-    validate_repository(repo_path)?;
+/// Points the admin entry of a worktree moved on disk at its new location
+fn repair_worktree(repo_path: &str, worktree_path: &Path) -> AppResult<()> {
+    git_for(Feature::WorktreeRepair, repo_path)?
+        .args(["worktree", "repair"])
+        .arg(worktree_path)
+        .run()?;
+    Ok(())
+}
 
-    let mut cmd = Command::new("git");
-    cmd.current_dir(repo_path);
-    cmd.args(["worktree", "unlock", worktree_path]);
+/// Moves a worktree directory back to `source` after re-linking it at
+/// `destination` failed with `error`, so the worktree is not left broken.
+/// With `repaired`, the admin entry already points at `destination` and is
+/// repaired again.
+fn move_back(
+    repo_path: &str,
+    source: &Path,
+    destination: &Path,
+    repaired: bool,
+    error: AppError,
+) -> AppError {
+    let mut restored = fs::rename(destination, source).map_err(AppError::from);
+    if repaired {
+        restored = restored
+            .and_then(|()| repair_worktree(repo_path, source))
+            .and_then(|()| reconnect_submodules(source));
+    }
+    match restored {
+        Ok(()) => error,
+        Err(_) => AppError::Other(format!(
+            "{} (the worktree could not be moved back from {})",
+            error,
+            destination.display()
+        )),
+    }
+}
 
-    let output = cmd.output()?;
+/// Moves a linked worktree to `new_path`, keeping its local changes.
+///
+/// Locked worktrees are refused. Worktrees with submodules, which
+/// `git worktree move` rejects, are moved on disk and then re-linked with
+/// `git worktree repair`; if that fails, the directory is moved back.
+pub fn move_worktree(
+    repo_path: &str,
+    worktree_path: impl AsRef<Path>,
+    new_path: impl AsRef<Path>,
+) -> AppResult<()> {
+    let worktree_path = &resolve_worktree_path(repo_path, worktree_path);
+    let repo = validate_repository(repo_path)?;
+    let wt = find_worktree_by_path(&repo, worktree_path)?;
 
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        return Err(AppError::Command(stderr.to_string()));
+    if let Some(reason) = lock_reason(&wt)? {
+        return Err(locked_error(reason, worktree_path));
     }
 
+    let source = wt.path().to_path_buf();
+    let destination = resolve_worktree_path(repo_path, new_path);
+    if destination.exists() {
+        return Err(AppError::InvalidPath(format!(
+            "Destination already exists: {}",
+            destination.display()
+        )));
+    }
+    if !destination.parent().is_some_and(Path::is_dir) {
+        return Err(AppError::InvalidPath(format!(
+            "Destination parent directory does not exist: {}",
+            destination.display()
+        )));
+    }
+
+    let has_submodules = Repository::open(&source)
+        .and_then(|wt_repo| {
+            wt_repo
+                .submodules()
+                .map(|submodules| !submodules.is_empty())
+        })
+        .unwrap_or(false);
+
+    if has_submodules {
+        fs::rename(&source, &destination)?;
+        if let Err(error) = repair_worktree(repo_path, &destination) {
+            return Err(move_back(repo_path, &source, &destination, false, error));
+        }
+        if let Err(error) = reconnect_submodules(&destination) {
+            return Err(move_back(repo_path, &source, &destination, true, error));
+        }
+    } else {
+        git(repo_path)?
+            .args(["worktree", "move"])
+            .args([&source, &destination])
+            .run()?;
+    }
+
+    relocate_stash_worktree(&repo, &source, &destination)?;
+
     Ok(())
 }
 
+/// Names of the repository's linked worktrees.
+///
+/// Read from the administrative directories rather than through libgit2,
+/// which skips names that are not valid UTF-8.
+pub fn worktree_names(repo: &Repository) -> Vec<OsString> {
+    let Ok(entries) = fs::read_dir(common_dir(repo).join("worktrees")) else {
+        return Vec::new();
+    };
+    let mut names: Vec<OsString> = entries
+        .flatten()
+        .filter(|entry| entry.path().is_dir())
+        .map(|entry| entry.file_name())
+        .collect();
+    names.sort();
+    names
+}
+
+/// Opens a linked worktree by name, including names that are not valid
+/// UTF-8, which libgit2 can only open from the worktree itself
+pub fn open_worktree(repo: &Repository, name: &OsStr) -> Option<Worktree> {
+    match name.to_str() {
+        Some(name) => repo.find_worktree(name).ok(),
+        None => {
+            let wt_repo = Repository::open(recorded_worktree_path(repo, name)?).ok()?;
+            Worktree::open_from_repository(&wt_repo).ok()
+        }
+    }
+}
+
+/// Reads the worktree directory recorded in a linked worktree's
+/// administrative `gitdir` file, which points at `<worktree>/.git`
+fn recorded_worktree_path(repo: &Repository, name: &OsStr) -> Option<PathBuf> {
+    let gitdir_file = common_dir(repo).join("worktrees").join(name).join("gitdir");
+    let contents = fs::read(gitdir_file).ok()?;
+    let dot_git = path_from_bytes(contents.trim_ascii());
+    dot_git.parent().map(Path::to_path_buf)
+}
+
+/// Returns true if the worktree's `.git` file points at `admin_dir`
+fn backlink_is_valid(worktree_dir: &Path, admin_dir: &Path) -> bool {
+    let Ok(contents) = fs::read(worktree_dir.join(".git")) else {
+        return false;
+    };
+    let Some(target) = contents.trim_ascii().strip_prefix(b"gitdir:") else {
+        return false;
+    };
+    let target = worktree_dir.join(path_from_bytes(target.trim_ascii()));
+
+    target.exists() && same_path(target, admin_dir)
+}
+
+/// Detects linked worktrees whose links to the repository are broken in
+/// either direction, typically after a directory was moved by hand
+pub fn check_worktree_links(repo_path: &str) -> AppResult<Vec<WorktreeLinkIssue>> {
+    let repo = validate_repository(repo_path)?;
+    let mut issues = Vec::new();
+
+    for name in worktree_names(&repo) {
+        let admin_dir = common_dir(&repo).join("worktrees").join(&name);
+        let Some(wt_path) = recorded_worktree_path(&repo, &name) else {
+            continue;
+        };
+
+        let problem = if !wt_path.is_dir() {
+            Some(WorktreeLinkProblem::WorktreeMissing)
+        } else if !backlink_is_valid(&wt_path, &admin_dir) {
+            Some(WorktreeLinkProblem::BacklinkBroken)
+        } else {
+            None
+        };
+
+        if let Some(problem) = problem {
+            issues.push(WorktreeLinkIssue {
+                name: name.to_string_lossy().to_string(),
+                path: wt_path.to_string_lossy().to_string(),
+                problem,
+            });
+        }
+    }
+
+    Ok(issues)
+}
+
+/// Repairs worktree links with `git worktree repair`.
+///
+/// Run from a moved main repository it fixes every worktree's back-link;
+/// worktrees that were themselves moved must be passed in `worktree_paths`
+/// with their new location. Returns the links git reported as repaired.
+pub fn repair_worktrees(
+    repo_path: &str,
+    worktree_paths: &[String],
+) -> AppResult<Vec<RepairedLink>> {
+    validate_repository(repo_path)?;
+
+    let output = git_for(Feature::WorktreeRepair, repo_path)?
+        .args(["worktree", "repair"])
+        .args(
+            worktree_paths
+                .iter()
+                .map(|path| resolve_worktree_path(repo_path, path)),
+        )
+        .output()?
+        .check()?;
+
+    let mut repaired = Vec::new();
+    for line in output.stdout.lines().chain(output.stderr.lines()) {
+        let Some(report) = line.strip_prefix("repair: ") else {
+            continue;
+        };
+        if let Some((description, path)) = report.rsplit_once(": ") {
+            repaired.push(RepairedLink {
+                description: description.to_string(),
+                path: path.to_string(),
+            });
+        }
+    }
+
+    Ok(repaired)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -205,6 +689,14 @@ mod tests {
     use std::process::Command as StdCommand;
     use tempfile::TempDir;
 
+    /// Confirmation that only accepts discarding uncommitted changes
+    fn forced() -> RemovalConfirmation {
+        RemovalConfirmation {
+            uncommitted_changes: true,
+            ..Default::default()
+        }
+    }
+
     /// Helper to create a test git repository
     fn create_test_repo() -> TempDir {
         let temp_dir = TempDir::new().expect("Failed to create temp dir");
@@ -372,6 +864,25 @@ mod tests {
             .expect("Failed to remove worktree");
     }
 
+    #[test]
+    fn test_add_worktree_relative_to_repository() {
+        let temp_dir = create_test_repo();
+        let repo_path = temp_dir.path().join("repo");
+        fs::create_dir(&repo_path).unwrap();
+        StdCommand::new("git")
+            .current_dir(temp_dir.path())
+            .args(["clone", "-q", ".", "repo"])
+            .output()
+            .unwrap();
+        let repo_path = repo_path.to_str().unwrap();
+
+        add_worktree(repo_path, "../relative-wt", "relative", true).unwrap();
+
+        assert!(temp_dir.path().join("relative-wt").join(".git").exists());
+        remove_worktree(repo_path, "../relative-wt", RemovalConfirmation::default()).unwrap();
+        assert!(!temp_dir.path().join("relative-wt").exists());
+    }
+
     #[test]
     fn test_add_worktree_existing_branch() {
         let temp_dir = create_test_repo();
@@ -448,7 +959,11 @@ mod tests {
 
         assert!(worktree_path.exists());
 
-        let result = remove_worktree(repo_path, worktree_path.to_str().unwrap(), false);
+        let result = remove_worktree(
+            repo_path,
+            worktree_path.to_str().unwrap(),
+            RemovalConfirmation::default(),
+        );
 
         assert!(result.is_ok());
         assert!(!worktree_path.exists());
@@ -474,11 +989,15 @@ mod tests {
         fs::write(&new_file, "Uncommitted content").expect("Failed to write file");
 
         // Try to remove without force - should fail
-        let result = remove_worktree(repo_path, worktree_path.to_str().unwrap(), false);
+        let result = remove_worktree(
+            repo_path,
+            worktree_path.to_str().unwrap(),
+            RemovalConfirmation::default(),
+        );
         assert!(matches!(result, Err(AppError::UncommittedChanges)));
 
         // Remove with force - should succeed
-        remove_worktree(repo_path, worktree_path.to_str().unwrap(), true)
+        remove_worktree(repo_path, worktree_path.to_str().unwrap(), forced())
             .expect("Failed to force remove worktree");
     }
 
@@ -511,7 +1030,7 @@ mod tests {
         fs::write(&test_file, "uncommitted content").expect("Failed to write file");
 
         // Force remove should work
-        let result = remove_worktree(repo_path, worktree_path.to_str().unwrap(), true);
+        let result = remove_worktree(repo_path, worktree_path.to_str().unwrap(), forced());
         assert!(result.is_ok());
     }
 
@@ -520,10 +1039,180 @@ mod tests {
         let temp_dir = create_test_repo();
         let repo_path = temp_dir.path().to_str().unwrap();
 
-        let result = remove_worktree(repo_path, "/nonexistent/worktree", false);
+        let result = remove_worktree(
+            repo_path,
+            "/nonexistent/worktree",
+            RemovalConfirmation::default(),
+        );
         assert!(matches!(result, Err(AppError::WorktreeNotFound(_))));
     }
 
+    #[test]
+    fn test_remove_worktree_with_unnormalized_path() {
+        let temp_dir = create_test_repo();
+        let repo_path = temp_dir.path().to_str().unwrap();
+
+        let worktree_path = temp_dir.path().parent().unwrap().join("unnormalized-wt");
+        add_worktree(
+            repo_path,
+            worktree_path.to_str().unwrap(),
+            "unnormalized",
+            true,
+        )
+        .unwrap();
+
+        let dotted = temp_dir.path().join("..").join("unnormalized-wt").join(".");
+        let trailing = format!("{}/", dotted.to_str().unwrap());
+        remove_worktree(repo_path, &trailing, RemovalConfirmation::default())
+            .expect("Failed to remove worktree by unnormalized path");
+        assert!(!worktree_path.exists());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_lock_worktree_through_symlink() {
+        let temp_dir = create_test_repo();
+        let repo_path = temp_dir.path().to_str().unwrap();
+
+        let worktree_path = temp_dir.path().parent().unwrap().join("symlinked-wt");
+        let link_path = temp_dir.path().join("wt-link");
+        add_worktree(
+            repo_path,
+            worktree_path.to_str().unwrap(),
+            "symlinked",
+            true,
+        )
+        .unwrap();
+        std::os::unix::fs::symlink(&worktree_path, &link_path).unwrap();
+        let link = link_path.to_str().unwrap();
+
+        lock_worktree(repo_path, link, None).expect("Failed to lock through symlink");
+        assert!(removal_preflight(repo_path, link).unwrap().is_locked);
+        unlock_worktree(repo_path, link).expect("Failed to unlock through symlink");
+
+        remove_worktree(repo_path, link, RemovalConfirmation::default()).unwrap();
+        assert!(!worktree_path.exists());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_non_utf8_worktree_path_round_trip() {
+        use crate::git::paths::path_from_bytes;
+
+        let temp_dir = create_test_repo();
+        let repo_path = temp_dir.path().to_str().unwrap();
+
+        let parent = temp_dir.path().parent().unwrap().canonicalize().unwrap();
+        let worktree_path = parent.join(path_from_bytes(b"non-utf8-\xff-wt"));
+        add_worktree(repo_path, &worktree_path, "non-utf8", true).unwrap();
+
+        let worktrees = list_worktrees(repo_path).unwrap();
+        let listed = worktrees.iter().find(|wt| !wt.is_main).unwrap();
+        let bytes = listed.path_bytes.clone().expect("raw path bytes");
+        assert_eq!(path_from_bytes(&bytes), worktree_path);
+        assert_eq!(listed.branch.as_deref(), Some("non-utf8"));
+
+        let round_tripped = path_from_bytes(&bytes);
+        lock_worktree(repo_path, &round_tripped, None).unwrap();
+        unlock_worktree(repo_path, &round_tripped).unwrap();
+        remove_worktree(repo_path, &round_tripped, RemovalConfirmation::default()).unwrap();
+        assert!(!worktree_path.exists());
+    }
+
+    #[test]
+    fn test_remove_locked_worktree_requires_confirmation() {
+        let temp_dir = create_test_repo();
+        let repo_path = temp_dir.path().to_str().unwrap();
+
+        let worktree_path = temp_dir.path().parent().unwrap().join("remove-locked-wt");
+        let wt_path = worktree_path.to_str().unwrap();
+        add_worktree(repo_path, wt_path, "remove-locked", true).unwrap();
+        lock_worktree(repo_path, wt_path, Some("on usb drive")).unwrap();
+
+        let result = remove_worktree(repo_path, wt_path, forced());
+        assert!(matches!(result, Err(AppError::WorktreeLocked(r)) if r == "on usb drive"));
+        assert!(worktree_path.exists());
+
+        let confirm = RemovalConfirmation {
+            locked: true,
+            ..Default::default()
+        };
+        remove_worktree(repo_path, wt_path, confirm).expect("Failed to remove locked worktree");
+        assert!(!worktree_path.exists());
+    }
+
+    #[test]
+    fn test_remove_worktree_with_unpushed_commits_fails() {
+        let temp_dir = create_test_repo();
+        let repo_path = temp_dir.path().to_str().unwrap();
+
+        let worktree_path = temp_dir.path().parent().unwrap().join("remove-unpushed-wt");
+        let wt_path = worktree_path.to_str().unwrap();
+        add_worktree(repo_path, wt_path, "remove-unpushed", true).unwrap();
+
+        fs::write(worktree_path.join("work.txt"), "work").unwrap();
+        StdCommand::new("git")
+            .current_dir(&worktree_path)
+            .args(["add", "."])
+            .output()
+            .unwrap();
+        StdCommand::new("git")
+            .current_dir(&worktree_path)
+            .args(["commit", "-m", "Only here"])
+            .output()
+            .unwrap();
+
+        let result = remove_worktree(repo_path, wt_path, forced());
+        assert!(matches!(result, Err(AppError::UnpushedCommits(1))));
+
+        let confirm = RemovalConfirmation {
+            unpushed_commits: true,
+            ..Default::default()
+        };
+        remove_worktree(repo_path, wt_path, confirm).expect("Failed to remove worktree");
+        assert!(!worktree_path.exists());
+    }
+
+    #[test]
+    fn test_removal_preflight() {
+        let temp_dir = create_test_repo();
+        let repo_path = temp_dir.path().to_str().unwrap();
+
+        let worktree_path = temp_dir.path().parent().unwrap().join("preflight-wt");
+        let wt_path = worktree_path.to_str().unwrap();
+        add_worktree(repo_path, wt_path, "preflight", true).unwrap();
+
+        let preflight = removal_preflight(repo_path, wt_path).unwrap();
+        assert_eq!(preflight.branch.as_deref(), Some("preflight"));
+        assert!(!preflight.is_locked);
+        assert!(!preflight.has_uncommitted_changes);
+        assert_eq!(preflight.upstream, None);
+        assert_eq!(preflight.unpushed_commits, 0);
+        assert_eq!(preflight.ignored_size_bytes, 0);
+        assert_eq!(preflight.stash_count, 0);
+
+        fs::write(worktree_path.join(".gitignore"), "target/\n").unwrap();
+        fs::create_dir(worktree_path.join("target")).unwrap();
+        fs::write(worktree_path.join("target").join("out.bin"), [0u8; 100]).unwrap();
+        fs::write(worktree_path.join("dirty.txt"), "dirty").unwrap();
+        StdCommand::new("git")
+            .current_dir(&worktree_path)
+            .args(["stash", "push", "--include-untracked", "--", "dirty.txt"])
+            .output()
+            .unwrap();
+        lock_worktree(repo_path, wt_path, None).unwrap();
+
+        let preflight = removal_preflight(repo_path, wt_path).unwrap();
+        assert!(preflight.is_locked);
+        assert_eq!(preflight.lock_reason, None);
+        assert!(preflight.has_uncommitted_changes);
+        assert_eq!(preflight.ignored_size_bytes, 100);
+        assert_eq!(preflight.stash_count, 1);
+
+        unlock_worktree(repo_path, wt_path).unwrap();
+        remove_worktree(repo_path, wt_path, forced()).unwrap();
+    }
+
     // ==================== Uncommitted Changes Tests ====================
 
     #[test]
@@ -807,4 +1496,278 @@ mod tests {
             .output()
             .expect("Failed to remove worktree");
     }
+
+    // ==================== Move Worktree Tests ====================
+
+    #[test]
+    fn test_move_worktree() {
+        let temp_dir = create_test_repo();
+        let repo_path = temp_dir.path().to_str().unwrap();
+        let old_path = temp_dir.path().parent().unwrap().join("move-from-wt");
+        let new_path = temp_dir.path().parent().unwrap().join("move-to-wt");
+
+        add_worktree(repo_path, old_path.to_str().unwrap(), "move-branch", true)
+            .expect("Failed to add worktree");
+        fs::write(old_path.join("local.txt"), "local change").unwrap();
+
+        move_worktree(
+            repo_path,
+            old_path.to_str().unwrap(),
+            new_path.to_str().unwrap(),
+        )
+        .expect("Failed to move worktree");
+
+        assert!(!old_path.exists());
+        assert!(new_path.join("local.txt").exists());
+
+        let worktrees = list_worktrees(repo_path).expect("Failed to list worktrees");
+        let moved = worktrees.iter().find(|wt| !wt.is_main).unwrap();
+        assert_eq!(moved.path, new_path.to_str().unwrap());
+        assert_eq!(moved.branch.as_deref(), Some("move-branch"));
+
+        remove_worktree(repo_path, new_path.to_str().unwrap(), forced())
+            .expect("Failed to remove worktree");
+    }
+
+    #[test]
+    fn test_move_locked_worktree_refused() {
+        let temp_dir = create_test_repo();
+        let repo_path = temp_dir.path().to_str().unwrap();
+        let old_path = temp_dir.path().parent().unwrap().join("move-locked-wt");
+        let new_path = temp_dir.path().parent().unwrap().join("move-locked-dest");
+
+        add_worktree(repo_path, old_path.to_str().unwrap(), "move-locked", true)
+            .expect("Failed to add worktree");
+        lock_worktree(repo_path, old_path.to_str().unwrap(), Some("in use"))
+            .expect("Failed to lock worktree");
+
+        let result = move_worktree(
+            repo_path,
+            old_path.to_str().unwrap(),
+            new_path.to_str().unwrap(),
+        );
+        assert!(matches!(result, Err(AppError::WorktreeLocked(reason)) if reason == "in use"));
+        assert!(old_path.exists());
+
+        // Cleanup
+        unlock_worktree(repo_path, old_path.to_str().unwrap()).unwrap();
+        remove_worktree(repo_path, old_path.to_str().unwrap(), forced()).unwrap();
+    }
+
+    #[test]
+    fn test_move_worktree_destination_exists() {
+        let temp_dir = create_test_repo();
+        let repo_path = temp_dir.path().to_str().unwrap();
+        let old_path = temp_dir.path().parent().unwrap().join("move-exists-wt");
+
+        add_worktree(repo_path, old_path.to_str().unwrap(), "move-exists", true)
+            .expect("Failed to add worktree");
+
+        let result = move_worktree(repo_path, old_path.to_str().unwrap(), repo_path);
+        assert!(matches!(result, Err(AppError::InvalidPath(_))));
+
+        // Cleanup
+        remove_worktree(repo_path, old_path.to_str().unwrap(), forced()).unwrap();
+    }
+
+    #[test]
+    fn test_move_worktree_not_found() {
+        let temp_dir = create_test_repo();
+        let repo_path = temp_dir.path().to_str().unwrap();
+
+        let result = move_worktree(repo_path, "/nonexistent/worktree", "/nonexistent/dest");
+        assert!(matches!(result, Err(AppError::WorktreeNotFound(_))));
+    }
+
+    #[test]
+    fn test_move_worktree_with_submodule() {
+        let temp_dir = create_test_repo();
+        let repo_path = temp_dir.path().to_str().unwrap();
+        let sub_repo = create_test_repo();
+        let old_path = temp_dir.path().parent().unwrap().join("move-sub-wt");
+        let new_path = temp_dir.path().parent().unwrap().join("move-sub-dest");
+
+        StdCommand::new("git")
+            .current_dir(repo_path)
+            .args(["-c", "protocol.file.allow=always", "submodule", "add"])
+            .arg(sub_repo.path())
+            .arg("sub")
+            .output()
+            .expect("Failed to add submodule");
+        StdCommand::new("git")
+            .current_dir(repo_path)
+            .args(["commit", "-m", "Add submodule"])
+            .output()
+            .expect("Failed to commit");
+
+        add_worktree(repo_path, old_path.to_str().unwrap(), "move-sub", true)
+            .expect("Failed to add worktree");
+        StdCommand::new("git")
+            .current_dir(&old_path)
+            .args([
+                "-c",
+                "protocol.file.allow=always",
+                "submodule",
+                "update",
+                "--init",
+            ])
+            .output()
+            .expect("Failed to init submodule");
+        assert!(old_path.join("sub/README.md").exists());
+
+        move_worktree(
+            repo_path,
+            old_path.to_str().unwrap(),
+            new_path.to_str().unwrap(),
+        )
+        .expect("Failed to move worktree");
+
+        assert!(!old_path.exists());
+        let sub = Repository::open(new_path.join("sub")).expect("Submodule link broken");
+        assert_eq!(
+            Path::new(sub.workdir().unwrap()),
+            new_path.join("sub").as_path()
+        );
+        assert!(!has_uncommitted_changes(new_path.to_str().unwrap()).unwrap());
+
+        // Cleanup
+        StdCommand::new("git")
+            .current_dir(repo_path)
+            .args(["worktree", "remove", "--force", "--force"])
+            .arg(&new_path)
+            .output()
+            .expect("Failed to remove worktree");
+        let _ = fs::remove_dir_all(&new_path);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_move_worktree_with_submodule_moves_back_when_repair_fails() {
+        let temp_dir = create_test_repo();
+        let repo_path = temp_dir.path().to_str().unwrap();
+        let sub_repo = create_test_repo();
+        let worktrees_dir = TempDir::new().unwrap();
+        fs::create_dir_all(worktrees_dir.path().join("a")).unwrap();
+        fs::create_dir_all(worktrees_dir.path().join("b/c")).unwrap();
+        let old_path = worktrees_dir.path().join("a/wt");
+        let new_path = worktrees_dir.path().join("b/c/wt");
+
+        StdCommand::new("git")
+            .current_dir(repo_path)
+            .args(["-c", "protocol.file.allow=always", "submodule", "add"])
+            .arg(sub_repo.path())
+            .arg("sub")
+            .output()
+            .expect("Failed to add submodule");
+        StdCommand::new("git")
+            .current_dir(repo_path)
+            .args(["commit", "-m", "Add submodule"])
+            .output()
+            .expect("Failed to commit");
+        add_worktree(repo_path, old_path.to_str().unwrap(), "move-back", true)
+            .expect("Failed to add worktree");
+
+        // A relative symlink as `.git` only resolves from the original
+        // location, so `git worktree repair` fails at the destination
+        let gitfile = worktrees_dir.path().join("a/gitfile");
+        fs::rename(old_path.join(".git"), &gitfile).unwrap();
+        std::os::unix::fs::symlink("../gitfile", old_path.join(".git")).unwrap();
+
+        let result = move_worktree(
+            repo_path,
+            old_path.to_str().unwrap(),
+            new_path.to_str().unwrap(),
+        );
+
+        assert!(matches!(result, Err(AppError::GitCommand { .. })));
+        assert!(old_path.join("README.md").exists());
+        assert!(!new_path.exists());
+        let worktrees = list_worktrees(repo_path).unwrap();
+        assert!(worktrees.iter().any(|wt| same_path(&wt.path, &old_path)));
+    }
+
+    // ==================== Repair Worktree Tests ====================
+
+    #[test]
+    fn test_check_worktree_links_healthy() {
+        let temp_dir = create_test_repo();
+        let repo_path = temp_dir.path().to_str().unwrap();
+        let worktree_path = temp_dir.path().parent().unwrap().join("links-healthy-wt");
+
+        add_worktree(
+            repo_path,
+            worktree_path.to_str().unwrap(),
+            "links-healthy",
+            true,
+        )
+        .expect("Failed to add worktree");
+
+        let issues = check_worktree_links(repo_path).expect("Failed to check links");
+        assert!(issues.is_empty());
+
+        // Cleanup
+        remove_worktree(repo_path, worktree_path.to_str().unwrap(), forced()).unwrap();
+    }
+
+    #[test]
+    fn test_repair_moved_worktree() {
+        let temp_dir = create_test_repo();
+        let repo_path = temp_dir.path().to_str().unwrap();
+        let old_path = temp_dir.path().parent().unwrap().join("repair-old-wt");
+        let new_path = temp_dir.path().parent().unwrap().join("repair-new-wt");
+
+        add_worktree(repo_path, old_path.to_str().unwrap(), "repair-moved", true)
+            .expect("Failed to add worktree");
+        fs::rename(&old_path, &new_path).unwrap();
+
+        let issues = check_worktree_links(repo_path).expect("Failed to check links");
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].problem, WorktreeLinkProblem::WorktreeMissing);
+
+        // The entry stays listed while broken
+        let worktrees = list_worktrees(repo_path).expect("Failed to list worktrees");
+        assert_eq!(worktrees.len(), 2);
+
+        let repaired = repair_worktrees(repo_path, &[new_path.to_str().unwrap().to_string()])
+            .expect("Failed to repair");
+        assert!(!repaired.is_empty());
+
+        assert!(check_worktree_links(repo_path).unwrap().is_empty());
+        let worktrees = list_worktrees(repo_path).expect("Failed to list worktrees");
+        let linked = worktrees.iter().find(|wt| !wt.is_main).unwrap();
+        assert_eq!(linked.path, new_path.to_str().unwrap());
+        assert_eq!(linked.branch.as_deref(), Some("repair-moved"));
+
+        // Cleanup
+        remove_worktree(repo_path, new_path.to_str().unwrap(), forced()).unwrap();
+    }
+
+    #[test]
+    fn test_repair_after_main_repository_moved() {
+        let parent = TempDir::new().expect("Failed to create temp dir");
+        let temp_dir = create_test_repo();
+        let old_repo = parent.path().join("old-main");
+        let new_repo = parent.path().join("new-main");
+        let worktree_path = parent.path().join("linked-wt");
+
+        fs::rename(temp_dir.path(), &old_repo).unwrap();
+        add_worktree(
+            old_repo.to_str().unwrap(),
+            worktree_path.to_str().unwrap(),
+            "main-moved",
+            true,
+        )
+        .expect("Failed to add worktree");
+        fs::rename(&old_repo, &new_repo).unwrap();
+
+        let repo_path = new_repo.to_str().unwrap();
+        let issues = check_worktree_links(repo_path).expect("Failed to check links");
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].problem, WorktreeLinkProblem::BacklinkBroken);
+
+        let repaired = repair_worktrees(repo_path, &[]).expect("Failed to repair");
+        assert_eq!(repaired.len(), 1);
+        assert!(check_worktree_links(repo_path).unwrap().is_empty());
+        assert!(Repository::open(&worktree_path).is_ok());
+    }
 }
@@ -2,7 +2,14 @@ mod commands;
 mod error;
 mod git;
 
-use commands::{branches, git_ops, repository, worktree};
+use commands::{
+    audit, auth, backend, branches, config, executable, git_ops, rebase, reflog, remotes,
+    repository, sparse, stash, submodules, trash, undo, worktree,
+};
+
+use tauri::Emitter;
+
+pub use git::askpass::askpass_client;
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
@@ -10,7 +17,23 @@ pub fn run() {
         .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_fs::init())
         .plugin(tauri_plugin_shell::init())
+        .setup(|app| {
+            // Find git now, so a missing or old git is reported up front
+            git::executable::info();
+
+            let handle = app.handle().clone();
+            git::askpass::set_prompt_handler(move |prompt| {
+                let _ = handle.emit(auth::AUTH_PROMPT_EVENT, prompt);
+            });
+            Ok(())
+        })
         .invoke_handler(tauri::generate_handler![
+            // Git executable
+            executable::git_executable_info,
+            executable::set_git_executable,
+            // Git backend
+            backend::git_backend,
+            backend::set_git_backend,
             // Repository commands
             repository::select_repository,
             repository::open_repository,
@@ -18,7 +41,11 @@ pub fn run() {
             // Worktree commands
             worktree::list_worktrees,
             worktree::add_worktree,
+            worktree::removal_preflight,
             worktree::remove_worktree,
+            worktree::move_worktree,
+            worktree::check_worktree_links,
+            worktree::repair_worktrees,
             worktree::lock_worktree,
             worktree::unlock_worktree,
             // Git operations
@@ -29,9 +56,80 @@ pub fn run() {
             git_ops::git_commit,
             git_ops::git_stage,
             git_ops::git_unstage,
+            git_ops::git_reset,
+            git_ops::git_revert,
+            git_ops::git_revert_continue,
+            git_ops::git_revert_abort,
+            git_ops::git_cherry_pick,
+            git_ops::git_cherry_pick_continue,
+            git_ops::git_cherry_pick_abort,
             // Branch operations
             branches::list_branches,
             branches::checkout_branch,
+            branches::create_branch,
+            branches::rename_branch,
+            branches::delete_branch,
+            branches::set_branch_upstream,
+            branches::unset_branch_upstream,
+            branches::analyze_branches,
+            branches::cleanup_branches,
+            // Stash operations
+            stash::list_stashes,
+            stash::push_stash,
+            stash::apply_stash,
+            stash::pop_stash,
+            stash::drop_stash,
+            stash::show_stash,
+            // Submodule operations
+            submodules::list_submodules,
+            submodules::init_submodules,
+            submodules::update_submodules,
+            submodules::sync_submodules,
+            // Sparse checkout
+            sparse::sparse_checkout_info,
+            sparse::enable_sparse_checkout,
+            sparse::disable_sparse_checkout,
+            sparse::set_sparse_patterns,
+            sparse::add_sparse_patterns,
+            sparse::list_sparse_profiles,
+            sparse::save_sparse_profile,
+            sparse::delete_sparse_profile,
+            // Authentication
+            auth::respond_auth_prompt,
+            // Remotes
+            remotes::list_remotes,
+            remotes::add_remote,
+            remotes::rename_remote,
+            remotes::remove_remote,
+            remotes::set_remote_url,
+            remotes::set_remote_push_url,
+            remotes::list_remote_branches,
+            // Config
+            config::list_config,
+            config::get_config,
+            config::set_config,
+            config::unset_config,
+            config::worktree_config_enabled,
+            // Trash operations
+            trash::list_trash,
+            trash::restore_trash,
+            trash::purge_trash,
+            // Audit log
+            audit::query_audit_log,
+            audit::export_audit_log,
+            // Undo
+            undo::last_undo_entry,
+            undo::undo_last_operation,
+            // Reflog
+            reflog::read_reflog,
+            reflog::create_branch_from_reflog,
+            reflog::add_worktree_from_reflog,
+            // Interactive rebase
+            rebase::rebase_plan,
+            rebase::rebase_execute,
+            rebase::rebase_progress,
+            rebase::rebase_continue,
+            rebase::rebase_abort,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
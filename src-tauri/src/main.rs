@@ -2,5 +2,10 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
 fn main() {
+    // git and ssh run this executable to ask for credentials
+    if let Some(code) = wtview_lib::askpass_client() {
+        std::process::exit(code);
+    }
+
     wtview_lib::run()
 }
@@ -1,12 +1,15 @@
-import { useState } from 'react';
+import { useEffect, useState } from 'react';
 import { QueryClient, QueryClientProvider } from '@tanstack/react-query';
+import { toast } from 'sonner';
 
 import { Toaster } from '@/components/ui/sonner';
 import { Header, Sidebar, MainContent } from '@/components/layout';
+import { AuthPromptDialog } from '@/components/git';
 import { AddWorktreeDialog, DeleteWorktreeDialog } from '@/components/worktree';
 import { useAppStore } from '@/stores/appStore';
 import { useWorktrees } from '@/hooks/useWorktrees';
 import { useTheme } from '@/hooks/useTheme';
+import { useGitExecutableInfo } from '@/hooks/useGitOperations';
 
 const queryClient = new QueryClient({
   defaultOptions: {
@@ -24,8 +27,16 @@ function AppContent() {
   const { currentRepo, selectedWorktreePath } = useAppStore();
   const { data: worktrees } = useWorktrees(currentRepo?.path ?? null);
 
+  const { data: gitInfo } = useGitExecutableInfo();
+
   useTheme();
 
+  useEffect(() => {
+    if (gitInfo?.error) {
+      toast.error(gitInfo.error, { duration: Infinity });
+    }
+  }, [gitInfo?.error]);
+
   const selectedWorktree = worktrees?.find((wt) => wt.path === selectedWorktreePath) ?? null;
 
   return (
@@ -52,6 +63,7 @@ function AppContent() {
         </>
       )}
 
+      <AuthPromptDialog />
       <Toaster />
     </div>
   );
@@ -0,0 +1,96 @@
+import { describe, it, expect, vi, beforeEach } from 'vitest';
+import { act, render, screen, waitFor } from '@testing-library/react';
+import userEvent from '@testing-library/user-event';
+import { invoke } from '@tauri-apps/api/core';
+import { listen } from '@tauri-apps/api/event';
+import { AuthPromptDialog } from './AuthPromptDialog';
+import type { AuthPrompt } from '@/lib/tauri';
+
+vi.mock('@tauri-apps/api/core', () => ({
+  invoke: vi.fn(),
+}));
+
+vi.mock('@tauri-apps/api/event', () => ({
+  listen: vi.fn(),
+}));
+
+vi.mock('sonner', () => ({
+  toast: {
+    success: vi.fn(),
+    error: vi.fn(),
+  },
+}));
+
+const mockInvoke = vi.mocked(invoke);
+const mockListen = vi.mocked(listen);
+
+describe('AuthPromptDialog', () => {
+  let emit: (prompt: AuthPrompt) => void;
+
+  beforeEach(() => {
+    vi.clearAllMocks();
+    mockInvoke.mockResolvedValue(undefined);
+    mockListen.mockImplementation(async (_event, handler) => {
+      emit = (prompt) => handler({ event: 'auth-prompt', id: 0, payload: prompt as never });
+      return () => {};
+    });
+  });
+
+  async function renderWithPrompt(prompt: AuthPrompt) {
+    render(<AuthPromptDialog />);
+    await waitFor(() => expect(mockListen).toHaveBeenCalledWith('auth-prompt', expect.any(Function)));
+    act(() => emit(prompt));
+  }
+
+  it('does not render without a prompt', () => {
+    render(<AuthPromptDialog />);
+
+    expect(screen.queryByRole('dialog')).not.toBeInTheDocument();
+  });
+
+  it('shows the prompt and hides secret input', async () => {
+    await renderWithPrompt({
+      id: 1,
+      prompt: "Password for 'https://me@example.com': ",
+      secret: true,
+    });
+
+    expect(screen.getByRole('dialog')).toBeInTheDocument();
+    expect(screen.getByText(/Password for 'https:\/\/me@example.com'/)).toBeInTheDocument();
+    expect(screen.getByLabelText('Response')).toHaveAttribute('type', 'password');
+  });
+
+  it('sends the response on submit', async () => {
+    const user = userEvent.setup();
+    await renderWithPrompt({
+      id: 2,
+      prompt: "Username for 'https://example.com': ",
+      secret: false,
+    });
+
+    await user.type(screen.getByLabelText('Response'), 'me');
+    await user.click(screen.getByRole('button', { name: 'Submit' }));
+
+    expect(mockInvoke).toHaveBeenCalledWith('respond_auth_prompt', {
+      id: 2,
+      response: 'me',
+    });
+    await waitFor(() => expect(screen.queryByRole('dialog')).not.toBeInTheDocument());
+  });
+
+  it('cancels the prompt', async () => {
+    const user = userEvent.setup();
+    await renderWithPrompt({
+      id: 3,
+      prompt: 'Are you sure you want to continue connecting (yes/no/[fingerprint])? ',
+      secret: false,
+    });
+
+    await user.click(screen.getByRole('button', { name: 'Cancel' }));
+
+    expect(mockInvoke).toHaveBeenCalledWith('respond_auth_prompt', {
+      id: 3,
+      response: null,
+    });
+  });
+});
@@ -0,0 +1,83 @@
+import { useEffect, useState } from 'react';
+import { KeyRound } from 'lucide-react';
+import { toast } from 'sonner';
+
+import { Button } from '@/components/ui/button';
+import {
+  Dialog,
+  DialogContent,
+  DialogDescription,
+  DialogFooter,
+  DialogHeader,
+  DialogTitle,
+} from '@/components/ui/dialog';
+import { Input } from '@/components/ui/input';
+import { onAuthPrompt, respondAuthPrompt } from '@/lib/tauri';
+import type { AuthPrompt } from '@/lib/tauri';
+
+/** Asks the user for credentials when a fetch, pull or push needs them */
+export function AuthPromptDialog() {
+  const [prompts, setPrompts] = useState<AuthPrompt[]>([]);
+  const [response, setResponse] = useState('');
+
+  useEffect(() => {
+    const unlisten = onAuthPrompt((prompt) => setPrompts((current) => [...current, prompt]));
+    return () => {
+      unlisten.then((stop) => stop());
+    };
+  }, []);
+
+  const prompt = prompts[0];
+
+  const answer = async (value: string | null) => {
+    if (!prompt) return;
+
+    setPrompts((current) => current.slice(1));
+    setResponse('');
+    try {
+      await respondAuthPrompt(prompt.id, value);
+    } catch (error) {
+      toast.error(`Failed to answer prompt: ${error}`);
+    }
+  };
+
+  const handleSubmit = (e: React.FormEvent) => {
+    e.preventDefault();
+    answer(response);
+  };
+
+  return (
+    <Dialog open={!!prompt} onOpenChange={(open) => !open && answer(null)}>
+      <DialogContent>
+        <form onSubmit={handleSubmit}>
+          <DialogHeader>
+            <DialogTitle className="flex items-center gap-2">
+              <KeyRound className="h-5 w-5 text-primary" />
+              Authentication Required
+            </DialogTitle>
+            <DialogDescription className="whitespace-pre-wrap break-all font-mono">
+              {prompt?.prompt}
+            </DialogDescription>
+          </DialogHeader>
+
+          <div className="py-4">
+            <Input
+              aria-label="Response"
+              type={prompt?.secret ? 'password' : 'text'}
+              value={response}
+              onChange={(e) => setResponse(e.target.value)}
+              autoFocus
+            />
+          </div>
+
+          <DialogFooter>
+            <Button type="button" variant="outline" onClick={() => answer(null)}>
+              Cancel
+            </Button>
+            <Button type="submit">Submit</Button>
+          </DialogFooter>
+        </form>
+      </DialogContent>
+    </Dialog>
+  );
+}
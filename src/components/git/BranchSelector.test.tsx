@@ -5,6 +5,7 @@ import { invoke } from '@tauri-apps/api/core';
 import { QueryClient, QueryClientProvider } from '@tanstack/react-query';
 import { BranchSelector } from './BranchSelector';
 import { toast } from 'sonner';
+import { mockBranch } from '@/test/test-utils';
 import type { BranchInfo } from '@/lib/tauri';
 
 vi.mock('@tauri-apps/api/core', () => ({
@@ -42,11 +43,11 @@ describe('BranchSelector', () => {
   };
 
   const mockBranches: BranchInfo[] = [
-    { name: 'main', is_remote: false, is_current: true },
-    { name: 'develop', is_remote: false, is_current: false },
-    { name: 'feature/test', is_remote: false, is_current: false },
-    { name: 'origin/main', is_remote: true, is_current: false },
-    { name: 'origin/develop', is_remote: true, is_current: false },
+    mockBranch('main', { is_current: true }),
+    mockBranch('develop'),
+    mockBranch('feature/test'),
+    mockBranch('origin/main', { is_remote: true }),
+    mockBranch('origin/develop', { is_remote: true }),
   ];
 
   beforeEach(() => {
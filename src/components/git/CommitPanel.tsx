@@ -7,6 +7,7 @@ import { Card, CardContent, CardHeader, CardTitle } from '@/components/ui/card';
 import { Input } from '@/components/ui/input';
 import { Label } from '@/components/ui/label';
 import { useGitStage, useGitUnstage, useGitCommit } from '@/hooks/useGitOperations';
+import { pathArg } from '@/lib/tauri';
 import type { FileStatus } from '@/lib/tauri';
 
 interface CommitPanelProps {
@@ -28,17 +29,17 @@ export function CommitPanel({ worktreePath, repoPath, files }: CommitPanelProps)
   const isStaging = stageMutation.isPending || unstageMutation.isPending;
   const isCommitting = commitMutation.isPending;
 
-  const handleStage = async (filePath: string) => {
+  const handleStage = async (file: FileStatus) => {
     try {
-      await stageMutation.mutateAsync({ worktreePath, filePath });
+      await stageMutation.mutateAsync({ worktreePath, filePath: pathArg(file) });
     } catch (error) {
       toast.error(`Failed to stage file: ${error}`);
     }
   };
 
-  const handleUnstage = async (filePath: string) => {
+  const handleUnstage = async (file: FileStatus) => {
     try {
-      await unstageMutation.mutateAsync({ worktreePath, filePath });
+      await unstageMutation.mutateAsync({ worktreePath, filePath: pathArg(file) });
     } catch (error) {
       toast.error(`Failed to unstage file: ${error}`);
     }
@@ -47,7 +48,7 @@ export function CommitPanel({ worktreePath, repoPath, files }: CommitPanelProps)
   const handleStageAll = async () => {
     try {
       for (const file of unstagedFiles) {
-        await stageMutation.mutateAsync({ worktreePath, filePath: file.path });
+        await stageMutation.mutateAsync({ worktreePath, filePath: pathArg(file) });
       }
       toast.success('All files staged');
     } catch (error) {
@@ -58,7 +59,7 @@ export function CommitPanel({ worktreePath, repoPath, files }: CommitPanelProps)
   const handleUnstageAll = async () => {
     try {
       for (const file of stagedFiles) {
-        await unstageMutation.mutateAsync({ worktreePath, filePath: file.path });
+        await unstageMutation.mutateAsync({ worktreePath, filePath: pathArg(file) });
       }
       toast.success('All files unstaged');
     } catch (error) {
@@ -175,7 +176,7 @@ export function CommitPanel({ worktreePath, repoPath, files }: CommitPanelProps)
                   <Button
                     variant="ghost"
                     size="icon"
-                    onClick={() => handleUnstage(file.path)}
+                    onClick={() => handleUnstage(file)}
                     disabled={isStaging}
                     className="h-6 w-6 rounded-md opacity-0 transition-opacity group-hover:opacity-100"
                   >
@@ -224,7 +225,7 @@ export function CommitPanel({ worktreePath, repoPath, files }: CommitPanelProps)
                   <Button
                     variant="ghost"
                     size="icon"
-                    onClick={() => handleStage(file.path)}
+                    onClick={() => handleStage(file)}
                     disabled={isStaging}
                     className="h-6 w-6 rounded-md opacity-0 transition-opacity group-hover:opacity-100"
                   >
@@ -1,3 +1,4 @@
 export { RemoteActions } from './RemoteActions';
 export { CommitPanel } from './CommitPanel';
 export { BranchSelector } from './BranchSelector';
+export { AuthPromptDialog } from './AuthPromptDialog';
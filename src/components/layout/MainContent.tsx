@@ -6,6 +6,7 @@ import { RemoteActions, CommitPanel, BranchSelector } from '@/components/git';
 import { useAppStore } from '@/stores/appStore';
 import { useWorktrees, useLockWorktree, useUnlockWorktree } from '@/hooks/useWorktrees';
 import { useGitStatus } from '@/hooks/useGitOperations';
+import { pathArg } from '@/lib/tauri';
 import { toast } from 'sonner';
 
 interface MainContentProps {
@@ -57,7 +58,7 @@ export function MainContent({ onDeleteWorktree }: MainContentProps) {
     try {
       await lockMutation.mutateAsync({
         repoPath: currentRepo.path,
-        worktreePath: selectedWorktree.path,
+        worktreePath: pathArg(selectedWorktree),
       });
       toast.success('Worktree locked');
     } catch (error) {
@@ -69,7 +70,7 @@ export function MainContent({ onDeleteWorktree }: MainContentProps) {
     try {
       await unlockMutation.mutateAsync({
         repoPath: currentRepo.path,
-        worktreePath: selectedWorktree.path,
+        worktreePath: pathArg(selectedWorktree),
       });
       toast.success('Worktree unlocked');
     } catch (error) {
@@ -4,6 +4,7 @@ import userEvent from '@testing-library/user-event';
 import { invoke } from '@tauri-apps/api/core';
 import { QueryClient, QueryClientProvider } from '@tanstack/react-query';
 import { AddWorktreeDialog } from './AddWorktreeDialog';
+import { mockBranch } from '@/test/test-utils';
 import type { BranchInfo } from '@/lib/tauri';
 
 vi.mock('@tauri-apps/api/core', () => ({
@@ -32,9 +33,9 @@ function createWrapper() {
 
 describe('AddWorktreeDialog', () => {
   const mockBranches: BranchInfo[] = [
-    { name: 'main', is_remote: false, is_current: true },
-    { name: 'develop', is_remote: false, is_current: false },
-    { name: 'origin/main', is_remote: true, is_current: false },
+    mockBranch('main', { is_current: true }),
+    mockBranch('develop'),
+    mockBranch('origin/main', { is_remote: true }),
   ];
 
   beforeEach(() => {
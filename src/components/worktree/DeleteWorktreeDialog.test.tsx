@@ -293,6 +293,44 @@ describe('DeleteWorktreeDialog', () => {
     });
   });
 
+  it('confirms unpushed commits after deletion fails with unpushed commits', async () => {
+    let callCount = 0;
+    mockInvoke.mockImplementation(() => {
+      callCount++;
+      if (callCount === 1) {
+        return Promise.reject(new Error('Worktree branch has 2 unpushed commit(s)'));
+      }
+      return Promise.resolve(undefined);
+    });
+
+    render(
+      <DeleteWorktreeDialog
+        open={true}
+        onOpenChange={vi.fn()}
+        worktree={mockWorktree}
+        repoPath="/repo"
+      />,
+      { wrapper: createWrapper() }
+    );
+
+    await userEvent.click(screen.getByRole('button', { name: /^delete$/i }));
+
+    await waitFor(() => {
+      expect(screen.getByText(/commits that are not pushed/i)).toBeInTheDocument();
+    });
+
+    await userEvent.click(screen.getByRole('button', { name: /force delete/i }));
+
+    await waitFor(() => {
+      expect(mockInvoke).toHaveBeenCalledWith('remove_worktree', {
+        repoPath: '/repo',
+        worktreePath: '/path/to/worktree',
+        force: false,
+        confirm: { unpushed_commits: true },
+      });
+    });
+  });
+
   it('shows loading spinner when deleting', async () => {
     mockInvoke.mockImplementation(() => new Promise(() => {})); // Never resolves
 
@@ -13,7 +13,8 @@ import {
 } from '@/components/ui/dialog';
 import { useRemoveWorktree } from '@/hooks/useWorktrees';
 import { useAppStore } from '@/stores/appStore';
-import type { WorktreeInfo } from '@/lib/tauri';
+import { pathArg } from '@/lib/tauri';
+import type { RemovalConfirmation, WorktreeInfo } from '@/lib/tauri';
 
 interface DeleteWorktreeDialogProps {
   open: boolean;
@@ -29,11 +30,13 @@ export function DeleteWorktreeDialog({
   repoPath,
 }: DeleteWorktreeDialogProps) {
   const [force, setForce] = useState(false);
+  const [confirm, setConfirm] = useState<RemovalConfirmation | undefined>(undefined);
   const removeWorktreeMutation = useRemoveWorktree();
   const selectWorktree = useAppStore((s) => s.selectWorktree);
 
   const handleClose = () => {
     setForce(false);
+    setConfirm(undefined);
     onOpenChange(false);
   };
 
@@ -43,8 +46,9 @@ export function DeleteWorktreeDialog({
     try {
       await removeWorktreeMutation.mutateAsync({
         repoPath,
-        worktreePath: worktree.path,
+        worktreePath: pathArg(worktree),
         force,
+        confirm,
       });
       toast.success('Worktree deleted');
       selectWorktree(null);
@@ -54,6 +58,12 @@ export function DeleteWorktreeDialog({
       if (errorStr.includes('uncommitted') || errorStr.includes('untracked')) {
         toast.error('Worktree has uncommitted changes. Enable force delete to remove anyway.');
         setForce(true);
+      } else if (errorStr.includes('unpushed')) {
+        toast.error('Worktree branch has unpushed commits. Delete again to remove anyway.');
+        setConfirm({ ...confirm, unpushed_commits: true });
+      } else if (errorStr.includes('locked')) {
+        toast.error('Worktree is locked. Delete again to remove anyway.');
+        setConfirm({ ...confirm, locked: true });
       } else {
         toast.error(`Failed to delete worktree: ${error}`);
       }
@@ -95,7 +105,26 @@ export function DeleteWorktreeDialog({
               <AlertTriangle className="mt-0.5 h-4 w-4 flex-shrink-0 text-destructive" />
               <p className="text-sm text-destructive">
                 Force delete is enabled. This will remove the worktree even if it has uncommitted
-                changes. This action cannot be undone.
+                changes. The changes are kept in the trash and can be restored later.
+              </p>
+            </div>
+          )}
+
+          {confirm?.unpushed_commits && (
+            <div className="flex items-start gap-2 rounded-md border border-destructive/50 bg-destructive/10 p-3">
+              <AlertTriangle className="mt-0.5 h-4 w-4 flex-shrink-0 text-destructive" />
+              <p className="text-sm text-destructive">
+                The branch has commits that are not pushed. They stay on the branch, but the
+                worktree will be removed.
+              </p>
+            </div>
+          )}
+
+          {confirm?.locked && (
+            <div className="flex items-start gap-2 rounded-md border border-destructive/50 bg-destructive/10 p-3">
+              <AlertTriangle className="mt-0.5 h-4 w-4 flex-shrink-0 text-destructive" />
+              <p className="text-sm text-destructive">
+                This worktree is locked. Deleting it will ignore the lock.
               </p>
             </div>
           )}
@@ -107,7 +136,7 @@ export function DeleteWorktreeDialog({
           </Button>
           <Button variant="destructive" onClick={handleDelete} disabled={isDeleting}>
             {isDeleting && <Loader2 className="mr-2 h-4 w-4 animate-spin" />}
-            {force ? 'Force Delete' : 'Delete'}
+            {force || confirm ? 'Force Delete' : 'Delete'}
           </Button>
         </DialogFooter>
       </DialogContent>
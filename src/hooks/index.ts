@@ -1,6 +1,10 @@
 export { useWorktrees, useAddWorktree, useRemoveWorktree, useLockWorktree, useUnlockWorktree } from './useWorktrees';
 export { useBranches, useCheckoutBranch } from './useBranches';
 export {
+  useGitExecutableInfo,
+  useSetGitExecutable,
+  useGitBackend,
+  useSetGitBackend,
   useGitStatus,
   useGitFetch,
   useGitPull,
@@ -8,4 +12,7 @@ export {
   useGitCommit,
   useGitStage,
   useGitUnstage,
+  useGitReset,
+  useGitRevert,
+  useGitCherryPick,
 } from './useGitOperations';
@@ -2,7 +2,7 @@ import { describe, it, expect, vi, beforeEach } from 'vitest';
 import { renderHook, waitFor, act } from '@testing-library/react';
 import { invoke } from '@tauri-apps/api/core';
 import { useBranches, useCheckoutBranch } from './useBranches';
-import { createQueryWrapper } from '@/test/test-utils';
+import { createQueryWrapper, mockBranch } from '@/test/test-utils';
 import type { BranchInfo } from '@/lib/tauri';
 
 vi.mock('@tauri-apps/api/core', () => ({
@@ -19,9 +19,9 @@ describe('useBranches', () => {
   describe('useBranches hook', () => {
     it('fetches branches when repoPath is provided', async () => {
       const mockBranches: BranchInfo[] = [
-        { name: 'main', is_remote: false, is_current: true },
-        { name: 'feature', is_remote: false, is_current: false },
-        { name: 'origin/main', is_remote: true, is_current: false },
+        mockBranch('main', { is_current: true }),
+        mockBranch('feature'),
+        mockBranch('origin/main', { is_remote: true }),
       ];
       mockInvoke.mockResolvedValue(mockBranches);
 
@@ -70,8 +70,8 @@ describe('useBranches', () => {
 
     it('returns branches with correct properties', async () => {
       const mockBranches: BranchInfo[] = [
-        { name: 'main', is_remote: false, is_current: true },
-        { name: 'origin/main', is_remote: true, is_current: false },
+        mockBranch('main', { is_current: true }),
+        mockBranch('origin/main', { is_remote: true }),
       ];
       mockInvoke.mockResolvedValue(mockBranches);
 
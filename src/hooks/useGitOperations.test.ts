@@ -2,6 +2,10 @@ import { describe, it, expect, vi, beforeEach } from 'vitest';
 import { renderHook, waitFor, act } from '@testing-library/react';
 import { invoke } from '@tauri-apps/api/core';
 import {
+  useGitExecutableInfo,
+  useSetGitExecutable,
+  useGitBackend,
+  useSetGitBackend,
   useGitStatus,
   useGitFetch,
   useGitPull,
@@ -9,6 +13,9 @@ import {
   useGitCommit,
   useGitStage,
   useGitUnstage,
+  useGitReset,
+  useGitRevert,
+  useGitCherryPick,
 } from './useGitOperations';
 import { createQueryWrapper } from '@/test/test-utils';
 import type { GitStatusResult } from '@/lib/tauri';
@@ -24,6 +31,76 @@ describe('useGitOperations', () => {
     vi.clearAllMocks();
   });
 
+  describe('useGitExecutableInfo hook', () => {
+    it('reports why git cannot be used', async () => {
+      const info = {
+        path: null,
+        configured_path: null,
+        version: null,
+        minimum_version: { major: 2, minor: 20, patch: 0 },
+        capabilities: { worktree_repair: false, sparse_checkout: false, config_scopes: false },
+        error: 'Git not found: git was not found in PATH',
+      };
+      mockInvoke.mockResolvedValue(info);
+
+      const { result } = renderHook(() => useGitExecutableInfo(), {
+        wrapper: createQueryWrapper(),
+      });
+
+      await waitFor(() => expect(result.current.isSuccess).toBe(true));
+
+      expect(mockInvoke).toHaveBeenCalledWith('git_executable_info');
+      expect(result.current.data?.error).toContain('Git not found');
+    });
+  });
+
+  describe('useSetGitExecutable hook', () => {
+    it('calls set_git_executable with the path', async () => {
+      mockInvoke.mockResolvedValue({});
+
+      const { result } = renderHook(() => useSetGitExecutable(), {
+        wrapper: createQueryWrapper(),
+      });
+
+      await act(async () => {
+        await result.current.mutateAsync('/opt/git/bin/git');
+      });
+
+      expect(mockInvoke).toHaveBeenCalledWith('set_git_executable', { path: '/opt/git/bin/git' });
+    });
+  });
+
+  describe('useGitBackend hook', () => {
+    it('fetches the selected backend', async () => {
+      mockInvoke.mockResolvedValue('libgit2');
+
+      const { result } = renderHook(() => useGitBackend(), {
+        wrapper: createQueryWrapper(),
+      });
+
+      await waitFor(() => expect(result.current.isSuccess).toBe(true));
+
+      expect(mockInvoke).toHaveBeenCalledWith('git_backend');
+      expect(result.current.data).toBe('libgit2');
+    });
+  });
+
+  describe('useSetGitBackend hook', () => {
+    it('calls set_git_backend with the backend', async () => {
+      mockInvoke.mockResolvedValue(undefined);
+
+      const { result } = renderHook(() => useSetGitBackend(), {
+        wrapper: createQueryWrapper(),
+      });
+
+      await act(async () => {
+        await result.current.mutateAsync('libgit2');
+      });
+
+      expect(mockInvoke).toHaveBeenCalledWith('set_git_backend', { backend: 'libgit2' });
+    });
+  });
+
   describe('useGitStatus hook', () => {
     it('fetches status when worktreePath is provided', async () => {
       const mockStatus: GitStatusResult = {
@@ -360,4 +437,83 @@ describe('useGitOperations', () => {
       ).rejects.toThrow('File not staged');
     });
   });
+
+  describe('useGitReset hook', () => {
+    it('calls gitReset with correct parameters', async () => {
+      mockInvoke.mockResolvedValue({ previous_head: 'abc123', snapshot: null });
+
+      const { result } = renderHook(() => useGitReset(), {
+        wrapper: createQueryWrapper(),
+      });
+
+      await act(async () => {
+        await result.current.mutateAsync({
+          worktreePath: '/worktree',
+          revision: 'HEAD~1',
+          mode: 'mixed',
+          repoPath: '/repo',
+        });
+      });
+
+      expect(mockInvoke).toHaveBeenCalledWith('git_reset', {
+        worktreePath: '/worktree',
+        revision: 'HEAD~1',
+        mode: 'mixed',
+      });
+    });
+  });
+
+  describe('useGitRevert hook', () => {
+    it('calls gitRevert with correct parameters', async () => {
+      mockInvoke.mockResolvedValue({
+        completed: true,
+        status: { branch: 'main', files: [], ahead: 1, behind: 0 },
+      });
+
+      const { result } = renderHook(() => useGitRevert(), {
+        wrapper: createQueryWrapper(),
+      });
+
+      await act(async () => {
+        await result.current.mutateAsync({
+          worktreePath: '/worktree',
+          commits: ['abc123'],
+          repoPath: '/repo',
+        });
+      });
+
+      expect(mockInvoke).toHaveBeenCalledWith('git_revert', {
+        worktreePath: '/worktree',
+        commits: ['abc123'],
+      });
+    });
+  });
+
+  describe('useGitCherryPick hook', () => {
+    it('calls gitCherryPick with correct parameters', async () => {
+      mockInvoke.mockResolvedValue({
+        completed: true,
+        status: { branch: 'release', files: [], ahead: 1, behind: 0 },
+      });
+
+      const { result } = renderHook(() => useGitCherryPick(), {
+        wrapper: createQueryWrapper(),
+      });
+
+      await act(async () => {
+        await result.current.mutateAsync({
+          worktreePath: '/release',
+          commits: ['abc123', 'def456'],
+          options: { record_origin: true },
+          repoPath: '/repo',
+        });
+      });
+
+      expect(mockInvoke).toHaveBeenCalledWith('git_cherry_pick', {
+        worktreePath: '/release',
+        commits: ['abc123', 'def456'],
+        options: { record_origin: true },
+      });
+    });
+  });
 });
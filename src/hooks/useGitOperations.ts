@@ -8,8 +8,53 @@ import {
   gitCommit,
   gitStage,
   gitUnstage,
+  gitReset,
+  gitRevert,
+  gitCherryPick,
+  gitExecutableInfo,
+  setGitExecutable,
+  gitBackend,
+  setGitBackend,
 } from '@/lib/tauri';
-import type { GitStatusResult } from '@/lib/tauri';
+import type { CherryPickOptions, GitBackend, GitStatusResult, PathArg, ResetMode } from '@/lib/tauri';
+
+export function useGitExecutableInfo() {
+  return useQuery({
+    queryKey: ['git-executable'],
+    queryFn: gitExecutableInfo,
+    staleTime: Infinity,
+  });
+}
+
+export function useSetGitExecutable() {
+  const queryClient = useQueryClient();
+
+  return useMutation({
+    mutationFn: (path: string | null) => setGitExecutable(path),
+    onSuccess: (info) => {
+      queryClient.setQueryData(['git-executable'], info);
+    },
+  });
+}
+
+export function useGitBackend() {
+  return useQuery({
+    queryKey: ['git-backend'],
+    queryFn: gitBackend,
+    staleTime: Infinity,
+  });
+}
+
+export function useSetGitBackend() {
+  const queryClient = useQueryClient();
+
+  return useMutation({
+    mutationFn: (backend: GitBackend) => setGitBackend(backend),
+    onSuccess: (_, backend) => {
+      queryClient.setQueryData(['git-backend'], backend);
+    },
+  });
+}
 
 export function useGitStatus(worktreePath: string | null) {
   return useQuery({
@@ -81,7 +126,7 @@ export function useGitCommit() {
 
 interface StageParams {
   worktreePath: string;
-  filePath: string;
+  filePath: PathArg;
 }
 
 export function useGitStage() {
@@ -106,4 +151,62 @@ export function useGitUnstage() {
   });
 }
 
+interface ResetParams {
+  worktreePath: string;
+  revision: string;
+  mode: ResetMode;
+  repoPath: string;
+}
+
+export function useGitReset() {
+  const queryClient = useQueryClient();
+
+  return useMutation({
+    mutationFn: ({ worktreePath, revision, mode }: ResetParams) =>
+      gitReset(worktreePath, revision, mode),
+    onSuccess: (_, { worktreePath, repoPath }) => {
+      queryClient.invalidateQueries({ queryKey: ['status', worktreePath] });
+      queryClient.invalidateQueries({ queryKey: ['worktrees', repoPath] });
+    },
+  });
+}
+
+interface RevertParams {
+  worktreePath: string;
+  commits: string[];
+  repoPath: string;
+}
+
+export function useGitRevert() {
+  const queryClient = useQueryClient();
+
+  return useMutation({
+    mutationFn: ({ worktreePath, commits }: RevertParams) => gitRevert(worktreePath, commits),
+    onSuccess: (_, { worktreePath, repoPath }) => {
+      queryClient.invalidateQueries({ queryKey: ['status', worktreePath] });
+      queryClient.invalidateQueries({ queryKey: ['worktrees', repoPath] });
+    },
+  });
+}
+
+interface CherryPickParams {
+  worktreePath: string;
+  commits: string[];
+  options?: CherryPickOptions;
+  repoPath: string;
+}
+
+export function useGitCherryPick() {
+  const queryClient = useQueryClient();
+
+  return useMutation({
+    mutationFn: ({ worktreePath, commits, options }: CherryPickParams) =>
+      gitCherryPick(worktreePath, commits, options),
+    onSuccess: (_, { worktreePath, repoPath }) => {
+      queryClient.invalidateQueries({ queryKey: ['status', worktreePath] });
+      queryClient.invalidateQueries({ queryKey: ['worktrees', repoPath] });
+    },
+  });
+}
+
 export type { GitStatusResult };
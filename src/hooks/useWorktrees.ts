@@ -7,7 +7,7 @@ import {
   lockWorktree,
   unlockWorktree,
 } from '@/lib/tauri';
-import type { WorktreeInfo } from '@/lib/tauri';
+import type { PathArg, RemovalConfirmation, WorktreeInfo } from '@/lib/tauri';
 
 export function useWorktrees(repoPath: string | null) {
   return useQuery({
@@ -19,17 +19,24 @@ export function useWorktrees(repoPath: string | null) {
 
 interface AddWorktreeParams {
   repoPath: string;
-  worktreePath: string;
+  worktreePath: PathArg;
   branch: string;
   createBranch: boolean;
+  sparseProfile?: string;
 }
 
 export function useAddWorktree() {
   const queryClient = useQueryClient();
 
   return useMutation({
-    mutationFn: ({ repoPath, worktreePath, branch, createBranch }: AddWorktreeParams) =>
-      addWorktree(repoPath, worktreePath, branch, createBranch),
+    mutationFn: ({
+      repoPath,
+      worktreePath,
+      branch,
+      createBranch,
+      sparseProfile,
+    }: AddWorktreeParams) =>
+      addWorktree(repoPath, worktreePath, branch, createBranch, sparseProfile),
     onSuccess: (_, { repoPath }) => {
       queryClient.invalidateQueries({ queryKey: ['worktrees', repoPath] });
       queryClient.invalidateQueries({ queryKey: ['branches', repoPath] });
@@ -39,16 +46,17 @@ export function useAddWorktree() {
 
 interface RemoveWorktreeParams {
   repoPath: string;
-  worktreePath: string;
+  worktreePath: PathArg;
   force: boolean;
+  confirm?: RemovalConfirmation;
 }
 
 export function useRemoveWorktree() {
   const queryClient = useQueryClient();
 
   return useMutation({
-    mutationFn: ({ repoPath, worktreePath, force }: RemoveWorktreeParams) =>
-      removeWorktree(repoPath, worktreePath, force),
+    mutationFn: ({ repoPath, worktreePath, force, confirm }: RemoveWorktreeParams) =>
+      removeWorktree(repoPath, worktreePath, force, confirm),
     onSuccess: (_, { repoPath }) => {
       queryClient.invalidateQueries({ queryKey: ['worktrees', repoPath] });
     },
@@ -57,7 +65,7 @@ export function useRemoveWorktree() {
 
 interface LockWorktreeParams {
   repoPath: string;
-  worktreePath: string;
+  worktreePath: PathArg;
   reason?: string;
 }
 
@@ -75,7 +83,7 @@ export function useLockWorktree() {
 
 interface UnlockWorktreeParams {
   repoPath: string;
-  worktreePath: string;
+  worktreePath: PathArg;
 }
 
 export function useUnlockWorktree() {
@@ -7,6 +7,7 @@ import {
   listWorktrees,
   addWorktree,
   removeWorktree,
+  removalPreflight,
   lockWorktree,
   unlockWorktree,
   gitFetch,
@@ -16,13 +17,70 @@ import {
   gitCommit,
   gitStage,
   gitUnstage,
+  gitReset,
+  gitRevert,
+  gitRevertContinue,
+  gitRevertAbort,
+  gitCherryPick,
+  gitCherryPickContinue,
+  gitCherryPickAbort,
   listBranches,
   checkoutBranch,
+  createBranch,
+  renameBranch,
+  deleteBranch,
+  setBranchUpstream,
+  unsetBranchUpstream,
+  analyzeBranches,
+  cleanupBranches,
+  listStashes,
+  pushStash,
+  applyStash,
+  popStash,
+  dropStash,
+  showStash,
+  moveWorktree,
+  checkWorktreeLinks,
+  repairWorktrees,
+  listSubmodules,
+  initSubmodules,
+  updateSubmodules,
+  syncSubmodules,
+  listTrash,
+  restoreTrash,
+  purgeTrash,
+  queryAuditLog,
+  exportAuditLog,
+  lastUndoEntry,
+  undoLastOperation,
+  readReflog,
+  createBranchFromReflog,
+  addWorktreeFromReflog,
+  rebasePlan,
+  rebaseExecute,
+  rebaseProgress,
+  rebaseContinue,
+  rebaseAbort,
+  sparseCheckoutInfo,
+  setSparsePatterns,
+  saveSparseProfile,
+  getConfig,
+  setConfig,
+  addRemote,
+  renameRemote,
+  setRemotePushUrl,
+  respondAuthPrompt,
+  gitExecutableInfo,
+  setGitExecutable,
+  gitBackend,
+  setGitBackend,
+  pathArg,
   type WorktreeInfo,
   type GitStatusResult,
   type BranchInfo,
   type RepositoryInfo,
 } from "./tauri";
+import { mockBranch } from "@/test/test-utils";
 
 vi.mock("@tauri-apps/api/core", () => ({
   invoke: vi.fn(),
@@ -190,6 +248,20 @@ describe("Tauri API wrappers", () => {
       });
     });
 
+    it("passes a sparse profile through", async () => {
+      mockInvoke.mockResolvedValue(undefined);
+
+      await addWorktree("/repo", "/repo-feature", "feature", true, "frontend");
+
+      expect(mockInvoke).toHaveBeenCalledWith("add_worktree", {
+        repoPath: "/repo",
+        worktreePath: "/repo-feature",
+        branch: "feature",
+        createBranch: true,
+        sparseProfile: "frontend",
+      });
+    });
+
     it("calls invoke with correct parameters for existing branch", async () => {
       mockInvoke.mockResolvedValue(undefined);
 
@@ -212,6 +284,31 @@ describe("Tauri API wrappers", () => {
     });
   });
 
+  describe("removalPreflight", () => {
+    it("calls invoke with correct parameters", async () => {
+      const preflight = {
+        path: "/repo-feature",
+        branch: "feature",
+        is_locked: false,
+        lock_reason: null,
+        has_uncommitted_changes: false,
+        upstream: "origin/feature",
+        unpushed_commits: 2,
+        ignored_size_bytes: 4096,
+        stash_count: 1,
+      };
+      mockInvoke.mockResolvedValue(preflight);
+
+      const result = await removalPreflight("/repo", "/repo-feature");
+
+      expect(mockInvoke).toHaveBeenCalledWith("removal_preflight", {
+        repoPath: "/repo",
+        worktreePath: "/repo-feature",
+      });
+      expect(result).toEqual(preflight);
+    });
+  });
+
   describe("removeWorktree", () => {
     it("calls invoke with force=false", async () => {
       mockInvoke.mockResolvedValue(undefined);
@@ -237,6 +334,22 @@ describe("Tauri API wrappers", () => {
       });
     });
 
+    it("passes per-risk confirmation", async () => {
+      mockInvoke.mockResolvedValue(undefined);
+
+      await removeWorktree("/repo", "/repo-feature", false, {
+        unpushed_commits: true,
+        locked: true,
+      });
+
+      expect(mockInvoke).toHaveBeenCalledWith("remove_worktree", {
+        repoPath: "/repo",
+        worktreePath: "/repo-feature",
+        force: false,
+        confirm: { unpushed_commits: true, locked: true },
+      });
+    });
+
     it("propagates errors from invoke", async () => {
       mockInvoke.mockRejectedValue(new Error("Worktree has uncommitted changes"));
 
@@ -477,6 +590,18 @@ describe("Tauri API wrappers", () => {
       });
     });
 
+    it("passes raw bytes for non-UTF-8 paths", async () => {
+      mockInvoke.mockResolvedValue(undefined);
+      const file = { path: "caf\uFFFD.txt", path_bytes: [99, 97, 102, 233, 46, 116, 120, 116] };
+
+      await gitStage("/worktree", pathArg(file));
+
+      expect(mockInvoke).toHaveBeenCalledWith("git_stage", {
+        worktreePath: "/worktree",
+        filePath: [99, 97, 102, 233, 46, 116, 120, 116],
+      });
+    });
+
     it("propagates errors from invoke", async () => {
       mockInvoke.mockRejectedValue(new Error("File not found"));
 
@@ -503,12 +628,113 @@ describe("Tauri API wrappers", () => {
     });
   });
 
+  describe("gitReset", () => {
+    it("calls invoke with correct parameters", async () => {
+      const resetResult = { previous_head: "abc123", snapshot: "wt-reset-1700000000" };
+      mockInvoke.mockResolvedValue(resetResult);
+
+      const result = await gitReset("/worktree", "HEAD~1", "hard");
+
+      expect(mockInvoke).toHaveBeenCalledWith("git_reset", {
+        worktreePath: "/worktree",
+        revision: "HEAD~1",
+        mode: "hard",
+      });
+      expect(result).toEqual(resetResult);
+    });
+  });
+
+  describe("gitRevert", () => {
+    it("calls invoke with correct parameters", async () => {
+      const revertResult = {
+        completed: false,
+        status: {
+          branch: "main",
+          files: [{ path: "README.md", status: "conflicted", staged: false }],
+          ahead: 0,
+          behind: 0,
+        },
+      };
+      mockInvoke.mockResolvedValue(revertResult);
+
+      const result = await gitRevert("/worktree", ["abc123", "def456"]);
+
+      expect(mockInvoke).toHaveBeenCalledWith("git_revert", {
+        worktreePath: "/worktree",
+        commits: ["abc123", "def456"],
+      });
+      expect(result).toEqual(revertResult);
+    });
+  });
+
+  describe("gitRevertContinue", () => {
+    it("calls invoke with correct parameters", async () => {
+      mockInvoke.mockResolvedValue({ completed: true, status: {} });
+
+      await gitRevertContinue("/worktree");
+
+      expect(mockInvoke).toHaveBeenCalledWith("git_revert_continue", {
+        worktreePath: "/worktree",
+      });
+    });
+  });
+
+  describe("gitRevertAbort", () => {
+    it("calls invoke with correct parameters", async () => {
+      mockInvoke.mockResolvedValue(undefined);
+
+      await gitRevertAbort("/worktree");
+
+      expect(mockInvoke).toHaveBeenCalledWith("git_revert_abort", {
+        worktreePath: "/worktree",
+      });
+    });
+  });
+
+  describe("gitCherryPick", () => {
+    it("calls invoke with correct parameters", async () => {
+      mockInvoke.mockResolvedValue({ completed: true, status: {} });
+
+      await gitCherryPick("/release", ["abc123"], { record_origin: true });
+
+      expect(mockInvoke).toHaveBeenCalledWith("git_cherry_pick", {
+        worktreePath: "/release",
+        commits: ["abc123"],
+        options: { record_origin: true },
+      });
+    });
+  });
+
+  describe("gitCherryPickContinue", () => {
+    it("calls invoke with correct parameters", async () => {
+      mockInvoke.mockResolvedValue({ completed: true, status: {} });
+
+      await gitCherryPickContinue("/release");
+
+      expect(mockInvoke).toHaveBeenCalledWith("git_cherry_pick_continue", {
+        worktreePath: "/release",
+      });
+    });
+  });
+
+  describe("gitCherryPickAbort", () => {
+    it("calls invoke with correct parameters", async () => {
+      mockInvoke.mockResolvedValue(undefined);
+
+      await gitCherryPickAbort("/release");
+
+      expect(mockInvoke).toHaveBeenCalledWith("git_cherry_pick_abort", {
+        worktreePath: "/release",
+      });
+    });
+  });
+
   describe("listBranches", () => {
     it("calls invoke and returns branches", async () => {
       const mockBranches: BranchInfo[] = [
-        { name: "main", is_remote: false, is_current: true },
-        { name: "feature", is_remote: false, is_current: false },
-        { name: "origin/main", is_remote: true, is_current: false },
+        mockBranch("main", { is_current: true }),
+        mockBranch("feature"),
+        mockBranch("origin/main", { is_remote: true }),
       ];
       mockInvoke.mockResolvedValue(mockBranches);
 
@@ -545,6 +771,19 @@ describe("Tauri API wrappers", () => {
       });
     });
 
+    it("passes the local changes mode", async () => {
+      mockInvoke.mockResolvedValue({ branch: "feature", created_from: null, stashed: true });
+
+      const result = await checkoutBranch("/worktree", "feature", "stash");
+
+      expect(mockInvoke).toHaveBeenCalledWith("checkout_branch", {
+        worktreePath: "/worktree",
+        branch: "feature",
+        localChanges: "stash",
+      });
+      expect(result.stashed).toBe(true);
+    });
+
     it("handles branch names with slashes", async () => {
       mockInvoke.mockResolvedValue(undefined);
 
@@ -562,4 +801,751 @@ describe("Tauri API wrappers", () => {
       await expect(checkoutBranch("/worktree", "nonexistent")).rejects.toThrow("Branch not found");
     });
   });
+
+  describe("createBranch", () => {
+    it("calls invoke with correct parameters", async () => {
+      mockInvoke.mockResolvedValue(undefined);
+
+      await createBranch("/repo", "feature", "origin/develop");
+
+      expect(mockInvoke).toHaveBeenCalledWith("create_branch", {
+        repoPath: "/repo",
+        name: "feature",
+        startPoint: "origin/develop",
+      });
+    });
+  });
+
+  describe("renameBranch", () => {
+    it("calls invoke with correct parameters", async () => {
+      mockInvoke.mockResolvedValue(undefined);
+
+      await renameBranch("/repo", "old", "new", false);
+
+      expect(mockInvoke).toHaveBeenCalledWith("rename_branch", {
+        repoPath: "/repo",
+        oldName: "old",
+        newName: "new",
+        force: false,
+      });
+    });
+  });
+
+  describe("deleteBranch", () => {
+    it("calls invoke with correct parameters", async () => {
+      mockInvoke.mockResolvedValue(undefined);
+
+      await deleteBranch("/repo", "feature", true);
+
+      expect(mockInvoke).toHaveBeenCalledWith("delete_branch", {
+        repoPath: "/repo",
+        name: "feature",
+        force: true,
+      });
+    });
+
+    it("propagates errors from invoke", async () => {
+      mockInvoke.mockRejectedValue(new Error("Branch already checked out in another worktree"));
+
+      await expect(deleteBranch("/repo", "main", true)).rejects.toThrow(
+        "Branch already checked out in another worktree"
+      );
+    });
+  });
+
+  describe("setBranchUpstream", () => {
+    it("calls invoke with correct parameters", async () => {
+      mockInvoke.mockResolvedValue(undefined);
+
+      await setBranchUpstream("/repo", "feature", "origin/feature");
+
+      expect(mockInvoke).toHaveBeenCalledWith("set_branch_upstream", {
+        repoPath: "/repo",
+        name: "feature",
+        upstream: "origin/feature",
+      });
+    });
+  });
+
+  describe("unsetBranchUpstream", () => {
+    it("calls invoke with correct parameters", async () => {
+      mockInvoke.mockResolvedValue(undefined);
+
+      await unsetBranchUpstream("/repo", "feature");
+
+      expect(mockInvoke).toHaveBeenCalledWith("unset_branch_upstream", {
+        repoPath: "/repo",
+        name: "feature",
+      });
+    });
+  });
+
+  describe("analyzeBranches", () => {
+    it("calls invoke with correct parameters", async () => {
+      mockInvoke.mockResolvedValue([]);
+
+      const result = await analyzeBranches("/repo", "main", 90);
+
+      expect(mockInvoke).toHaveBeenCalledWith("analyze_branches", {
+        repoPath: "/repo",
+        base: "main",
+        staleDays: 90,
+      });
+      expect(result).toEqual([]);
+    });
+  });
+
+  describe("cleanupBranches", () => {
+    it("calls invoke with correct parameters", async () => {
+      mockInvoke.mockResolvedValue([]);
+
+      await cleanupBranches("/repo", ["done"], "main", false, true, true);
+
+      expect(mockInvoke).toHaveBeenCalledWith("cleanup_branches", {
+        repoPath: "/repo",
+        branches: ["done"],
+        base: "main",
+        deleteUnmerged: false,
+        discardChanges: true,
+        dryRun: true,
+      });
+    });
+  });
+
+  // ==================== Stash Commands ====================
+
+  describe("listStashes", () => {
+    it("calls invoke with correct parameters", async () => {
+      mockInvoke.mockResolvedValue([]);
+
+      const result = await listStashes("/worktree");
+
+      expect(mockInvoke).toHaveBeenCalledWith("list_stashes", { worktreePath: "/worktree" });
+      expect(result).toEqual([]);
+    });
+  });
+
+  describe("pushStash", () => {
+    it("calls invoke with correct parameters", async () => {
+      mockInvoke.mockResolvedValue(null);
+
+      await pushStash("/worktree", "wip", true, false, ["src"]);
+
+      expect(mockInvoke).toHaveBeenCalledWith("push_stash", {
+        worktreePath: "/worktree",
+        message: "wip",
+        includeUntracked: true,
+        keepIndex: false,
+        paths: ["src"],
+      });
+    });
+
+    it("defaults to stashing all paths", async () => {
+      mockInvoke.mockResolvedValue(null);
+
+      await pushStash("/worktree", null, false, false);
+
+      expect(mockInvoke).toHaveBeenCalledWith("push_stash", {
+        worktreePath: "/worktree",
+        message: null,
+        includeUntracked: false,
+        keepIndex: false,
+        paths: [],
+      });
+    });
+  });
+
+  describe("applyStash", () => {
+    it("calls invoke with correct parameters", async () => {
+      mockInvoke.mockResolvedValue(undefined);
+
+      await applyStash("/worktree", 1, true);
+
+      expect(mockInvoke).toHaveBeenCalledWith("apply_stash", {
+        worktreePath: "/worktree",
+        index: 1,
+        restoreIndex: true,
+      });
+    });
+  });
+
+  describe("popStash", () => {
+    it("calls invoke with correct parameters", async () => {
+      mockInvoke.mockResolvedValue(undefined);
+
+      await popStash("/worktree", 0, false);
+
+      expect(mockInvoke).toHaveBeenCalledWith("pop_stash", {
+        worktreePath: "/worktree",
+        index: 0,
+        restoreIndex: false,
+      });
+    });
+  });
+
+  describe("dropStash", () => {
+    it("calls invoke with correct parameters", async () => {
+      mockInvoke.mockResolvedValue(undefined);
+
+      await dropStash("/worktree", 2);
+
+      expect(mockInvoke).toHaveBeenCalledWith("drop_stash", { worktreePath: "/worktree", index: 2 });
+    });
+  });
+
+  describe("showStash", () => {
+    it("calls invoke with correct parameters", async () => {
+      mockInvoke.mockResolvedValue([]);
+
+      await showStash("/worktree", 0);
+
+      expect(mockInvoke).toHaveBeenCalledWith("show_stash", { worktreePath: "/worktree", index: 0 });
+    });
+  });
+
+  describe("moveWorktree", () => {
+    it("calls invoke with correct parameters", async () => {
+      mockInvoke.mockResolvedValue(undefined);
+
+      await moveWorktree("/repo", "/old/worktree", "/new/worktree");
+
+      expect(mockInvoke).toHaveBeenCalledWith("move_worktree", {
+        repoPath: "/repo",
+        worktreePath: "/old/worktree",
+        newPath: "/new/worktree",
+      });
+    });
+
+    it("propagates errors from invoke", async () => {
+      mockInvoke.mockRejectedValue(new Error("Worktree is locked: in use"));
+
+      await expect(moveWorktree("/repo", "/old", "/new")).rejects.toThrow("Worktree is locked");
+    });
+  });
+
+  describe("checkWorktreeLinks", () => {
+    it("calls invoke with correct parameters", async () => {
+      mockInvoke.mockResolvedValue([]);
+
+      const result = await checkWorktreeLinks("/repo");
+
+      expect(mockInvoke).toHaveBeenCalledWith("check_worktree_links", { repoPath: "/repo" });
+      expect(result).toEqual([]);
+    });
+  });
+
+  describe("repairWorktrees", () => {
+    it("calls invoke with explicit worktree paths", async () => {
+      mockInvoke.mockResolvedValue([]);
+
+      await repairWorktrees("/repo", ["/moved/worktree"]);
+
+      expect(mockInvoke).toHaveBeenCalledWith("repair_worktrees", {
+        repoPath: "/repo",
+        worktreePaths: ["/moved/worktree"],
+      });
+    });
+
+    it("defaults to no explicit paths", async () => {
+      mockInvoke.mockResolvedValue([]);
+
+      await repairWorktrees("/repo");
+
+      expect(mockInvoke).toHaveBeenCalledWith("repair_worktrees", {
+        repoPath: "/repo",
+        worktreePaths: [],
+      });
+    });
+  });
+
+  describe("pathArg", () => {
+    it("uses the display path for UTF-8 paths", () => {
+      expect(pathArg({ path: "/repo-feature" })).toBe("/repo-feature");
+    });
+
+    it("prefers raw bytes when present", () => {
+      expect(pathArg({ path: "/repo-\uFFFD", path_bytes: [47, 255] })).toEqual([47, 255]);
+    });
+  });
+
+  describe("listTrash", () => {
+    it("calls invoke with correct parameters", async () => {
+      mockInvoke.mockResolvedValue([]);
+
+      await listTrash("/repo");
+
+      expect(mockInvoke).toHaveBeenCalledWith("list_trash", { repoPath: "/repo" });
+    });
+  });
+
+  describe("restoreTrash", () => {
+    it("calls invoke with correct parameters", async () => {
+      mockInvoke.mockResolvedValue(undefined);
+
+      await restoreTrash("/repo", "feature-1700000000", "/repo-feature");
+
+      expect(mockInvoke).toHaveBeenCalledWith("restore_trash", {
+        repoPath: "/repo",
+        name: "feature-1700000000",
+        worktreePath: "/repo-feature",
+      });
+    });
+  });
+
+  describe("purgeTrash", () => {
+    it("calls invoke with correct parameters", async () => {
+      mockInvoke.mockResolvedValue(["feature-1700000000"]);
+
+      const result = await purgeTrash("/repo", 30);
+
+      expect(mockInvoke).toHaveBeenCalledWith("purge_trash", {
+        repoPath: "/repo",
+        retentionDays: 30,
+      });
+      expect(result).toEqual(["feature-1700000000"]);
+    });
+  });
+
+  // ==================== Audit Log ====================
+
+  describe("queryAuditLog", () => {
+    it("calls invoke with correct parameters", async () => {
+      mockInvoke.mockResolvedValue([]);
+
+      const result = await queryAuditLog("/repo", {
+        operation: "remove_worktree",
+        limit: 10,
+      });
+
+      expect(mockInvoke).toHaveBeenCalledWith("query_audit_log", {
+        repoPath: "/repo",
+        query: { operation: "remove_worktree", limit: 10 },
+      });
+      expect(result).toEqual([]);
+    });
+  });
+
+  describe("exportAuditLog", () => {
+    it("calls invoke with correct parameters", async () => {
+      mockInvoke.mockResolvedValue(3);
+
+      const result = await exportAuditLog("/repo", "/tmp/audit.jsonl");
+
+      expect(mockInvoke).toHaveBeenCalledWith("export_audit_log", {
+        repoPath: "/repo",
+        destination: "/tmp/audit.jsonl",
+        query: undefined,
+      });
+      expect(result).toBe(3);
+    });
+  });
+
+  // ==================== Undo ====================
+
+  describe("lastUndoEntry", () => {
+    it("calls invoke with correct parameters", async () => {
+      mockInvoke.mockResolvedValue(null);
+
+      const result = await lastUndoEntry("/repo");
+
+      expect(mockInvoke).toHaveBeenCalledWith("last_undo_entry", {
+        repoPath: "/repo",
+      });
+      expect(result).toBeNull();
+    });
+  });
+
+  describe("undoLastOperation", () => {
+    it("calls invoke with correct parameters", async () => {
+      const undoResult = {
+        operation: "delete_branch",
+        head_restored: false,
+        restored_branches: ["feature"],
+        skipped_branches: [],
+      };
+      mockInvoke.mockResolvedValue(undoResult);
+
+      const result = await undoLastOperation("/repo");
+
+      expect(mockInvoke).toHaveBeenCalledWith("undo_last_operation", {
+        repoPath: "/repo",
+      });
+      expect(result).toEqual(undoResult);
+    });
+  });
+
+  // ==================== Reflog ====================
+
+  describe("readReflog", () => {
+    it("calls invoke with correct parameters", async () => {
+      mockInvoke.mockResolvedValue([]);
+
+      const result = await readReflog("/repo-feature", "feature", 20);
+
+      expect(mockInvoke).toHaveBeenCalledWith("read_reflog", {
+        worktreePath: "/repo-feature",
+        reference: "feature",
+        limit: 20,
+      });
+      expect(result).toEqual([]);
+    });
+  });
+
+  describe("createBranchFromReflog", () => {
+    it("calls invoke with correct parameters", async () => {
+      mockInvoke.mockResolvedValue(undefined);
+
+      await createBranchFromReflog("/repo", undefined, 1, "recovered");
+
+      expect(mockInvoke).toHaveBeenCalledWith("create_branch_from_reflog", {
+        worktreePath: "/repo",
+        reference: undefined,
+        index: 1,
+        name: "recovered",
+      });
+    });
+  });
+
+  describe("addWorktreeFromReflog", () => {
+    it("calls invoke with correct parameters", async () => {
+      mockInvoke.mockResolvedValue(undefined);
+
+      await addWorktreeFromReflog(
+        "/repo",
+        "HEAD",
+        2,
+        "/repo-recovered",
+        "recovered"
+      );
+
+      expect(mockInvoke).toHaveBeenCalledWith("add_worktree_from_reflog", {
+        repoPath: "/repo",
+        reference: "HEAD",
+        index: 2,
+        worktreePath: "/repo-recovered",
+        branch: "recovered",
+      });
+    });
+  });
+
+  // ==================== Interactive Rebase ====================
+
+  describe("rebasePlan", () => {
+    it("calls invoke with correct parameters", async () => {
+      const steps = [{ action: "pick", oid: "abc123", summary: "Add a", message: null }];
+      mockInvoke.mockResolvedValue(steps);
+
+      const result = await rebasePlan("/worktree", "main");
+
+      expect(mockInvoke).toHaveBeenCalledWith("rebase_plan", {
+        worktreePath: "/worktree",
+        upstream: "main",
+      });
+      expect(result).toEqual(steps);
+    });
+  });
+
+  describe("rebaseExecute", () => {
+    it("calls invoke with correct parameters", async () => {
+      mockInvoke.mockResolvedValue({ state: "completed", done: 2, total: 2 });
+      const plan = [
+        { action: "pick" as const, oid: "abc123", summary: "Add a" },
+        { action: "squash" as const, oid: "def456", summary: "Add b", message: "Add a and b" },
+      ];
+
+      await rebaseExecute("/worktree", "main", plan);
+
+      expect(mockInvoke).toHaveBeenCalledWith("rebase_execute", {
+        worktreePath: "/worktree",
+        upstream: "main",
+        plan,
+      });
+    });
+  });
+
+  describe("rebaseProgress", () => {
+    it("calls invoke with correct parameters", async () => {
+      mockInvoke.mockResolvedValue(null);
+
+      const result = await rebaseProgress("/worktree");
+
+      expect(mockInvoke).toHaveBeenCalledWith("rebase_progress", {
+        worktreePath: "/worktree",
+      });
+      expect(result).toBeNull();
+    });
+  });
+
+  describe("rebaseContinue", () => {
+    it("calls invoke with correct parameters", async () => {
+      mockInvoke.mockResolvedValue({ state: "completed", done: 2, total: 2 });
+
+      await rebaseContinue("/worktree");
+
+      expect(mockInvoke).toHaveBeenCalledWith("rebase_continue", {
+        worktreePath: "/worktree",
+      });
+    });
+  });
+
+  describe("rebaseAbort", () => {
+    it("calls invoke with correct parameters", async () => {
+      mockInvoke.mockResolvedValue(undefined);
+
+      await rebaseAbort("/worktree");
+
+      expect(mockInvoke).toHaveBeenCalledWith("rebase_abort", {
+        worktreePath: "/worktree",
+      });
+    });
+  });
+
+  // ==================== Submodule Operations ====================
+
+  describe("listSubmodules", () => {
+    it("calls invoke with correct parameters", async () => {
+      mockInvoke.mockResolvedValue([]);
+
+      const result = await listSubmodules("/worktree");
+
+      expect(mockInvoke).toHaveBeenCalledWith("list_submodules", {
+        worktreePath: "/worktree",
+      });
+      expect(result).toEqual([]);
+    });
+  });
+
+  describe("initSubmodules", () => {
+    it("defaults to all submodules", async () => {
+      mockInvoke.mockResolvedValue(undefined);
+
+      await initSubmodules("/worktree");
+
+      expect(mockInvoke).toHaveBeenCalledWith("init_submodules", {
+        worktreePath: "/worktree",
+        paths: [],
+      });
+    });
+  });
+
+  describe("updateSubmodules", () => {
+    it("calls invoke with correct parameters", async () => {
+      mockInvoke.mockResolvedValue(undefined);
+
+      await updateSubmodules("/worktree", ["vendor/lib"], true, true);
+
+      expect(mockInvoke).toHaveBeenCalledWith("update_submodules", {
+        worktreePath: "/worktree",
+        paths: ["vendor/lib"],
+        init: true,
+        recursive: true,
+      });
+    });
+  });
+
+  describe("syncSubmodules", () => {
+    it("calls invoke with correct parameters", async () => {
+      mockInvoke.mockResolvedValue(undefined);
+
+      await syncSubmodules("/worktree");
+
+      expect(mockInvoke).toHaveBeenCalledWith("sync_submodules", {
+        worktreePath: "/worktree",
+        paths: [],
+        recursive: false,
+      });
+    });
+  });
+
+  describe("sparseCheckoutInfo", () => {
+    it("calls invoke with correct parameters", async () => {
+      const info = { enabled: true, cone: true, patterns: ["src"] };
+      mockInvoke.mockResolvedValue(info);
+
+      const result = await sparseCheckoutInfo("/worktree");
+
+      expect(mockInvoke).toHaveBeenCalledWith("sparse_checkout_info", {
+        worktreePath: "/worktree",
+      });
+      expect(result).toEqual(info);
+    });
+  });
+
+  describe("setSparsePatterns", () => {
+    it("calls invoke with correct parameters", async () => {
+      mockInvoke.mockResolvedValue(undefined);
+
+      await setSparsePatterns("/worktree", ["src", "docs"]);
+
+      expect(mockInvoke).toHaveBeenCalledWith("set_sparse_patterns", {
+        worktreePath: "/worktree",
+        patterns: ["src", "docs"],
+      });
+    });
+  });
+
+  describe("saveSparseProfile", () => {
+    it("calls invoke with correct parameters", async () => {
+      mockInvoke.mockResolvedValue(undefined);
+
+      await saveSparseProfile("/repo", "frontend", ["src"]);
+
+      expect(mockInvoke).toHaveBeenCalledWith("save_sparse_profile", {
+        repoPath: "/repo",
+        name: "frontend",
+        patterns: ["src"],
+      });
+    });
+  });
+
+  describe("getConfig", () => {
+    it("calls invoke with correct parameters", async () => {
+      const entry = { name: "user.email", value: "me@example.com", scope: "worktree" };
+      mockInvoke.mockResolvedValue(entry);
+
+      const result = await getConfig("/worktree", "user.email");
+
+      expect(mockInvoke).toHaveBeenCalledWith("get_config", {
+        worktreePath: "/worktree",
+        name: "user.email",
+      });
+      expect(result).toEqual(entry);
+    });
+  });
+
+  describe("setConfig", () => {
+    it("calls invoke with correct parameters", async () => {
+      mockInvoke.mockResolvedValue(undefined);
+
+      await setConfig("/worktree", "user.email", "me@example.com", "worktree");
+
+      expect(mockInvoke).toHaveBeenCalledWith("set_config", {
+        worktreePath: "/worktree",
+        name: "user.email",
+        value: "me@example.com",
+        scope: "worktree",
+      });
+    });
+  });
+
+  describe("addRemote", () => {
+    it("calls invoke with correct parameters", async () => {
+      mockInvoke.mockResolvedValue(undefined);
+
+      await addRemote("/repo", "colleague", "https://example.com/fork.git");
+
+      expect(mockInvoke).toHaveBeenCalledWith("add_remote", {
+        repoPath: "/repo",
+        name: "colleague",
+        url: "https://example.com/fork.git",
+      });
+    });
+  });
+
+  describe("renameRemote", () => {
+    it("returns refspecs that could not be updated", async () => {
+      mockInvoke.mockResolvedValue(["+refs/pull/*:refs/pr/*"]);
+
+      const result = await renameRemote("/repo", "colleague", "alice");
+
+      expect(mockInvoke).toHaveBeenCalledWith("rename_remote", {
+        repoPath: "/repo",
+        oldName: "colleague",
+        newName: "alice",
+      });
+      expect(result).toEqual(["+refs/pull/*:refs/pr/*"]);
+    });
+  });
+
+  describe("setRemotePushUrl", () => {
+    it("passes null to push to the fetch URL again", async () => {
+      mockInvoke.mockResolvedValue(undefined);
+
+      await setRemotePushUrl("/repo", "origin", null);
+
+      expect(mockInvoke).toHaveBeenCalledWith("set_remote_push_url", {
+        repoPath: "/repo",
+        name: "origin",
+        url: null,
+      });
+    });
+  });
+
+  describe("respondAuthPrompt", () => {
+    it("calls invoke with correct parameters", async () => {
+      mockInvoke.mockResolvedValue(undefined);
+
+      await respondAuthPrompt(3, "hunter2");
+
+      expect(mockInvoke).toHaveBeenCalledWith("respond_auth_prompt", {
+        id: 3,
+        response: "hunter2",
+      });
+    });
+
+    it("passes null to cancel a prompt", async () => {
+      mockInvoke.mockResolvedValue(undefined);
+
+      await respondAuthPrompt(3, null);
+
+      expect(mockInvoke).toHaveBeenCalledWith("respond_auth_prompt", {
+        id: 3,
+        response: null,
+      });
+    });
+  });
+
+  describe("gitExecutableInfo", () => {
+    it("calls invoke with correct parameters", async () => {
+      const info = {
+        path: "/usr/bin/git",
+        configured_path: null,
+        version: { major: 2, minor: 39, patch: 5 },
+        minimum_version: { major: 2, minor: 20, patch: 0 },
+        capabilities: { worktree_repair: true, sparse_checkout: true, config_scopes: true },
+        error: null,
+      };
+      mockInvoke.mockResolvedValue(info);
+
+      const result = await gitExecutableInfo();
+
+      expect(mockInvoke).toHaveBeenCalledWith("git_executable_info");
+      expect(result).toEqual(info);
+    });
+  });
+
+  describe("setGitExecutable", () => {
+    it("calls invoke with correct parameters", async () => {
+      mockInvoke.mockResolvedValue({});
+
+      await setGitExecutable("/opt/git/bin/git");
+
+      expect(mockInvoke).toHaveBeenCalledWith("set_git_executable", {
+        path: "/opt/git/bin/git",
+      });
+    });
+  });
+
+  describe("gitBackend", () => {
+    it("calls invoke with correct parameters", async () => {
+      mockInvoke.mockResolvedValue("libgit2");
+
+      const result = await gitBackend();
+
+      expect(mockInvoke).toHaveBeenCalledWith("git_backend");
+      expect(result).toBe("libgit2");
+    });
+  });
+
+  describe("setGitBackend", () => {
+    it("calls invoke with correct parameters", async () => {
+      mockInvoke.mockResolvedValue(undefined);
+
+      await setGitBackend("cli");
+
+      expect(mockInvoke).toHaveBeenCalledWith("set_git_backend", {
+        backend: "cli",
+      });
+    });
+  });
 });
@@ -1,8 +1,22 @@
 import { invoke } from "@tauri-apps/api/core";
+import { listen, type UnlistenFn } from "@tauri-apps/api/event";
 
 // Types matching the Rust backend
+
+/**
+ * A path passed to the backend: the display string, or the raw bytes from a
+ * model's `path_bytes` when the path is not valid UTF-8.
+ */
+export type PathArg = string | number[];
+
+/** Returns the lossless form of a path reported by the backend */
+export function pathArg(item: { path: string; path_bytes?: number[] }): PathArg {
+  return item.path_bytes ?? item.path;
+}
+
 export interface WorktreeInfo {
   path: string;
+  path_bytes?: number[];
   branch: string | null;
   is_main: boolean;
   is_locked: boolean;
@@ -10,6 +24,7 @@ export interface WorktreeInfo {
 
 export interface FileStatus {
   path: string;
+  path_bytes?: number[];
   status: string;
   staged: boolean;
 }
@@ -25,6 +40,252 @@ export interface BranchInfo {
   name: string;
   is_remote: boolean;
   is_current: boolean;
+  upstream: string | null;
+  upstream_gone: boolean;
+  ahead: number;
+  behind: number;
+  last_commit_summary: string | null;
+  last_commit_time: number | null;
+  worktree_path: string | null;
+}
+
+export type LocalChangesMode = "refuse" | "merge" | "stash";
+
+export type ResetMode = "soft" | "mixed" | "hard";
+
+export interface ResetResult {
+  previous_head: string;
+  snapshot: string | null;
+}
+
+export interface CherryPickOptions {
+  record_origin?: boolean;
+  no_commit?: boolean;
+}
+
+export interface SequencerResult {
+  completed: boolean;
+  status: GitStatusResult;
+}
+
+export interface CheckoutResult {
+  branch: string;
+  created_from: string | null;
+  stashed: boolean;
+}
+
+export interface BranchCleanupCandidate {
+  name: string;
+  merged: boolean;
+  upstream_gone: boolean;
+  stale: boolean;
+  last_commit_time: number | null;
+  worktree_path: string | null;
+}
+
+export interface BranchCleanupResult {
+  name: string;
+  removed_worktree: string | null;
+  deleted: boolean;
+  error: string | null;
+}
+
+export interface TrashEntry {
+  name: string;
+  path: string;
+  branch: string | null;
+  head: string;
+  time: number;
+}
+
+export interface AuditEntry {
+  timestamp: number;
+  user: string;
+  operation: string;
+  repo: string;
+  worktree: string | null;
+  args: unknown;
+  success: boolean;
+  error: string | null;
+  duration_ms: number;
+}
+
+export interface AuditQuery {
+  operation?: string;
+  worktree?: string;
+  user?: string;
+  since?: number;
+  until?: number;
+  failed_only?: boolean;
+  limit?: number;
+}
+
+export interface HeadState {
+  branch: string | null;
+  oid: string | null;
+}
+
+export interface BranchChange {
+  name: string;
+  before: string | null;
+  after: string | null;
+}
+
+export interface UndoEntry {
+  operation: string;
+  time: number;
+  worktree: string | null;
+  head_before: HeadState | null;
+  head_after: HeadState | null;
+  branches: BranchChange[];
+}
+
+export interface UndoResult {
+  operation: string;
+  head_restored: boolean;
+  restored_branches: string[];
+  skipped_branches: string[];
+}
+
+export interface ReflogEntry {
+  index: number;
+  old_oid: string | null;
+  new_oid: string;
+  message: string | null;
+  committer: string;
+  time: number;
+}
+
+export type RebaseAction = "pick" | "squash" | "fixup" | "reword" | "drop" | "edit";
+
+export interface RebaseStep {
+  action: RebaseAction;
+  oid: string;
+  summary: string;
+  message?: string | null;
+}
+
+export interface RebaseProgress {
+  state: "completed" | "stopped" | "conflicts";
+  done: number;
+  total: number;
+  stopped_at: string | null;
+  status: GitStatusResult;
+}
+
+export interface SubmoduleInfo {
+  name: string;
+  path: string;
+  url: string | null;
+  recorded_oid: string | null;
+  checked_out_oid: string | null;
+  initialized: boolean;
+  commit_changed: boolean;
+  dirty: boolean;
+}
+
+export interface SparseCheckoutInfo {
+  enabled: boolean;
+  cone: boolean;
+  patterns: string[];
+}
+
+export interface SparseProfile {
+  name: string;
+  patterns: string[];
+}
+
+export interface GitVersion {
+  major: number;
+  minor: number;
+  patch: number;
+}
+
+export interface GitExecutableInfo {
+  path: string | null;
+  configured_path: string | null;
+  version: GitVersion | null;
+  minimum_version: GitVersion;
+  capabilities: {
+    worktree_repair: boolean;
+    sparse_checkout: boolean;
+    config_scopes: boolean;
+  };
+  error: string | null;
+}
+
+/** How stage, unstage, commit, checkout, lock and unlock are carried out */
+export type GitBackend = "cli" | "libgit2";
+
+export interface AuthPrompt {
+  id: number;
+  prompt: string;
+  secret: boolean;
+}
+
+export interface RemoteInfo {
+  name: string;
+  fetch_url: string | null;
+  push_url: string | null;
+  fetch_refspecs: string[];
+}
+
+export interface RemoteBranch {
+  name: string;
+  oid: string;
+}
+
+export interface RemoteBranches {
+  head: string | null;
+  branches: RemoteBranch[];
+}
+
+export type ConfigScope = "system" | "global" | "local" | "worktree" | "command";
+
+export interface ConfigEntry {
+  name: string;
+  value: string | null;
+  scope: ConfigScope;
+}
+
+export interface StashInfo {
+  index: number;
+  oid: string;
+  message: string;
+  branch: string | null;
+  worktree_path: string | null;
+  time: number | null;
+}
+
+export interface RemovalPreflight {
+  path: string;
+  branch: string | null;
+  is_locked: boolean;
+  lock_reason: string | null;
+  has_uncommitted_changes: boolean;
+  upstream: string | null;
+  unpushed_commits: number;
+  ignored_size_bytes: number;
+  stash_count: number;
+}
+
+export interface RemovalConfirmation {
+  uncommitted_changes?: boolean;
+  unpushed_commits?: boolean;
+  locked?: boolean;
+}
+
+export type WorktreeLinkProblem = "worktree_missing" | "backlink_broken";
+
+export interface WorktreeLinkIssue {
+  name: string;
+  path: string;
+  problem: WorktreeLinkProblem;
+}
+
+export interface RepairedLink {
+  description: string;
+  path: string;
 }
 
 export interface RepositoryInfo {
@@ -53,24 +314,39 @@ export async function listWorktrees(repoPath: string): Promise<WorktreeInfo[]> {
 
 export async function addWorktree(
   repoPath: string,
-  worktreePath: string,
+  worktreePath: PathArg,
   branch: string,
-  createBranch: boolean
+  createBranch: boolean,
+  sparseProfile?: string
 ): Promise<void> {
-  return invoke("add_worktree", { repoPath, worktreePath, branch, createBranch });
+  return invoke("add_worktree", {
+    repoPath,
+    worktreePath,
+    branch,
+    createBranch,
+    sparseProfile,
+  });
+}
+
+export async function removalPreflight(
+  repoPath: string,
+  worktreePath: PathArg
+): Promise<RemovalPreflight> {
+  return invoke("removal_preflight", { repoPath, worktreePath });
 }
 
 export async function removeWorktree(
   repoPath: string,
-  worktreePath: string,
-  force: boolean
+  worktreePath: PathArg,
+  force: boolean,
+  confirm?: RemovalConfirmation
 ): Promise<void> {
-  return invoke("remove_worktree", { repoPath, worktreePath, force });
+  return invoke("remove_worktree", { repoPath, worktreePath, force, confirm });
 }
 
 export async function lockWorktree(
   repoPath: string,
-  worktreePath: string,
+  worktreePath: PathArg,
   reason?: string
 ): Promise<void> {
   return invoke("lock_worktree", { repoPath, worktreePath, reason });
@@ -78,7 +354,7 @@ export async function lockWorktree(
 
 export async function unlockWorktree(
   repoPath: string,
-  worktreePath: string
+  worktreePath: PathArg
 ): Promise<void> {
   return invoke("unlock_worktree", { repoPath, worktreePath });
 }
@@ -96,7 +372,7 @@ export async function gitPush(worktreePath: string): Promise<string> {
   return invoke("git_push", { worktreePath });
 }
 
-export async function gitStatus(worktreePath: string): Promise<GitStatusResult> {
+export async function gitStatus(worktreePath: PathArg): Promise<GitStatusResult> {
   return invoke("git_status", { worktreePath });
 }
 
@@ -108,19 +384,62 @@ export async function gitCommit(
 }
 
 export async function gitStage(
-  worktreePath: string,
-  filePath: string
+  worktreePath: PathArg,
+  filePath: PathArg
 ): Promise<void> {
   return invoke("git_stage", { worktreePath, filePath });
 }
 
 export async function gitUnstage(
-  worktreePath: string,
-  filePath: string
+  worktreePath: PathArg,
+  filePath: PathArg
 ): Promise<void> {
   return invoke("git_unstage", { worktreePath, filePath });
 }
 
+export async function gitReset(
+  worktreePath: string,
+  revision: string,
+  mode: ResetMode
+): Promise<ResetResult> {
+  return invoke("git_reset", { worktreePath, revision, mode });
+}
+
+export async function gitRevert(
+  worktreePath: string,
+  commits: string[]
+): Promise<SequencerResult> {
+  return invoke("git_revert", { worktreePath, commits });
+}
+
+export async function gitRevertContinue(
+  worktreePath: string
+): Promise<SequencerResult> {
+  return invoke("git_revert_continue", { worktreePath });
+}
+
+export async function gitRevertAbort(worktreePath: string): Promise<void> {
+  return invoke("git_revert_abort", { worktreePath });
+}
+
+export async function gitCherryPick(
+  worktreePath: string,
+  commits: string[],
+  options?: CherryPickOptions
+): Promise<SequencerResult> {
+  return invoke("git_cherry_pick", { worktreePath, commits, options });
+}
+
+export async function gitCherryPickContinue(
+  worktreePath: string
+): Promise<SequencerResult> {
+  return invoke("git_cherry_pick_continue", { worktreePath });
+}
+
+export async function gitCherryPickAbort(worktreePath: string): Promise<void> {
+  return invoke("git_cherry_pick_abort", { worktreePath });
+}
+
 // Branch operations
 export async function listBranches(repoPath: string): Promise<BranchInfo[]> {
   return invoke("list_branches", { repoPath });
@@ -128,7 +447,459 @@ export async function listBranches(repoPath: string): Promise<BranchInfo[]> {
 
 export async function checkoutBranch(
   worktreePath: string,
+  branch: string,
+  localChanges?: LocalChangesMode
+): Promise<CheckoutResult> {
+  return invoke("checkout_branch", { worktreePath, branch, localChanges });
+}
+
+export async function createBranch(
+  repoPath: string,
+  name: string,
+  startPoint?: string
+): Promise<void> {
+  return invoke("create_branch", { repoPath, name, startPoint });
+}
+
+export async function renameBranch(
+  repoPath: string,
+  oldName: string,
+  newName: string,
+  force: boolean
+): Promise<void> {
+  return invoke("rename_branch", { repoPath, oldName, newName, force });
+}
+
+export async function deleteBranch(
+  repoPath: string,
+  name: string,
+  force: boolean
+): Promise<void> {
+  return invoke("delete_branch", { repoPath, name, force });
+}
+
+export async function setBranchUpstream(
+  repoPath: string,
+  name: string,
+  upstream: string
+): Promise<void> {
+  return invoke("set_branch_upstream", { repoPath, name, upstream });
+}
+
+export async function unsetBranchUpstream(
+  repoPath: string,
+  name: string
+): Promise<void> {
+  return invoke("unset_branch_upstream", { repoPath, name });
+}
+
+export async function analyzeBranches(
+  repoPath: string,
+  base: string,
+  staleDays: number
+): Promise<BranchCleanupCandidate[]> {
+  return invoke("analyze_branches", { repoPath, base, staleDays });
+}
+
+export async function cleanupBranches(
+  repoPath: string,
+  branches: string[],
+  base: string,
+  deleteUnmerged: boolean,
+  discardChanges: boolean,
+  dryRun: boolean
+): Promise<BranchCleanupResult[]> {
+  return invoke("cleanup_branches", {
+    repoPath,
+    branches,
+    base,
+    deleteUnmerged,
+    discardChanges,
+    dryRun,
+  });
+}
+
+// Stash operations
+export async function listStashes(worktreePath: string): Promise<StashInfo[]> {
+  return invoke("list_stashes", { worktreePath });
+}
+
+export async function pushStash(
+  worktreePath: string,
+  message: string | null,
+  includeUntracked: boolean,
+  keepIndex: boolean,
+  paths: string[] = []
+): Promise<StashInfo | null> {
+  return invoke("push_stash", {
+    worktreePath,
+    message,
+    includeUntracked,
+    keepIndex,
+    paths,
+  });
+}
+
+export async function applyStash(
+  worktreePath: string,
+  index: number,
+  restoreIndex: boolean
+): Promise<void> {
+  return invoke("apply_stash", { worktreePath, index, restoreIndex });
+}
+
+export async function popStash(
+  worktreePath: string,
+  index: number,
+  restoreIndex: boolean
+): Promise<void> {
+  return invoke("pop_stash", { worktreePath, index, restoreIndex });
+}
+
+export async function dropStash(worktreePath: string, index: number): Promise<void> {
+  return invoke("drop_stash", { worktreePath, index });
+}
+
+export async function showStash(
+  worktreePath: string,
+  index: number
+): Promise<FileStatus[]> {
+  return invoke("show_stash", { worktreePath, index });
+}
+
+export async function moveWorktree(
+  repoPath: string,
+  worktreePath: PathArg,
+  newPath: PathArg
+): Promise<void> {
+  return invoke("move_worktree", { repoPath, worktreePath, newPath });
+}
+
+export async function checkWorktreeLinks(
+  repoPath: string
+): Promise<WorktreeLinkIssue[]> {
+  return invoke("check_worktree_links", { repoPath });
+}
+
+export async function repairWorktrees(
+  repoPath: string,
+  worktreePaths: string[] = []
+): Promise<RepairedLink[]> {
+  return invoke("repair_worktrees", { repoPath, worktreePaths });
+}
+
+// Submodule operations
+export async function listSubmodules(
+  worktreePath: string
+): Promise<SubmoduleInfo[]> {
+  return invoke("list_submodules", { worktreePath });
+}
+
+export async function initSubmodules(
+  worktreePath: string,
+  paths: string[] = []
+): Promise<void> {
+  return invoke("init_submodules", { worktreePath, paths });
+}
+
+export async function updateSubmodules(
+  worktreePath: string,
+  paths: string[] = [],
+  init = true,
+  recursive = false
+): Promise<void> {
+  return invoke("update_submodules", { worktreePath, paths, init, recursive });
+}
+
+export async function syncSubmodules(
+  worktreePath: string,
+  paths: string[] = [],
+  recursive = false
+): Promise<void> {
+  return invoke("sync_submodules", { worktreePath, paths, recursive });
+}
+
+// Sparse checkout
+export async function sparseCheckoutInfo(
+  worktreePath: string
+): Promise<SparseCheckoutInfo> {
+  return invoke("sparse_checkout_info", { worktreePath });
+}
+
+export async function enableSparseCheckout(worktreePath: string): Promise<void> {
+  return invoke("enable_sparse_checkout", { worktreePath });
+}
+
+export async function disableSparseCheckout(worktreePath: string): Promise<void> {
+  return invoke("disable_sparse_checkout", { worktreePath });
+}
+
+export async function setSparsePatterns(
+  worktreePath: string,
+  patterns: string[]
+): Promise<void> {
+  return invoke("set_sparse_patterns", { worktreePath, patterns });
+}
+
+export async function addSparsePatterns(
+  worktreePath: string,
+  patterns: string[]
+): Promise<void> {
+  return invoke("add_sparse_patterns", { worktreePath, patterns });
+}
+
+export async function listSparseProfiles(
+  repoPath: string
+): Promise<SparseProfile[]> {
+  return invoke("list_sparse_profiles", { repoPath });
+}
+
+export async function saveSparseProfile(
+  repoPath: string,
+  name: string,
+  patterns: string[]
+): Promise<void> {
+  return invoke("save_sparse_profile", { repoPath, name, patterns });
+}
+
+export async function deleteSparseProfile(
+  repoPath: string,
+  name: string
+): Promise<void> {
+  return invoke("delete_sparse_profile", { repoPath, name });
+}
+
+// Git executable
+export async function gitExecutableInfo(): Promise<GitExecutableInfo> {
+  return invoke("git_executable_info");
+}
+
+/** Sets the git executable to use; `null` goes back to looking it up */
+export async function setGitExecutable(path: string | null): Promise<GitExecutableInfo> {
+  return invoke("set_git_executable", { path });
+}
+
+// Git backend
+export async function gitBackend(): Promise<GitBackend> {
+  return invoke("git_backend");
+}
+
+/** Chooses between the git CLI and libgit2 for local operations */
+export async function setGitBackend(backend: GitBackend): Promise<void> {
+  return invoke("set_git_backend", { backend });
+}
+
+// Authentication
+/** Calls `handler` when git or ssh asks for a credential or confirmation */
+export async function onAuthPrompt(
+  handler: (prompt: AuthPrompt) => void
+): Promise<UnlistenFn> {
+  return listen<AuthPrompt>("auth-prompt", (event) => handler(event.payload));
+}
+
+/** Answers a prompt; `null` cancels it, failing the operation that asked */
+export async function respondAuthPrompt(
+  id: number,
+  response: string | null
+): Promise<void> {
+  return invoke("respond_auth_prompt", { id, response });
+}
+
+// Remotes
+export async function listRemotes(repoPath: string): Promise<RemoteInfo[]> {
+  return invoke("list_remotes", { repoPath });
+}
+
+export async function addRemote(
+  repoPath: string,
+  name: string,
+  url: string
+): Promise<void> {
+  return invoke("add_remote", { repoPath, name, url });
+}
+
+export async function renameRemote(
+  repoPath: string,
+  oldName: string,
+  newName: string
+): Promise<string[]> {
+  return invoke("rename_remote", { repoPath, oldName, newName });
+}
+
+export async function removeRemote(repoPath: string, name: string): Promise<void> {
+  return invoke("remove_remote", { repoPath, name });
+}
+
+export async function setRemoteUrl(
+  repoPath: string,
+  name: string,
+  url: string
+): Promise<void> {
+  return invoke("set_remote_url", { repoPath, name, url });
+}
+
+export async function setRemotePushUrl(
+  repoPath: string,
+  name: string,
+  url: string | null
+): Promise<void> {
+  return invoke("set_remote_push_url", { repoPath, name, url });
+}
+
+export async function listRemoteBranches(
+  repoPath: string,
+  name: string
+): Promise<RemoteBranches> {
+  return invoke("list_remote_branches", { repoPath, name });
+}
+
+// Config
+export async function listConfig(
+  worktreePath: string,
+  scope?: ConfigScope
+): Promise<ConfigEntry[]> {
+  return invoke("list_config", { worktreePath, scope });
+}
+
+export async function getConfig(
+  worktreePath: string,
+  name: string
+): Promise<ConfigEntry | null> {
+  return invoke("get_config", { worktreePath, name });
+}
+
+export async function setConfig(
+  worktreePath: string,
+  name: string,
+  value: string,
+  scope: ConfigScope
+): Promise<void> {
+  return invoke("set_config", { worktreePath, name, value, scope });
+}
+
+export async function unsetConfig(
+  worktreePath: string,
+  name: string,
+  scope: ConfigScope
+): Promise<void> {
+  return invoke("unset_config", { worktreePath, name, scope });
+}
+
+export async function worktreeConfigEnabled(worktreePath: string): Promise<boolean> {
+  return invoke("worktree_config_enabled", { worktreePath });
+}
+
+export async function listTrash(repoPath: string): Promise<TrashEntry[]> {
+  return invoke("list_trash", { repoPath });
+}
+
+export async function restoreTrash(
+  repoPath: string,
+  name: string,
+  worktreePath: string
+): Promise<void> {
+  return invoke("restore_trash", { repoPath, name, worktreePath });
+}
+
+export async function purgeTrash(
+  repoPath: string,
+  retentionDays: number
+): Promise<string[]> {
+  return invoke("purge_trash", { repoPath, retentionDays });
+}
+
+export async function queryAuditLog(
+  repoPath: string,
+  query?: AuditQuery
+): Promise<AuditEntry[]> {
+  return invoke("query_audit_log", { repoPath, query });
+}
+
+export async function exportAuditLog(
+  repoPath: string,
+  destination: string,
+  query?: AuditQuery
+): Promise<number> {
+  return invoke("export_audit_log", { repoPath, destination, query });
+}
+
+export async function rebasePlan(
+  worktreePath: string,
+  upstream: string
+): Promise<RebaseStep[]> {
+  return invoke("rebase_plan", { worktreePath, upstream });
+}
+
+export async function rebaseExecute(
+  worktreePath: string,
+  upstream: string,
+  plan: RebaseStep[]
+): Promise<RebaseProgress> {
+  return invoke("rebase_execute", { worktreePath, upstream, plan });
+}
+
+export async function rebaseProgress(
+  worktreePath: string
+): Promise<RebaseProgress | null> {
+  return invoke("rebase_progress", { worktreePath });
+}
+
+export async function rebaseContinue(
+  worktreePath: string
+): Promise<RebaseProgress> {
+  return invoke("rebase_continue", { worktreePath });
+}
+
+export async function rebaseAbort(worktreePath: string): Promise<void> {
+  return invoke("rebase_abort", { worktreePath });
+}
+
+export async function lastUndoEntry(
+  repoPath: string
+): Promise<UndoEntry | null> {
+  return invoke("last_undo_entry", { repoPath });
+}
+
+export async function undoLastOperation(
+  repoPath: string
+): Promise<UndoResult> {
+  return invoke("undo_last_operation", { repoPath });
+}
+
+export async function readReflog(
+  worktreePath: PathArg,
+  reference?: string,
+  limit?: number
+): Promise<ReflogEntry[]> {
+  return invoke("read_reflog", { worktreePath, reference, limit });
+}
+
+export async function createBranchFromReflog(
+  worktreePath: string,
+  reference: string | undefined,
+  index: number,
+  name: string
+): Promise<void> {
+  return invoke("create_branch_from_reflog", {
+    worktreePath,
+    reference,
+    index,
+    name,
+  });
+}
+
+export async function addWorktreeFromReflog(
+  repoPath: string,
+  reference: string | undefined,
+  index: number,
+  worktreePath: PathArg,
   branch: string
 ): Promise<void> {
-  return invoke("checkout_branch", { worktreePath, branch });
+  return invoke("add_worktree_from_reflog", {
+    repoPath,
+    reference,
+    index,
+    worktreePath,
+    branch,
+  });
 }
@@ -1,5 +1,6 @@
 import type { ReactNode } from 'react';
 import { QueryClient, QueryClientProvider } from '@tanstack/react-query';
+import type { BranchInfo } from '@/lib/tauri';
 
 export function createTestQueryClient() {
   return new QueryClient({
@@ -25,3 +26,22 @@ export function createQueryWrapper() {
     return <QueryClientProvider client={queryClient}>{children}</QueryClientProvider>;
   };
 }
+
+export function mockBranch(
+  name: string,
+  overrides: Partial<BranchInfo> = {}
+): BranchInfo {
+  return {
+    name,
+    is_remote: false,
+    is_current: false,
+    upstream: null,
+    upstream_gone: false,
+    ahead: 0,
+    behind: 0,
+    last_commit_summary: null,
+    last_commit_time: null,
+    worktree_path: null,
+    ...overrides,
+  };
+}